@@ -0,0 +1,214 @@
+//! Estimates the bridge fee a transfer to Ethereum needs to attach to be included in the next
+//! batch relayers consider profitable. Relayers compare the WETH value of a batch's fees to the
+//! cost of submitting it, so the suggestion is the fee that tops up the pending pool for a token
+//! to that cost, see `should_relay_batch` in the relayer.
+
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{
+    clarity::{u256, Address as EthAddress, Uint256},
+    error::GravityError,
+    num_conversion::DecimalAmount,
+    prices::get_weth_price,
+    web30::client::Web3,
+};
+use tonic::transport::Channel;
+
+use crate::query::{get_current_valset, get_denom_to_erc20, get_pending_batch_fees};
+
+/// The margin over the cost of submitting a batch used when the caller has no better
+/// estimate of what relayers expect, this matches the default relayer config
+pub const DEFAULT_BRIDGE_FEE_MARGIN: f64 = 1.1;
+
+// The batch gas constants are upper bounds worked out from the EVM gas schedule (Berlin and
+// later) for `submitBatch` in Gravity.sol, rounded up. They are only used to suggest a fee,
+// relayers simulate the actual batch before deciding to relay it, see `estimate_tx_batch_cost`
+
+/// Gas for a submitBatch call that does not depend on the batch contents: the 21000 intrinsic
+/// gas, hashing the validator set checkpoint, updating the batch nonce (5000) and the
+/// TransactionBatchExecutedEvent, about 60000 plus calldata headers
+const BATCH_BASE_GAS: Uint256 = u256!(100_000);
+/// Gas per member of the validator set: address, power and signature calldata (about 160
+/// bytes at 16 gas each) plus the 3000 gas ecrecover precompile for a set signature
+const BATCH_GAS_PER_VALIDATOR: Uint256 = u256!(8_000);
+/// Gas per transfer in the batch: destination, amount and fee calldata (about 1500), the
+/// ERC20 transfer with a cold account access (2600), a new holder's balance slot going from
+/// zero to non-zero (22100), the sender's balance update (2900) and the Transfer event
+const BATCH_GAS_PER_TX: Uint256 = u256!(40_000);
+
+/// A suggested bridge fee along with the inputs it was derived from
+#[derive(Debug, Clone)]
+pub struct BridgeFeeSuggestion {
+    /// the denom of the transfer on Cosmos
+    pub denom: String,
+    /// the ERC20 representing the denom on Ethereum
+    pub token: EthAddress,
+    /// the decimals of the ERC20, used to display the amounts
+    pub decimals: u8,
+    /// the current Ethereum gas price
+    pub gas_price: Uint256,
+    /// the estimated gas to submit a batch of the pending pool plus this transfer
+    pub batch_gas: Uint256,
+    /// the cost of submitting that batch in wei, including the relayer margin
+    pub batch_cost: Uint256,
+    /// the number of transfers of this token waiting to be batched
+    pub pool_txs: u64,
+    /// the fees of the transfers waiting to be batched, in the smallest unit of the token
+    pub pool_fees: Uint256,
+    /// the value of `pool_fees` in wei
+    pub pool_fees_weth: Uint256,
+    /// the suggested bridge fee in the smallest unit of the token
+    pub fee: Uint256,
+}
+
+/// Estimates the gas used by submitBatch on the Gravity contract for a batch
+/// of `txs` transfers signed by a validator set with `validators` members
+pub fn estimate_batch_gas(validators: usize, txs: u64) -> Uint256 {
+    let validators = Uint256::from_u64(validators as u64);
+    let txs = Uint256::from_u64(txs);
+    BATCH_BASE_GAS
+        .checked_add(BATCH_GAS_PER_VALIDATOR.checked_mul(validators).unwrap())
+        .unwrap()
+        .checked_add(BATCH_GAS_PER_TX.checked_mul(txs).unwrap())
+        .unwrap()
+}
+
+/// Suggests a bridge fee for a transfer of `denom` to Ethereum. If the fees already waiting
+/// in the pool for the token pay for a batch the suggestion is the average pool fee, otherwise it
+/// is the amount missing for a relayer expecting `margin` times its gas cost to submit the batch.
+/// `caller` is only used to query Uniswap prices and needs no balance
+pub async fn suggest_bridge_fee(
+    grpc_client: &mut GravityQueryClient<Channel>,
+    web3: &Web3,
+    denom: String,
+    caller: EthAddress,
+    margin: f64,
+) -> Result<BridgeFeeSuggestion, GravityError> {
+    let token: EthAddress = get_denom_to_erc20(grpc_client, denom.clone())
+        .await?
+        .erc20
+        .parse()?;
+    let decimals = web3.get_erc20_decimals(token, caller).await?;
+    let decimals: u8 = decimals.to_string().parse().map_err(|_| {
+        GravityError::ValidationError(format!("ERC20 {token} has invalid decimals {decimals}"))
+    })?;
+
+    let gas_price = web3.eth_gas_price().await?;
+    let valset = get_current_valset(grpc_client).await?;
+
+    let mut pool_fees = u256!(0);
+    let mut pool_txs = 0;
+    for fee in get_pending_batch_fees(grpc_client).await?.batch_fees {
+        if fee.token.parse::<EthAddress>()? == token {
+            pool_fees = Uint256::from_dec_or_hex_str_restricted(&fee.total_fees)?;
+            pool_txs = fee.tx_count;
+        }
+    }
+
+    let batch_gas = estimate_batch_gas(valset.members.len(), pool_txs + 1);
+    let batch_cost = cost_with_margin(gas_price.checked_mul(batch_gas).unwrap(), margin);
+    let pool_fees_weth = get_weth_price(token, pool_fees, caller, web3).await?;
+
+    let fee = if pool_fees_weth >= batch_cost && pool_txs > 0 {
+        // the pool already pays for a batch, matching the average fee keeps this
+        // transfer from being the first one left out of it
+        average_fee(pool_fees, pool_txs)
+    } else {
+        let one_token = DecimalAmount::from_base_units(u256!(1), 0).to_base_units(decimals)?;
+        let one_token_weth = get_weth_price(token, one_token, caller, web3).await?;
+        if one_token_weth.is_zero() {
+            return Err(GravityError::ValidationError(format!(
+                "{denom} has no WETH price on Uniswap, a bridge fee can not be suggested"
+            )));
+        }
+        missing_fee(batch_cost, pool_fees_weth, one_token, one_token_weth)
+    };
+
+    Ok(BridgeFeeSuggestion {
+        denom,
+        token,
+        decimals,
+        gas_price,
+        batch_gas,
+        batch_cost,
+        pool_txs,
+        pool_fees,
+        pool_fees_weth,
+        fee,
+    })
+}
+
+/// One more than the average fee of the `pool_txs` transfers in the pool
+fn average_fee(pool_fees: Uint256, pool_txs: u64) -> Uint256 {
+    pool_fees
+        .divide(Uint256::from_u64(pool_txs))
+        .unwrap()
+        .0
+        .checked_add(u256!(1))
+        .unwrap()
+}
+
+/// The amount of a token worth the part of `batch_cost` the pool does not pay for, given the
+/// WETH value of one whole token. Rounded up so the suggestion is never short of the cost
+fn missing_fee(
+    batch_cost: Uint256,
+    pool_fees_weth: Uint256,
+    one_token: Uint256,
+    one_token_weth: Uint256,
+) -> Uint256 {
+    let missing_weth = batch_cost.checked_sub(pool_fees_weth).unwrap_or(u256!(0));
+    let (fee, remainder) = missing_weth
+        .checked_mul(one_token)
+        .unwrap()
+        .divide(one_token_weth)
+        .unwrap();
+    if remainder.is_zero() {
+        fee
+    } else {
+        fee.checked_add(u256!(1)).unwrap()
+    }
+}
+
+/// bakes the margin into the cost, the same way relayers do when deciding to relay
+fn cost_with_margin(cost: Uint256, margin: f64) -> Uint256 {
+    let cost_as_float: f64 = cost.to_string().parse().unwrap();
+    let cost_with_margin = cost_as_float * margin;
+    Uint256::from_u128(cost_with_margin as u128)
+}
+
+#[test]
+fn test_estimate_batch_gas() {
+    assert_eq!(estimate_batch_gas(0, 0), u256!(100_000));
+    // 100000 + 4 * 8000 + 1 * 40000
+    assert_eq!(estimate_batch_gas(4, 1), u256!(172_000));
+    // 100000 + 125 * 8000 + 100 * 40000
+    assert_eq!(estimate_batch_gas(125, 100), u256!(5_100_000));
+    // every transfer and validator adds to the estimate
+    assert!(estimate_batch_gas(5, 1) > estimate_batch_gas(4, 1));
+    assert!(estimate_batch_gas(4, 2) > estimate_batch_gas(4, 1));
+}
+
+#[test]
+fn test_bridge_fee_math() {
+    assert_eq!(cost_with_margin(u256!(1_000_000), 1.1), u256!(1_100_000));
+    assert_eq!(cost_with_margin(u256!(1_000_000), 1.0), u256!(1_000_000));
+
+    assert_eq!(average_fee(u256!(300), 3), u256!(101));
+    assert_eq!(average_fee(u256!(10), 3), u256!(4));
+
+    let one_token = u256!(1_000_000);
+    // one token is worth 2 wei of WETH, 10 wei are missing so 5 tokens are needed
+    assert_eq!(
+        missing_fee(u256!(15), u256!(5), one_token, u256!(2)),
+        u256!(5_000_000)
+    );
+    // rounds up when the cost doesn't divide evenly
+    assert_eq!(
+        missing_fee(u256!(10), u256!(0), one_token, u256!(3)),
+        u256!(3_333_334)
+    );
+    // a pool worth more than the cost leaves nothing missing
+    assert_eq!(
+        missing_fee(u256!(10), u256!(20), one_token, u256!(3)),
+        u256!(0)
+    );
+}
@@ -6,6 +6,7 @@
 #[macro_use]
 extern crate log;
 
+pub mod fees;
 pub mod proposals;
 pub mod query;
 pub mod send;
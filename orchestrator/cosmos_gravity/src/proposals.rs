@@ -1,6 +1,6 @@
 //! This file handles submitting and querying governance proposals custom to Gravity bridge
 
-use std::{convert::TryFrom, time::Duration};
+use std::{collections::HashSet, convert::TryFrom, time::Duration};
 
 use gravity_proto::{
     cosmos_sdk_proto::cosmos::{
@@ -10,10 +10,13 @@ use gravity_proto::{
     },
     gravity::{AirdropProposal as AirdropProposalMsg, IbcMetadataProposal, UnhaltBridgeProposal},
 };
-use gravity_utils::deep_space::{
-    error::{AddressError, CosmosGrpcError},
-    utils::encode_any,
-    Address, Coin, Contact, PrivateKey,
+use gravity_utils::{
+    deep_space::{
+        error::{AddressError, CosmosGrpcError},
+        utils::encode_any,
+        Address, Coin, Contact, PrivateKey,
+    },
+    error::GravityError,
 };
 use serde::{Deserialize, Serialize};
 
@@ -61,6 +64,129 @@ impl TryFrom<AirdropProposalJsonUnparsed> for AirdropProposalJson {
     }
 }
 
+impl AirdropProposalJsonUnparsed {
+    /// Checks the proposal without contacting the chain, every recipient must be a valid
+    /// address that appears once and have a non zero amount
+    pub fn validate(&self) -> Result<(), GravityError> {
+        validate_title(&self.title, &self.description)?;
+        if self.denom.is_empty() {
+            return Err(GravityError::ValidationError(
+                "The airdrop has no denom".to_string(),
+            ));
+        }
+        if self.recipients.is_empty() {
+            return Err(GravityError::ValidationError(
+                "The airdrop has no recipients".to_string(),
+            ));
+        }
+        if self.recipients.len() != self.amounts.len() {
+            return Err(GravityError::ValidationError(format!(
+                "The airdrop has {} recipients but {} amounts, each recipient needs an amount",
+                self.recipients.len(),
+                self.amounts.len()
+            )));
+        }
+        let mut seen = HashSet::new();
+        for (i, (recipient, amount)) in self.recipients.iter().zip(self.amounts.iter()).enumerate()
+        {
+            let address: Address = recipient.parse().map_err(|e| {
+                GravityError::ValidationError(format!(
+                    "Recipient {i} {recipient} is not a valid address {e:?}"
+                ))
+            })?;
+            if !seen.insert(address.as_bytes().to_vec()) {
+                return Err(GravityError::ValidationError(format!(
+                    "Recipient {i} {recipient} appears more than once"
+                )));
+            }
+            if *amount == 0 {
+                return Err(GravityError::ValidationError(format!(
+                    "Recipient {i} {recipient} has an amount of zero"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The sum of all amounts, which is paid from the community pool
+    pub fn total(&self) -> u128 {
+        self.amounts.iter().map(|a| *a as u128).sum()
+    }
+
+    /// Estimates the size in bytes of the transaction submitting this airdrop
+    pub fn estimated_tx_size(&self) -> usize {
+        self.fixed_tx_size()
+            + self
+                .amounts
+                .iter()
+                .map(|a| airdrop_recipient_size(*a))
+                .sum::<usize>()
+    }
+
+    /// Splits the airdrop into as few proposals as keep each transaction under `max_tx_bytes`,
+    /// the order of recipients is kept and each part's title says which part it is
+    pub fn split(
+        self,
+        max_tx_bytes: usize,
+    ) -> Result<Vec<AirdropProposalJsonUnparsed>, GravityError> {
+        if self.estimated_tx_size() <= max_tx_bytes {
+            return Ok(vec![self]);
+        }
+        // room for the part number added to the title
+        let fixed = self.fixed_tx_size() + " (part 9999 of 9999)".len();
+        let mut parts: Vec<(Vec<String>, Vec<u64>)> = Vec::new();
+        let mut size = fixed;
+        for (recipient, amount) in self.recipients.into_iter().zip(self.amounts.into_iter()) {
+            let recipient_size = airdrop_recipient_size(amount);
+            if fixed + recipient_size > max_tx_bytes {
+                return Err(GravityError::ValidationError(format!(
+                    "A transaction of {max_tx_bytes} bytes is too small for even one airdrop recipient"
+                )));
+            }
+            if parts.is_empty() || size + recipient_size > max_tx_bytes {
+                parts.push((Vec::new(), Vec::new()));
+                size = fixed;
+            }
+            let part = parts.last_mut().unwrap();
+            part.0.push(recipient);
+            part.1.push(amount);
+            size += recipient_size;
+        }
+        let count = parts.len();
+        Ok(parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, (recipients, amounts))| AirdropProposalJsonUnparsed {
+                title: format!("{} (part {} of {count})", self.title, i + 1),
+                denom: self.denom.clone(),
+                description: self.description.clone(),
+                amounts,
+                recipients,
+            })
+            .collect())
+    }
+
+    fn fixed_tx_size(&self) -> usize {
+        // field tags and length prefixes of the proposal, the recipients and amounts fields
+        // are length prefixed with at most 5 bytes each
+        AIRDROP_TX_OVERHEAD_BYTES
+            + self.title.len()
+            + self.description.len()
+            + self.denom.len()
+            + 32
+    }
+}
+
+/// Bytes of a transaction submitting an airdrop that do not depend on the proposal contents,
+/// the signature, public key, fee, deposit and message wrapping, with room to spare
+const AIRDROP_TX_OVERHEAD_BYTES: usize = 1024;
+
+/// Each recipient is stored as 20 address bytes and a varint encoded amount
+fn airdrop_recipient_size(amount: u64) -> usize {
+    let varint_len = (64 - (amount | 1).leading_zeros() as usize + 6) / 7;
+    20 + varint_len
+}
+
 /// Encodes and submits an airdrop proposal provided the json file
 pub async fn submit_airdrop_proposal(
     proposal: AirdropProposalJson,
@@ -108,6 +234,20 @@ impl From<UnhaltBridgeProposalJson> for UnhaltBridgeProposal {
     }
 }
 
+impl UnhaltBridgeProposalJson {
+    /// Checks the proposal without contacting the chain
+    pub fn validate(&self) -> Result<(), GravityError> {
+        validate_title(&self.title, &self.description)?;
+        if self.target_nonce == 0 {
+            return Err(GravityError::ValidationError(
+                "target_nonce must be the last event nonce all validators agree on, it can not be zero"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Encodes and submits a proposal to reset the bridge oracle to a specific nonce that has
 /// not yet been observed
 pub async fn submit_unhalt_bridge_proposal(
@@ -133,6 +273,13 @@ pub struct PauseBridgeProposalJson {
     pub paused: bool,
 }
 
+impl PauseBridgeProposalJson {
+    /// Checks the proposal without contacting the chain
+    pub fn validate(&self) -> Result<(), GravityError> {
+        validate_title(&self.title, &self.description)
+    }
+}
+
 /// Submit a parameter change proposal to temporarily halt some operations of the bridge
 pub async fn submit_pause_bridge_proposal(
     proposal: PauseBridgeProposalJson,
@@ -182,6 +329,67 @@ pub struct IbcMetadataProposalJson {
     metadata: MetadataJson,
     ibc_denom: String,
 }
+impl IbcMetadataProposalJson {
+    /// Checks the proposal without contacting the chain. The base denom must be the `ibc/`
+    /// hash being described, the denom units must start at the base with exponent zero
+    /// and increase from there and the display denom must be one of them
+    pub fn validate(&self) -> Result<(), GravityError> {
+        validate_title(&self.title, &self.description)?;
+        let invalid = |msg: String| Err(GravityError::ValidationError(msg));
+        let hash = self.ibc_denom.strip_prefix("ibc/").unwrap_or_default();
+        if hash.len() != 64
+            || !hash
+                .chars()
+                .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase())
+        {
+            return invalid(format!(
+                "ibc_denom {} is not an IBC denom, expected ibc/ followed by an uppercase hex hash",
+                self.ibc_denom
+            ));
+        }
+        let metadata = &self.metadata;
+        if metadata.base != self.ibc_denom {
+            return invalid(format!(
+                "metadata.base {} must be the ibc_denom {}",
+                metadata.base, self.ibc_denom
+            ));
+        }
+        if metadata.name.is_empty() || metadata.symbol.is_empty() {
+            return invalid(
+                "metadata.name and metadata.symbol are used for the ERC20 and can not be empty"
+                    .to_string(),
+            );
+        }
+        match metadata.denom_units.first() {
+            Some(unit) if unit.denom == metadata.base && unit.exponent == 0 => {}
+            _ => {
+                return invalid(format!(
+                    "The first of metadata.denom_units must be the base denom {} with exponent 0",
+                    metadata.base
+                ))
+            }
+        }
+        for pair in metadata.denom_units.windows(2) {
+            if pair[1].exponent <= pair[0].exponent {
+                return invalid(format!(
+                    "metadata.denom_units must be ordered by increasing exponent, {} has exponent {} after {} with exponent {}",
+                    pair[1].denom, pair[1].exponent, pair[0].denom, pair[0].exponent
+                ));
+            }
+        }
+        if !metadata
+            .denom_units
+            .iter()
+            .any(|u| u.denom == metadata.display)
+        {
+            return invalid(format!(
+                "metadata.display {} is not one of the metadata.denom_units",
+                metadata.display
+            ));
+        }
+        Ok(())
+    }
+}
 impl From<IbcMetadataProposalJson> for IbcMetadataProposal {
     fn from(v: IbcMetadataProposalJson) -> Self {
         IbcMetadataProposal {
@@ -243,3 +451,13 @@ pub async fn submit_ibc_metadata_proposal(
         .create_gov_proposal(any, deposit, fee, key, wait_timeout)
         .await
 }
+
+/// Every proposal needs a title and description to be accepted by the gov module
+fn validate_title(title: &str, description: &str) -> Result<(), GravityError> {
+    if title.trim().is_empty() || description.trim().is_empty() {
+        return Err(GravityError::ValidationError(
+            "The proposal needs a title and a description".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -1,15 +1,15 @@
-use std::convert::TryFrom;
+use std::{cmp::Reverse, convert::TryFrom};
 
 use gravity_proto::gravity::{
     query_client::QueryClient as GravityQueryClient, Attestation, Params, QueryAttestationsRequest,
     QueryBatchConfirmsRequest, QueryBatchFeeRequest, QueryBatchFeeResponse,
-    QueryCurrentValsetRequest, QueryDenomToErc20Request, QueryDenomToErc20Response,
-    QueryErc20ToDenomRequest, QueryErc20ToDenomResponse, QueryLastEventNonceByAddrRequest,
-    QueryLastPendingBatchRequestByAddrRequest, QueryLastPendingLogicCallByAddrRequest,
-    QueryLastPendingValsetRequestByAddrRequest, QueryLastValsetRequestsRequest,
-    QueryLogicConfirmsRequest, QueryOutgoingLogicCallsRequest, QueryOutgoingTxBatchesRequest,
-    QueryParamsRequest, QueryPendingSendToEth, QueryPendingSendToEthResponse,
-    QueryValsetConfirmsByNonceRequest, QueryValsetRequestRequest,
+    QueryCurrentValsetRequest, QueryDelegateKeysByEthAddress, QueryDenomToErc20Request,
+    QueryDenomToErc20Response, QueryErc20ToDenomRequest, QueryErc20ToDenomResponse,
+    QueryLastEventNonceByAddrRequest, QueryLastPendingBatchRequestByAddrRequest,
+    QueryLastPendingLogicCallByAddrRequest, QueryLastPendingValsetRequestByAddrRequest,
+    QueryLastValsetRequestsRequest, QueryLogicConfirmsRequest, QueryOutgoingLogicCallsRequest,
+    QueryOutgoingTxBatchesRequest, QueryParamsRequest, QueryPendingSendToEth,
+    QueryPendingSendToEthResponse, QueryValsetConfirmsByNonceRequest, QueryValsetRequestRequest,
 };
 use gravity_utils::{
     clarity::Address as EthAddress, deep_space::address::Address, error::GravityError, types::*,
@@ -76,6 +76,50 @@ pub async fn get_oldest_unsigned_valsets(
         .collect())
 }
 
+/// The Cosmos block heights at which the oldest valset, batch and logic call we have
+/// not signed were created, None when there is nothing of that kind to sign
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnsignedHeights {
+    pub valset: Option<u64>,
+    pub batch: Option<u64>,
+    pub logic_call: Option<u64>,
+}
+
+/// Gets the creation heights of everything `address` has not signed yet, the heights are
+/// not part of the parsed types so this reads the responses directly
+pub async fn get_oldest_unsigned_heights(
+    client: &mut GravityQueryClient<Channel>,
+    address: Address,
+    prefix: String,
+) -> Result<UnsignedHeights, GravityError> {
+    let address = address.to_bech32(prefix).unwrap();
+    let valsets = client
+        .last_pending_valset_request_by_addr(QueryLastPendingValsetRequestByAddrRequest {
+            address: address.clone(),
+        })
+        .await?
+        .into_inner()
+        .valsets;
+    let batches = client
+        .last_pending_batch_request_by_addr(QueryLastPendingBatchRequestByAddrRequest {
+            address: address.clone(),
+        })
+        .await?
+        .into_inner()
+        .batch;
+    let calls = client
+        .last_pending_logic_call_by_addr(QueryLastPendingLogicCallByAddrRequest { address })
+        .await?
+        .into_inner()
+        .call;
+
+    Ok(UnsignedHeights {
+        valset: valsets.iter().map(|v| v.height).min(),
+        batch: batches.iter().map(|b| b.block).min(),
+        logic_call: calls.iter().map(|c| c.block).min(),
+    })
+}
+
 /// this input views the last five valset requests that have been made, useful if you're
 /// a relayer looking to ferry confirmations
 pub async fn get_latest_valsets(
@@ -182,6 +226,53 @@ pub async fn get_last_event_nonce_for_validator(
     Ok(response.into_inner().event_nonce)
 }
 
+/// Mirrors AttestationVotesPowerThreshold in the module, the percentage of the voting power that
+/// has to attest to an event for it to be observed
+const ATTESTATION_POWER_THRESHOLD_PERCENT: u128 = 66;
+
+/// Gets the last Ethereum event nonce observed on Cosmos. The module keeps this nonce to itself,
+/// so it is worked out from the last event nonce each member of the current validator set has
+/// attested to, the same way the module observes an event
+pub async fn get_last_observed_event_nonce(
+    client: &mut GravityQueryClient<Channel>,
+) -> Result<u64, GravityError> {
+    let valset = get_current_valset(client).await?;
+    let mut attested = Vec::new();
+    for member in valset.members {
+        let keys = client
+            .get_delegate_key_by_eth(QueryDelegateKeysByEthAddress {
+                eth_address: member.eth_address.to_string(),
+            })
+            .await?
+            .into_inner();
+        let event_nonce = client
+            .last_event_nonce_by_addr(QueryLastEventNonceByAddrRequest {
+                address: keys.orchestrator_address,
+            })
+            .await?
+            .into_inner()
+            .event_nonce;
+        attested.push((member.power, event_nonce));
+    }
+    Ok(observed_event_nonce(attested))
+}
+
+/// Validators attest to event nonces strictly in order, so the highest nonce reached by
+/// validators holding more than the attestation threshold of `(power, last event nonce)` is the
+/// last observed one
+fn observed_event_nonce(mut attested: Vec<(u64, u64)>) -> u64 {
+    let total_power: u128 = attested.iter().map(|(power, _)| u128::from(*power)).sum();
+    attested.sort_by_key(|(_, event_nonce)| Reverse(*event_nonce));
+    let mut power = 0u128;
+    for (member_power, event_nonce) in attested {
+        power += u128::from(member_power);
+        if power * 100 > total_power * ATTESTATION_POWER_THRESHOLD_PERCENT {
+            return event_nonce;
+        }
+    }
+    0
+}
+
 /// Gets the 100 latest logic calls for a relayer to consider relaying
 pub async fn get_latest_logic_calls(
     client: &mut GravityQueryClient<Channel>,
@@ -300,3 +391,19 @@ pub async fn get_pending_batch_fees(
     let request = client.batch_fees(QueryBatchFeeRequest {}).await?;
     Ok(request.into_inner())
 }
+
+#[test]
+fn test_observed_event_nonce() {
+    assert_eq!(observed_event_nonce(vec![]), 0);
+    assert_eq!(observed_event_nonce(vec![(100, 7)]), 7);
+    // 70% of the power has attested to 10, the rest is behind
+    assert_eq!(observed_event_nonce(vec![(30, 4), (40, 10), (30, 12)]), 10);
+    // exactly 66% is not enough, the module requires more than the threshold
+    assert_eq!(observed_event_nonce(vec![(66, 10), (34, 3)]), 3);
+    assert_eq!(observed_event_nonce(vec![(67, 10), (33, 3)]), 10);
+    // powers near the normalized total do not overflow
+    assert_eq!(
+        observed_event_nonce(vec![(u64::MAX, 5), (u64::MAX, 9), (u64::MAX, 2)]),
+        5
+    );
+}
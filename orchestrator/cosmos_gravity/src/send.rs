@@ -15,6 +15,7 @@ use gravity_proto::{
 use gravity_utils::{
     clarity::{
         constants::ZERO_ADDRESS, Address as EthAddress, PrivateKey as EthPrivateKey, Signature,
+        Uint256,
     },
     deep_space::{
         address::Address, coin::Coin, error::CosmosGrpcError, private_key::PrivateKey,
@@ -364,6 +365,115 @@ pub async fn send_to_eth(
         .await
 }
 
+/// A single transfer to Ethereum as sent by `send_to_eth_bulk`
+#[derive(Debug, Clone)]
+pub struct SendToEthTransfer {
+    pub destination: EthAddress,
+    pub amount: Coin,
+    pub bridge_fee: Coin,
+}
+
+/// Sends many transfers to Ethereum in a single transaction, one MsgSendToEth per transfer.
+/// The transfers are assigned increasing ids in the order they are given. Every transfer
+/// must pay its bridge fee in the denom it sends and the sender must hold the sum of all
+/// amounts, bridge fees and the transaction fee
+pub async fn send_to_eth_bulk(
+    private_key: PrivateKey,
+    transfers: &[SendToEthTransfer],
+    fee: Coin,
+    contact: &Contact,
+) -> Result<TxResponse, CosmosGrpcError> {
+    let our_address = private_key.to_address(&contact.get_prefix()).unwrap();
+    if transfers.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "No transfers to send to Ethereum".to_owned(),
+        ));
+    }
+
+    let mut totals: BTreeMap<String, Uint256> = BTreeMap::new();
+    totals.insert(fee.denom.clone(), fee.amount);
+    for transfer in transfers {
+        if transfer.amount.denom != transfer.bridge_fee.denom {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "{} {} is an invalid denom set for SendToEth you must pay fees in the same token your sending",
+                transfer.amount.denom, transfer.bridge_fee.denom,
+            )));
+        }
+        let total = totals
+            .entry(transfer.amount.denom.clone())
+            .or_insert(Uint256::from_u64(0));
+        *total = total
+            .checked_add(transfer.amount.amount)
+            .and_then(|t| t.checked_add(transfer.bridge_fee.amount))
+            .ok_or_else(|| {
+                CosmosGrpcError::BadInput("overflow of `U256::checked_add`".to_owned())
+            })?;
+    }
+
+    let balances = contact.get_balances(our_address).await?;
+    for (denom, total) in totals {
+        match balances.iter().find(|b| b.denom == denom) {
+            Some(balance) if balance.amount >= total => {}
+            Some(_) => {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "Insufficient balance of {denom} to send {total}",
+                )))
+            }
+            None => {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "No balance of {denom} to send",
+                )))
+            }
+        }
+    }
+
+    contact
+        .send_message(
+            &send_to_eth_msgs(&our_address, transfers),
+            Some(MEMO.to_string()),
+            &[fee],
+            Some(TIMEOUT),
+            private_key,
+        )
+        .await
+}
+
+/// Simulates sending `transfers` in a single transaction, returning the gas it uses
+pub async fn simulate_send_to_eth_bulk(
+    private_key: PrivateKey,
+    transfers: &[SendToEthTransfer],
+    contact: &Contact,
+) -> Result<u64, CosmosGrpcError> {
+    let our_address = private_key.to_address(&contact.get_prefix()).unwrap();
+    let res = contact
+        .simulate_tx(&send_to_eth_msgs(&our_address, transfers), private_key)
+        .await?;
+    Ok(res.gas_info.map(|g| g.gas_used).unwrap_or_default())
+}
+
+/// The message sending `transfer` from `our_address`
+pub fn send_to_eth_msg(our_address: &Address, transfer: &SendToEthTransfer) -> MsgSendToEth {
+    MsgSendToEth {
+        sender: our_address.to_string(),
+        eth_dest: transfer.destination.to_string(),
+        amount: Some(transfer.amount.clone().into()),
+        bridge_fee: Some(transfer.bridge_fee.clone().into()),
+    }
+}
+
+/// One message per transfer, in order
+fn send_to_eth_msgs(our_address: &Address, transfers: &[SendToEthTransfer]) -> Vec<Msg> {
+    transfers
+        .iter()
+        .map(|transfer| {
+            Msg::new(
+                "/gravity.v1.MsgSendToEth",
+                send_to_eth_msg(our_address, transfer),
+            )
+        })
+        .collect()
+}
+
 pub async fn send_request_batch(
     private_key: PrivateKey,
     denom: String,
@@ -15,7 +15,7 @@ use gravity_utils::{
 
 use crate::{
     message_signatures::encode_logic_call_confirm_hashed,
-    utils::{encode_valset_struct, get_logic_call_nonce, GasCost},
+    utils::{encode_valset_struct, get_logic_call_nonce, GasCost, RelayResult},
 };
 
 /// this function generates an appropriate Ethereum transaction
@@ -30,7 +30,7 @@ pub async fn send_eth_logic_call(
     gravity_contract_address: EthAddress,
     gravity_id: String,
     our_eth_key: EthPrivateKey,
-) -> Result<(), GravityError> {
+) -> Result<RelayResult, GravityError> {
     let new_call_nonce = call.invalidation_nonce;
     let eth_address = our_eth_key.to_address();
     info!(
@@ -53,13 +53,13 @@ pub async fn send_eth_logic_call(
             "Someone else updated the LogicCall to {}, exiting early",
             before_nonce
         );
-        return Ok(());
+        return Ok(RelayResult::AlreadyRelayed);
     } else if current_block_height > Uint256::from_u64(call.timeout) {
         info!(
             "This LogicCall is timed out. timeout block: {} current block: {}, exiting early",
             current_block_height, call.timeout
         );
-        return Ok(());
+        return Ok(RelayResult::TimedOut);
     }
 
     let payload = encode_logic_call_payload(current_valset, &call, confirms, gravity_id)?;
@@ -74,7 +74,10 @@ pub async fn send_eth_logic_call(
             vec![],
         )
         .await?;
-    info!("Sent batch update with txid {:#066x}", tx);
+    info!(
+        subsystem = "relayer", nonce = new_call_nonce, txid = format!("{:#066x}", tx);
+        "Sent logic call with txid {:#066x}", tx
+    );
 
     web3.wait_for_transaction(tx, timeout, None).await?;
 
@@ -90,13 +93,14 @@ pub async fn send_eth_logic_call(
             "Current nonce is {} expected to update to nonce {}",
             last_nonce, new_call_nonce
         );
+        Ok(RelayResult::NotUpdated)
     } else {
         info!(
             "Successfully updated LogicCall with new Nonce {:?}",
             last_nonce
         );
+        Ok(RelayResult::Relayed)
     }
-    Ok(())
 }
 
 /// Returns the cost in Eth of sending this batch
@@ -226,6 +226,7 @@ mod test {
             }],
             total_fee: token,
             token_contract: erc20_addr,
+            block: 0,
         };
 
         let checkpoint = encode_tx_batch_confirm("foo".to_string(), &batch);
@@ -273,6 +274,7 @@ mod test {
             }],
             total_fee: token,
             token_contract: erc20_addr,
+            block: 0,
         };
 
         let mut rng = rand::thread_rng();
@@ -10,11 +10,25 @@ use gravity_utils::{
     deep_space::address::Address as CosmosAddress,
     error::GravityError,
     u64_array_bigints,
-    web30::{client::Web3, types::SendTxOption},
+    web30::{client::Web3, jsonrpc::error::Web3Error, types::SendTxOption},
 };
 
 pub const SEND_TO_COSMOS_GAS_LIMIT: Uint256 = u256!(100_000);
+pub const WRAP_ETH_GAS_LIMIT: Uint256 = u256!(50_000);
 
+/// How the Gravity contract is allowed to move the tokens being sent to Cosmos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erc20Approval {
+    /// Approve the maximum amount once, later sends of the same token need no approval
+    Unlimited,
+    /// Approve exactly the amount being sent whenever the current allowance is lower,
+    /// this costs an approval transaction for most sends. A leftover non-zero allowance
+    /// is reset to zero first, as tokens such as USDT require
+    Exact,
+}
+
+/// Sends tokens to Cosmos, approving the Gravity contract for the maximum amount
+/// of the token first if it has not been approved yet
 #[allow(clippy::too_many_arguments)]
 pub async fn send_to_cosmos(
     erc20: Address,
@@ -25,6 +39,34 @@ pub async fn send_to_cosmos(
     wait_timeout: Duration,
     web3: &Web3,
     options: Vec<SendTxOption>,
+) -> Result<Uint256, GravityError> {
+    send_to_cosmos_with_approval(
+        erc20,
+        gravity_contract,
+        amount,
+        cosmos_destination,
+        sender_secret,
+        Erc20Approval::Unlimited,
+        wait_timeout,
+        web3,
+        options,
+    )
+    .await
+}
+
+/// Sends tokens to Cosmos, approving the Gravity contract as set by `approval` first
+/// if its current allowance does not cover the amount
+#[allow(clippy::too_many_arguments)]
+pub async fn send_to_cosmos_with_approval(
+    erc20: Address,
+    gravity_contract: Address,
+    amount: Uint256,
+    cosmos_destination: CosmosAddress,
+    sender_secret: EthPrivateKey,
+    approval: Erc20Approval,
+    wait_timeout: Duration,
+    web3: &Web3,
+    options: Vec<SendTxOption>,
 ) -> Result<Uint256, GravityError> {
     let sender_address = sender_secret.to_address();
 
@@ -55,17 +97,53 @@ pub async fn send_to_cosmos(
     // retries in a way that assists our transaction stress test
     let check_and_approve_erc20_transfer = async {
         loop {
-            if let Ok(approved) = web3
-                .check_erc20_approved(erc20, sender_address, gravity_contract)
-                .await
-            {
-                if !approved {
+            let allowance =
+                match get_erc20_allowance(erc20, sender_address, gravity_contract, web3).await {
+                    Ok(allowance) => allowance,
+                    Err(_) => continue,
+                };
+            let approved = match approval {
+                Erc20Approval::Unlimited => {
+                    match web3
+                        .check_erc20_approved(erc20, sender_address, gravity_contract)
+                        .await
+                    {
+                        Ok(approved) => approved,
+                        Err(_) => continue,
+                    }
+                }
+                Erc20Approval::Exact => allowance >= amount,
+            };
+            if !approved {
+                let mut next_nonce = nonce;
+                // tokens such as USDT refuse to change a non-zero allowance to another
+                // non-zero value, so an allowance that falls short is reset first
+                if !allowance.is_zero() {
                     info!(
-                        "Approving MAX {} from {} for gravity contract",
+                        "Resetting the {} allowance of {} for gravity contract to 0",
                         erc20, sender_address
                     );
-                    let txid = web3
-                        .approve_erc20_transfers(
+                    let txid = approve_erc20_amount(
+                        erc20,
+                        gravity_contract,
+                        u256!(0),
+                        &sender_secret,
+                        web3,
+                        options.clone(),
+                    )
+                    .await
+                    .expect("Can't reset the erc20 allowance within timeout");
+                    wait_for_approval(txid, wait_timeout, web3).await;
+                    next_nonce = next_nonce.checked_add(u256!(1)).unwrap();
+                    options.push(SendTxOption::Nonce(next_nonce));
+                }
+                let txid = match approval {
+                    Erc20Approval::Unlimited => {
+                        info!(
+                            "Approving MAX {} from {} for gravity contract",
+                            erc20, sender_address
+                        );
+                        web3.approve_erc20_transfers(
                             erc20,
                             &sender_secret,
                             gravity_contract,
@@ -73,24 +151,34 @@ pub async fn send_to_cosmos(
                             options.clone(),
                         )
                         .await
-                        .expect("Can't approve erc20 transfers within timeout");
-                    debug!(
-                        "We are not approved for ERC20 transfers, approving txid: {:#066x}",
-                        txid
-                    );
-                    web3.wait_for_transaction(txid, wait_timeout, None)
+                    }
+                    Erc20Approval::Exact => {
+                        info!(
+                            "Approving {} {} from {} for gravity contract",
+                            amount, erc20, sender_address
+                        );
+                        approve_erc20_amount(
+                            erc20,
+                            gravity_contract,
+                            amount,
+                            &sender_secret,
+                            web3,
+                            options.clone(),
+                        )
                         .await
-                        .unwrap_or_else(|_| {
-                            panic!(
-                                "Can't await for transaction within timeout, txid: {:#066x}",
-                                txid
-                            )
-                        });
-                    // increment the nonce for the next call
-                    options.push(SendTxOption::Nonce(nonce.checked_add(u256!(1)).unwrap()));
+                    }
                 }
-                break;
+                .expect("Can't approve erc20 transfers within timeout");
+                debug!(
+                    "We are not approved for ERC20 transfers, approving txid: {:#066x}",
+                    txid
+                );
+                wait_for_approval(txid, wait_timeout, web3).await;
+                // increment the nonce for the next call
+                next_nonce = next_nonce.checked_add(u256!(1)).unwrap();
+                options.push(SendTxOption::Nonce(next_nonce));
             }
+            break;
         }
     };
 
@@ -134,3 +222,82 @@ pub async fn send_to_cosmos(
 
     Ok(tx_hash)
 }
+
+/// Approves the Gravity contract to transfer exactly `amount` of `erc20` for the sender
+async fn approve_erc20_amount(
+    erc20: Address,
+    gravity_contract: Address,
+    amount: Uint256,
+    sender_secret: &EthPrivateKey,
+    web3: &Web3,
+    options: Vec<SendTxOption>,
+) -> Result<Uint256, Web3Error> {
+    web3.send_transaction(
+        erc20,
+        encode_call(
+            "approve(address,uint256)",
+            &[gravity_contract.into(), amount.into()],
+        )
+        .unwrap(),
+        u256!(0),
+        sender_secret.to_address(),
+        sender_secret,
+        options,
+    )
+    .await
+}
+
+async fn wait_for_approval(txid: Uint256, wait_timeout: Duration, web3: &Web3) {
+    web3.wait_for_transaction(txid, wait_timeout, None)
+        .await
+        .unwrap_or_else(|_| {
+            panic!(
+                "Can't await for transaction within timeout, txid: {:#066x}",
+                txid
+            )
+        });
+}
+
+/// Gets the amount of `erc20` that `spender` is allowed to transfer on behalf of `owner`
+pub async fn get_erc20_allowance(
+    erc20: Address,
+    owner: Address,
+    spender: Address,
+    web3: &Web3,
+) -> Result<Uint256, Web3Error> {
+    let payload = encode_call(
+        "allowance(address,address)",
+        &[owner.into(), spender.into()],
+    )
+    .unwrap();
+    let val = web3
+        .simulate_transaction(erc20, u256!(0), payload, owner, None)
+        .await?;
+    Ok(Uint256::from_bytes_be(&val).unwrap_or(u256!(0)))
+}
+
+/// Wraps `amount` of native ETH into WETH by depositing it into the WETH contract,
+/// waiting for the deposit to be included so the WETH can be sent right away
+pub async fn wrap_eth(
+    weth: Address,
+    amount: Uint256,
+    sender_secret: EthPrivateKey,
+    wait_timeout: Duration,
+    web3: &Web3,
+) -> Result<Uint256, GravityError> {
+    let sender_address = sender_secret.to_address();
+    info!("Wrapping {} wei from {} into WETH", amount, sender_address);
+    let tx_hash = web3
+        .send_transaction(
+            weth,
+            encode_call("deposit()", &[])?,
+            amount,
+            sender_address,
+            &sender_secret,
+            vec![SendTxOption::GasLimit(WRAP_ETH_GAS_LIMIT)],
+        )
+        .await?;
+    web3.wait_for_transaction(tx_hash, wait_timeout, None)
+        .await?;
+    Ok(tx_hash)
+}
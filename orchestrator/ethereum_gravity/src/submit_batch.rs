@@ -15,7 +15,7 @@ use gravity_utils::{
 
 use crate::{
     message_signatures::encode_tx_batch_confirm_hashed,
-    utils::{encode_valset_struct, get_tx_batch_nonce, GasCost},
+    utils::{encode_valset_struct, get_tx_batch_nonce, GasCost, RelayResult},
 };
 
 /// this function generates an appropriate Ethereum transaction
@@ -30,7 +30,7 @@ pub async fn send_eth_transaction_batch(
     gravity_contract_address: EthAddress,
     gravity_id: String,
     our_eth_key: EthPrivateKey,
-) -> Result<(), GravityError> {
+) -> Result<RelayResult, GravityError> {
     let new_batch_nonce = batch.nonce;
     let eth_address = our_eth_key.to_address();
     info!(
@@ -52,13 +52,13 @@ pub async fn send_eth_transaction_batch(
             "Someone else updated the batch to {}, exiting early",
             before_nonce
         );
-        return Ok(());
+        return Ok(RelayResult::AlreadyRelayed);
     } else if current_block_height > Uint256::from_u64(batch.batch_timeout) {
         info!(
             "This batch is timed out. timeout block: {} current block: {}, exiting early",
             current_block_height, batch.batch_timeout
         );
-        return Ok(());
+        return Ok(RelayResult::TimedOut);
     }
 
     let payload = encode_batch_payload(current_valset, &batch, confirms, gravity_id)?;
@@ -73,7 +73,13 @@ pub async fn send_eth_transaction_batch(
             vec![SendTxOption::GasPriceMultiplier(1.10f32)],
         )
         .await?;
-    info!("Sent batch update with txid {:#066x}", tx);
+    info!(
+        subsystem = "relayer",
+        nonce = new_batch_nonce,
+        token_contract = batch.token_contract.to_string(),
+        txid = format!("{:#066x}", tx);
+        "Sent batch update with txid {:#066x}", tx
+    );
 
     web3.wait_for_transaction(tx, timeout, None).await?;
 
@@ -89,10 +95,11 @@ pub async fn send_eth_transaction_batch(
             "Current nonce is {} expected to update to nonce {}",
             last_nonce, new_batch_nonce
         );
+        Ok(RelayResult::NotUpdated)
     } else {
         info!("Successfully updated Batch with new Nonce {:?}", last_nonce);
+        Ok(RelayResult::Relayed)
     }
-    Ok(())
 }
 
 /// Returns the cost in Eth of sending this batch
@@ -151,6 +151,20 @@ impl GasCost {
     }
 }
 
+/// What happened when submitting a valset, batch or logic call to the Gravity contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayResult {
+    /// The contract was already updated, another relayer got there first
+    AlreadyRelayed,
+    /// The batch or logic call timed out before it could be submitted
+    TimedOut,
+    /// Our transaction updated the contract
+    Relayed,
+    /// Our transaction was included but did not update the contract, usually because
+    /// another relayer's transaction was included first
+    NotUpdated,
+}
+
 /// This encodes the solidity struct ValsetArgs from the Gravity
 /// contract useful for all three major contract calls
 /// struct ValsetArgs {
@@ -15,7 +15,7 @@ use gravity_utils::{
 
 use crate::{
     message_signatures::encode_valset_confirm_hashed,
-    utils::{encode_valset_struct, get_valset_nonce, GasCost},
+    utils::{encode_valset_struct, get_valset_nonce, GasCost, RelayResult},
 };
 
 /// this function generates an appropriate Ethereum transaction
@@ -30,7 +30,7 @@ pub async fn send_eth_valset_update(
     gravity_contract_address: EthAddress,
     gravity_id: String,
     our_eth_key: EthPrivateKey,
-) -> Result<(), GravityError> {
+) -> Result<RelayResult, GravityError> {
     let old_nonce = old_valset.nonce;
     let new_nonce = new_valset.nonce;
     assert!(new_nonce > old_nonce);
@@ -45,7 +45,7 @@ pub async fn send_eth_valset_update(
             "Someone else updated the valset to {}, exiting early",
             before_nonce
         );
-        return Ok(());
+        return Ok(RelayResult::AlreadyRelayed);
     }
 
     let payload = encode_valset_update_payload(new_valset, old_valset, confirms, gravity_id)?;
@@ -60,7 +60,10 @@ pub async fn send_eth_valset_update(
             vec![SendTxOption::GasPriceMultiplier(1.10f32)],
         )
         .await?;
-    info!("Sent valset update with txid {:#066x}", tx);
+    info!(
+        subsystem = "relayer", nonce = new_nonce, txid = format!("{:#066x}", tx);
+        "Sent valset update with txid {:#066x}", tx
+    );
 
     web3.wait_for_transaction(tx, timeout, None).await?;
 
@@ -70,13 +73,14 @@ pub async fn send_eth_valset_update(
             "Current nonce is {} expected to update to nonce {}",
             last_nonce, new_nonce
         );
+        Ok(RelayResult::NotUpdated)
     } else {
         info!(
             "Successfully updated Valset with new Nonce {:?}",
             last_nonce
         );
+        Ok(RelayResult::Relayed)
     }
-    Ok(())
 }
 
 /// Returns the cost in Eth of sending this valset update
@@ -1,16 +1,20 @@
 //! Command line argument definitions for Gravity bridge tools
 //! See the clap documentation for how exactly this works, note that doc comments are displayed to the user
 
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
 use clap::Parser;
 use gravity_utils::{
     clarity::{Address as EthAddress, PrivateKey as EthPrivateKey},
     deep_space::{address::Address as CosmosAddress, Coin, PrivateKey as CosmosPrivateKey},
-    DEFAULT_ADDRESS_PREFIX,
+    num_conversion::DecimalAmount,
 };
 
+use crate::logging::LogFormat;
+
 /// Gravity Bridge tools (gbt) provides tools for interacting with the Onomy Gravity bridge for Cosmos based blockchains.
+/// Connection settings, fees and keys may also be set in config.toml or with GBT_* environment variables,
+/// command line flags always take precedence.
 #[derive(Parser)]
 #[clap(version = env!("CARGO_PKG_VERSION"), author = "Justin Kilpatrick <justin@althea.net>")]
 pub struct Opts {
@@ -21,12 +25,15 @@ pub struct Opts {
     #[arg(short, long)]
     pub quiet: bool,
     /// The home directory for Gravity Bridge Tools, by default
-    /// $HOME/.althea_gbt/
+    /// $HOME/.gbt/
     #[arg(short, long)]
     pub home: Option<PathBuf>,
     /// Set the address prefix for the Cosmos chain
-    #[arg(short, long, default_value = DEFAULT_ADDRESS_PREFIX)]
-    pub address_prefix: String,
+    #[arg(short, long)]
+    pub address_prefix: Option<String>,
+    /// Log as plain text or as one JSON object per line with structured fields
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
     #[command(subcommand)]
     pub subcmd: SubCommand,
 }
@@ -39,6 +46,8 @@ pub enum SubCommand {
     Gov(GovOpts),
     Keys(KeyOpts),
     Init(InitOpts),
+    Config(ConfigOpts),
+    Status(StatusOpts),
 }
 
 /// The Gravity Bridge orchestrator is required for all validators of the Cosmos chain running
@@ -47,20 +56,20 @@ pub enum SubCommand {
 pub struct OrchestratorOpts {
     /// Cosmos mnemonic phrase containing the tokens you would like to send
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// An Ethereum private key containing ETH to pay for fees, this will also hold the relayers earnings
     /// in the near future it will be possible to disable the Orchestrators integrated relayer
     #[arg(short, long)]
-    pub ethereum_key: EthPrivateKey,
+    pub ethereum_key: Option<EthPrivateKey>,
     /// (Optional) The Cosmos gRPC server that will be used
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// (Optional) The Ethereum RPC server that will be used
-    #[arg(long, default_value = "http://localhost:8545")]
-    pub ethereum_rpc: String,
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
     /// The address fo the Gravity contract on Ethereum
     #[arg(short, long)]
     pub gravity_contract_address: Option<EthAddress>,
@@ -73,10 +82,10 @@ pub struct OrchestratorOpts {
 pub struct RelayerOpts {
     /// An Ethereum private key containing ETH to pay for fees, this will also hold the relayers earnings
     #[arg(short, long)]
-    pub ethereum_key: EthPrivateKey,
+    pub ethereum_key: Option<EthPrivateKey>,
     /// Cosmos mnemonic phrase containing tokens used to pay fees on Cosmos for requesting batches
     #[arg(long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// (Optional) The Cosmos Denom and amount to pay Cosmos chain fees. If not set this relayer will not automatically
     /// request batches
     #[arg(short, long)]
@@ -85,11 +94,11 @@ pub struct RelayerOpts {
     #[arg(short, long)]
     pub gravity_contract_address: Option<EthAddress>,
     /// (Optional) The Ethereum RPC server that will be used
-    #[arg(long, default_value = "http://localhost:8545")]
-    pub ethereum_rpc: String,
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
     /// (Optional) The Cosmos gRPC server that will be used to
-    #[arg(short, long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(short, long)]
+    pub cosmos_grpc: Option<String>,
 }
 
 /// The Gravity Bridge client contains helpful command line tools for interacting with the Gravity bridge
@@ -102,8 +111,13 @@ pub struct ClientOpts {
 #[derive(Parser)]
 pub enum ClientSubcommand {
     CosmosToEth(CosmosToEthOpts),
+    CosmosToEthBulk(CosmosToEthBulkOpts),
     EthToCosmos(EthToCosmosOpts),
     DeployErc20Representation(DeployErc20RepresentationOpts),
+    PendingSends(PendingSendsOpts),
+    CancelSendToEth(CancelSendToEthOpts),
+    Track(TrackOpts),
+    SuggestBridgeFee(SuggestBridgeFeeOpts),
 }
 
 /// Send Cosmos tokens to Ethereum
@@ -111,23 +125,157 @@ pub enum ClientSubcommand {
 pub struct CosmosToEthOpts {
     /// Cosmos mnemonic phrase containing the tokens you would like to send
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The Denom and amount you wish to send eg: 1000000000anom
     #[arg(short, long)]
     pub amount: Coin,
     /// The Cosmos Denom and amount to pay Cosmos chain fees eg: 1000000000anom
     #[arg(short, long)]
-    pub fee: Coin,
+    pub fee: Option<Coin>,
     /// The amount you want to pay in bridge fees, these are used to pay relayers
-    /// on Ethereum and must be of the same denomination as `amount`
+    /// on Ethereum and must be of the same denomination as `amount`. Use `auto` to pay
+    /// the fee suggested by `gbt client suggest-bridge-fee`
     #[arg(short, long)]
-    pub bridge_fee: Coin,
+    pub bridge_fee: BridgeFeeArg,
     /// The destination address on the Ethereum chain
     #[arg(short, long)]
     pub eth_destination: EthAddress,
+    /// (Optional) The Ethereum RPC server used to price the bridge fee with `--bridge-fee auto`
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
+}
+
+/// Send Cosmos tokens to many Ethereum addresses listed in a CSV file, packing as many
+/// transfers as possible into each transaction
+#[derive(Parser)]
+pub struct CosmosToEthBulkOpts {
+    /// Cosmos mnemonic phrase containing the tokens you would like to send
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// (Optional) The Cosmos gRPC server that will be used to submit the transactions
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees for each transaction eg: 1000000000anom
+    #[arg(short, long)]
+    pub fee: Option<Coin>,
+    /// A CSV file with one transfer per line in the form `destination,amount,bridge_fee`
+    /// eg: 0x5A9...c1B,1000000000anom,1000anom. A header line and lines starting with `#` are skipped
+    #[arg(long)]
+    pub csv: PathBuf,
+    /// (Optional) The file the id of every sent transfer is written to, defaults to the CSV file
+    /// name with `.results.csv` appended. If it already exists the transfers it lists are not
+    /// sent again, so a partially failed run can be resumed by running the same command
+    #[arg(long)]
+    pub results: Option<PathBuf>,
+    /// (Optional) The most transfers to pack into a single transaction. By default this is
+    /// derived from the simulated gas of a transfer, the block gas limit and the largest
+    /// transaction the chain accepts
+    #[arg(long)]
+    pub max_transfers_per_tx: Option<usize>,
+}
+
+/// A bridge fee given on the command line, either an amount or `auto`
+#[derive(Debug, Clone)]
+pub enum BridgeFeeArg {
+    Auto,
+    Amount(Coin),
+}
+
+impl FromStr for BridgeFeeArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(BridgeFeeArg::Auto);
+        }
+        s.parse().map(BridgeFeeArg::Amount).map_err(|_| {
+            format!("Invalid bridge fee {s}, expected an amount such as 1000000anom or auto")
+        })
+    }
+}
+
+/// Suggest a bridge fee for sending a Cosmos denom to Ethereum, based on Ethereum gas prices
+/// and the fees already waiting to be batched for the same token
+#[derive(Parser)]
+pub struct SuggestBridgeFeeOpts {
+    /// The Cosmos Denom you want to send to Ethereum
+    #[arg(short, long)]
+    pub denom: String,
+    /// (Optional) The Cosmos gRPC server that will be used
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// (Optional) The Ethereum RPC server that will be used
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
+}
+
+/// List transfers to Ethereum from a Cosmos address that have not yet been sent to Ethereum
+#[derive(Parser)]
+pub struct PendingSendsOpts {
+    /// (Optional) The Cosmos address to list transfers for, by default the address of the configured Cosmos key
+    #[arg(long)]
+    pub address: Option<CosmosAddress>,
+    /// (Optional) Cosmos mnemonic phrase of the sender, used when no address is given
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// (Optional) The Cosmos gRPC server that will be used
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+}
+
+/// Cancel a transfer to Ethereum that has not yet been put in a batch, returning the
+/// amount and bridge fee to the sender. Transfers already in a batch can not be cancelled
+#[derive(Parser)]
+pub struct CancelSendToEthOpts {
+    /// Cosmos mnemonic phrase of the sender of the transfer
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees eg: 1000000000anom
+    #[arg(short, long)]
+    pub fee: Option<Coin>,
+    /// The id of the transfer to cancel, as shown by pending-sends
+    #[arg(long, required_unless_present = "all_unbatched")]
+    pub id: Option<u64>,
+    /// Cancel every transfer from this address that has not yet been put in a batch
+    #[arg(long, conflicts_with = "id")]
+    pub all_unbatched: bool,
+}
+
+/// Follow a transfer through the bridge, either an Ethereum to Cosmos deposit by its Ethereum txid
+/// or a Cosmos to Ethereum transfer by its id, printing each stage it reaches
+#[derive(Parser)]
+pub struct TrackOpts {
+    /// The Ethereum transaction hash of a deposit made with eth-to-cosmos
+    #[arg(long, required_unless_present = "send_id", conflicts_with = "send_id")]
+    pub eth_txid: Option<String>,
+    /// The id of a transfer made with cosmos-to-eth, as shown by pending-sends
+    #[arg(long)]
+    pub send_id: Option<u64>,
+    /// (Optional) The sender of the cosmos-to-eth transfer, by default the address of the configured Cosmos key.
+    /// Required to see transfers that are not yet in a batch
+    #[arg(long)]
+    pub sender: Option<CosmosAddress>,
+    /// Keep checking until the transfer has arrived instead of printing the current stage once
+    #[arg(short, long)]
+    pub wait: bool,
+    /// How long to keep checking with --wait before giving up, in seconds
+    #[arg(long, default_value_t = 3600)]
+    pub timeout: u64,
+    /// (Optional) The Cosmos gRPC server that will be used
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// (Optional) The Ethereum RPC server that will be used
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
+    /// (Optional) The address fo the Gravity contract on Ethereum, by default the one set in the chain parameters
+    #[arg(short, long)]
+    pub gravity_contract_address: Option<EthAddress>,
 }
 
 /// Send an Ethereum ERC20 token to Cosmos
@@ -135,22 +283,33 @@ pub struct CosmosToEthOpts {
 pub struct EthToCosmosOpts {
     /// The Ethereum private key to use for sending tokens
     #[arg(long)]
-    pub ethereum_key: EthPrivateKey,
+    pub ethereum_key: Option<EthPrivateKey>,
     /// (Optional) The Ethereum RPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:8545")]
-    pub ethereum_rpc: String,
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
     /// The address fo the Gravity contract on Ethereum
     #[arg(short, long)]
-    pub gravity_contract_address: EthAddress,
+    pub gravity_contract_address: Option<EthAddress>,
     /// The ERC20 contract address of the ERC20 you are sending
+    #[arg(short, long, required_unless_present = "wrap_eth")]
+    pub token_contract_address: Option<EthAddress>,
+    /// The amount of tokens you are sending eg. 1.2, this is converted exactly using the decimals of the ERC20
     #[arg(short, long)]
-    pub token_contract_address: EthAddress,
-    /// The amount of tokens you are sending eg. 1.2
-    #[arg(short, long)]
-    pub amount: f64,
+    pub amount: DecimalAmount,
     /// The destination address on the Cosmos blockchain
     #[arg(short, long)]
     pub destination: CosmosAddress,
+    /// Send native ETH, it is wrapped into WETH before being sent
+    #[arg(long, conflicts_with = "token_contract_address")]
+    pub wrap_eth: bool,
+    /// (Optional) The WETH contract used with --wrap-eth, defaults to WETH on Ethereum mainnet
+    #[arg(long, requires = "wrap_eth")]
+    pub weth_contract_address: Option<EthAddress>,
+    /// Approve the Gravity contract for exactly the amount being sent instead of the
+    /// maximum amount, this needs an approval transaction for most sends and a second one
+    /// to reset a leftover allowance to zero
+    #[arg(long)]
+    pub exact_approval: bool,
 }
 
 /// Deploy an ERC20 representation of a Cosmos asset on the Ethereum chain
@@ -158,17 +317,17 @@ pub struct EthToCosmosOpts {
 #[derive(Parser)]
 pub struct DeployErc20RepresentationOpts {
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// (Optional) The Ethereum RPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:8545")]
-    pub ethereum_rpc: String,
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
     /// The Cosmos Denom you wish to create an ERC20 representation for
     #[arg(short, long)]
     pub cosmos_denom: String,
     /// An Ethereum private key, containing enough ETH to pay for the transaction
     #[arg(short, long)]
-    pub ethereum_key: EthPrivateKey,
+    pub ethereum_key: Option<EthPrivateKey>,
     /// The address fo the Gravity contract on Ethereum
     #[arg(short, long)]
     pub gravity_contract_address: Option<EthAddress>,
@@ -196,22 +355,73 @@ pub struct RegisterOrchestratorAddressOpts {
     pub validator_phrase: CosmosPrivateKey,
     /// The Ethereum private key to register
     #[arg(short, long)]
-    pub ethereum_key: EthPrivateKey,
+    pub ethereum_key: Option<EthPrivateKey>,
     /// The phrase for the Cosmos key to register
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
 }
 
 /// Initialize configuration
 #[derive(Parser)]
 pub struct InitOpts {}
 
+/// Inspect the Gravity Bridge tools configuration
+#[derive(Parser)]
+pub struct ConfigOpts {
+    #[command(subcommand)]
+    pub subcmd: ConfigSubcommand,
+}
+
+#[derive(Parser)]
+pub enum ConfigSubcommand {
+    Check(ConfigCheckOpts),
+    Migrate(ConfigMigrateOpts),
+}
+
+/// Loads and validates a config file, then prints the effective settings
+#[derive(Parser)]
+pub struct ConfigCheckOpts {
+    /// (Optional) The config file to check, by default the config.toml in the home directory
+    #[arg(short, long)]
+    pub config_file: Option<PathBuf>,
+}
+
+/// Upgrades a config file written by an older version of gbt to the current format.
+/// The original file is kept as a backup next to it
+#[derive(Parser)]
+pub struct ConfigMigrateOpts {
+    /// (Optional) The config file to migrate, by default the config.toml in the home directory
+    #[arg(short, long)]
+    pub config_file: Option<PathBuf>,
+}
+
+/// Prints a one-shot snapshot of the bridge state on both chains, including the validator set,
+/// event nonces, pending batch fees and any batches or logic calls waiting to be relayed
+#[derive(Parser)]
+pub struct StatusOpts {
+    /// (Optional) The Cosmos gRPC server that will be used
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// (Optional) The Ethereum RPC server that will be used
+    #[arg(long)]
+    pub ethereum_rpc: Option<String>,
+    /// (Optional) The address fo the Gravity contract on Ethereum, by default the one set in the chain parameters
+    #[arg(short, long)]
+    pub gravity_contract_address: Option<EthAddress>,
+    /// (Optional) A validator operator address to show the delegate keys and last submitted event nonce for
+    #[arg(long)]
+    pub validator: Option<String>,
+    /// Print the status as JSON instead of human readable text
+    #[arg(long)]
+    pub json: bool,
+}
+
 /// The Gravity Bridge Governance subcommand contains tools for interacting with governance and submitting
 /// proposal types custom to Gravity Bridge
 #[derive(Parser)]
@@ -228,6 +438,12 @@ pub enum GovSubcommand {
     #[command(subcommand)]
     /// Query info about custom governance proposal types
     Query(GovQuerySubcommand),
+    /// Vote on a governance proposal
+    Vote(GovVoteOpts),
+    /// Add to the deposit of a governance proposal
+    Deposit(GovDepositOpts),
+    /// Print an example proposal.json for a proposal type
+    Template(GovTemplateOpts),
 }
 
 #[derive(Parser)]
@@ -236,19 +452,21 @@ pub enum GovSubmitSubcommand {
     Airdrop(AirdropProposalOpts),
     EmergencyBridgeHalt(EmergencyBridgeHaltProposalOpts),
     OracleUnhalt(OracleUnhaltProposalOpts),
+    ParamChange(ParamChangeProposalOpts),
 }
 
 #[derive(Parser)]
 pub enum GovQuerySubcommand {
     Airdrop(AirdropQueryOpts),
+    Proposals(ProposalsQueryOpts),
 }
 
 #[derive(Parser)]
 /// Queries active airdrop proposals and pretty-prints the interpreted data
 pub struct AirdropQueryOpts {
     /// (Optional) The Cosmos gRPC server that will be used to perform the query
-    #[arg(short, long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(short, long)]
+    pub cosmos_grpc: Option<String>,
     /// (Optional) query airdrops not actively being voted on
     #[arg(short, long)]
     pub query_history: bool,
@@ -257,6 +475,157 @@ pub struct AirdropQueryOpts {
     pub full_list: bool,
 }
 
+#[derive(Parser)]
+/// Queries Gravity governance proposals, IBC metadata, oracle unhalt, airdrop and Gravity
+/// parameter changes such as an emergency bridge halt, and prints their content, status,
+/// tally and deadline
+pub struct ProposalsQueryOpts {
+    /// (Optional) The Cosmos gRPC server that will be used to perform the query
+    #[arg(short, long)]
+    pub cosmos_grpc: Option<String>,
+    /// (Optional) Only show the proposal with this id
+    #[arg(long)]
+    pub id: Option<u64>,
+    /// Which proposals to show, one of deposit, voting, passed, rejected, failed or all
+    #[arg(short, long, default_value = "voting")]
+    pub status: ProposalStatusArg,
+    /// (Optional) display full recipients list for airdrops over 100 members
+    #[arg(short, long)]
+    pub full_list: bool,
+}
+
+/// A governance proposal status given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatusArg {
+    Deposit,
+    Voting,
+    Passed,
+    Rejected,
+    Failed,
+    All,
+}
+
+impl FromStr for ProposalStatusArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "deposit" => Ok(ProposalStatusArg::Deposit),
+            "voting" => Ok(ProposalStatusArg::Voting),
+            "passed" => Ok(ProposalStatusArg::Passed),
+            "rejected" => Ok(ProposalStatusArg::Rejected),
+            "failed" => Ok(ProposalStatusArg::Failed),
+            "all" => Ok(ProposalStatusArg::All),
+            _ => Err(format!(
+                "Invalid proposal status {s}, expected deposit, voting, passed, rejected, failed or all"
+            )),
+        }
+    }
+}
+
+/// Prints an example proposal.json for one of the `gov submit` proposal types, the JSON is
+/// printed to stdout and notes on each field are logged
+#[derive(Parser)]
+pub struct GovTemplateOpts {
+    /// The proposal type, one of ibc-metadata, airdrop, emergency-bridge-halt, oracle-unhalt
+    /// or param-change
+    pub proposal_type: ProposalTypeArg,
+}
+
+/// A `gov submit` proposal type given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalTypeArg {
+    IbcMetadata,
+    Airdrop,
+    EmergencyBridgeHalt,
+    OracleUnhalt,
+    ParamChange,
+}
+
+impl FromStr for ProposalTypeArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "ibc-metadata" => Ok(ProposalTypeArg::IbcMetadata),
+            "airdrop" => Ok(ProposalTypeArg::Airdrop),
+            "emergency-bridge-halt" => Ok(ProposalTypeArg::EmergencyBridgeHalt),
+            "oracle-unhalt" => Ok(ProposalTypeArg::OracleUnhalt),
+            "param-change" => Ok(ProposalTypeArg::ParamChange),
+            _ => Err(format!(
+                "Invalid proposal type {s}, expected ibc-metadata, airdrop, emergency-bridge-halt, oracle-unhalt or param-change"
+            )),
+        }
+    }
+}
+
+/// Votes on a governance proposal, validators can use this to act on bridge critical
+/// proposals such as an oracle unhalt without another CLI
+#[derive(Parser)]
+pub struct GovVoteOpts {
+    /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// The phrase for the address voting, usually the validator operator key
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// The id of the proposal to vote on
+    #[arg(short, long)]
+    pub proposal_id: u64,
+    /// The vote, one of yes, no, abstain or no-with-veto
+    #[arg(short, long)]
+    pub option: VoteOptionArg,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees
+    #[arg(short, long)]
+    pub fees: Option<Coin>,
+}
+
+/// A governance vote given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteOptionArg {
+    Yes,
+    No,
+    Abstain,
+    NoWithVeto,
+}
+
+impl FromStr for VoteOptionArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "yes" => Ok(VoteOptionArg::Yes),
+            "no" => Ok(VoteOptionArg::No),
+            "abstain" => Ok(VoteOptionArg::Abstain),
+            "no-with-veto" | "veto" => Ok(VoteOptionArg::NoWithVeto),
+            _ => Err(format!(
+                "Invalid vote {s}, expected yes, no, abstain or no-with-veto"
+            )),
+        }
+    }
+}
+
+/// Adds to the deposit of a governance proposal, a proposal enters the voting period once
+/// its deposit reaches the minimum deposit
+#[derive(Parser)]
+pub struct GovDepositOpts {
+    /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// The phrase for an address containing enough funds to pay the deposit
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// The id of the proposal to deposit on
+    #[arg(short, long)]
+    pub proposal_id: u64,
+    /// The Cosmos Denom and amount to deposit
+    #[arg(short, long)]
+    pub deposit: Coin,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees
+    #[arg(short, long)]
+    pub fees: Option<Coin>,
+}
+
 /// An IBC metadata proposal is a Governance proposal which allows setting denom metadata
 /// for an IBC token. This is an essential first setup in taking IBC tokens to Ethereum.
 /// The provided denom metadata will be used to set the name, symbol, description, and decimals
@@ -264,11 +633,11 @@ pub struct AirdropQueryOpts {
 #[derive(Parser)]
 pub struct IbcMetadataProposalOpts {
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The phrase for an address containing enough funds to submit the proposal.
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// Path to the proposal.json
     #[arg(short, long)]
     pub json: PathBuf,
@@ -277,28 +646,58 @@ pub struct IbcMetadataProposalOpts {
     pub deposit: Coin,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
+    /// Check the proposal.json and exit without submitting it
+    #[arg(long)]
+    pub validate_only: bool,
 }
 
 /// An Airdrop Proposal allows the community to create, vote on, and execute
 /// an airdrop. A list of addresses are all send an equal amount of tokens from the community pool.
+/// Airdrops too large for a single transaction are split into several proposals
 #[derive(Parser)]
 pub struct AirdropProposalOpts {
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The phrase for an address containing enough funds to submit the proposal.
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// Path to the proposal.json
-    #[arg(short, long)]
-    pub json: PathBuf,
+    #[arg(short, long, required_unless_present = "csv")]
+    pub json: Option<PathBuf>,
+    /// Path to a CSV file with one `address,amount` row per recipient, used instead of a
+    /// proposal.json. Rows for the same address are merged into one
+    #[arg(long, conflicts_with = "json", requires_all = ["title", "description", "denom"])]
+    pub csv: Option<PathBuf>,
+    /// The title of an airdrop read from --csv
+    #[arg(long, requires = "csv")]
+    pub title: Option<String>,
+    /// The description of an airdrop read from --csv
+    #[arg(long, requires = "csv")]
+    pub description: Option<String>,
+    /// The denom of an airdrop read from --csv
+    #[arg(long, requires = "csv")]
+    pub denom: Option<String>,
+    /// (Optional) The largest transaction in bytes the chain accepts, airdrops that would be
+    /// larger are split into several proposals. By default the block max_bytes consensus param
+    /// is queried and capped at the default Tendermint mempool max_tx_bytes of 1 MiB, set this
+    /// if your validators run with a different mempool limit
+    #[arg(long)]
+    pub max_tx_bytes: Option<usize>,
+    /// (Optional) Where to write the manifest tying each proposal id to its recipients,
+    /// defaults to the input file name with `.manifest.json` appended
+    #[arg(long)]
+    pub manifest: Option<PathBuf>,
     /// The Cosmos Denom and amount to pay the governance proposal deposit
     #[arg(short, long)]
     pub deposit: Coin,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
+    /// Check the proposal.json and exit without submitting it
+    #[arg(long)]
+    pub validate_only: bool,
 }
 
 /// In case of a critical bug or other event involving the bridge the Gravity Bridge community may
@@ -308,11 +707,11 @@ pub struct AirdropProposalOpts {
 #[derive(Parser)]
 pub struct EmergencyBridgeHaltProposalOpts {
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The phrase for an address containing enough funds to submit the proposal.
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// Path to the proposal.json
     #[arg(short, long)]
     pub json: PathBuf,
@@ -321,7 +720,10 @@ pub struct EmergencyBridgeHaltProposalOpts {
     pub deposit: Coin,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
+    /// Check the proposal.json and exit without submitting it
+    #[arg(long)]
+    pub validate_only: bool,
 }
 
 /// If there is a fork on the Ethereum mainnet it may cause disagreement in the bridge Oracle
@@ -331,11 +733,37 @@ pub struct EmergencyBridgeHaltProposalOpts {
 #[derive(Parser)]
 pub struct OracleUnhaltProposalOpts {
     /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
-    #[arg(long, default_value = "http://localhost:9090")]
-    pub cosmos_grpc: String,
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
+    /// The phrase for an address containing enough funds to submit the proposal.
+    #[arg(short, long)]
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
+    /// Path to the proposal.json
+    #[arg(short, long)]
+    pub json: PathBuf,
+    /// The Cosmos Denom and amount to pay the governance proposal deposit
+    #[arg(short, long)]
+    pub deposit: Coin,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees
+    #[arg(short, long)]
+    pub fees: Option<Coin>,
+    /// Check the proposal.json and exit without submitting it
+    #[arg(long)]
+    pub validate_only: bool,
+}
+
+/// A parameter change proposal sets new values for Gravity module parameters. The proposal.json
+/// contains a title, description and a `changes` object mapping parameter keys such as
+/// `SignedValsetsWindow` to their new values, the changes are checked against the current
+/// parameters and shown before the proposal is submitted
+#[derive(Parser)]
+pub struct ParamChangeProposalOpts {
+    /// (Optional) The Cosmos gRPC server that will be used to submit the transaction
+    #[arg(long)]
+    pub cosmos_grpc: Option<String>,
     /// The phrase for an address containing enough funds to submit the proposal.
     #[arg(short, long)]
-    pub cosmos_phrase: CosmosPrivateKey,
+    pub cosmos_phrase: Option<CosmosPrivateKey>,
     /// Path to the proposal.json
     #[arg(short, long)]
     pub json: PathBuf,
@@ -344,5 +772,8 @@ pub struct OracleUnhaltProposalOpts {
     pub deposit: Coin,
     /// The Cosmos Denom and amount to pay Cosmos chain fees
     #[arg(short, long)]
-    pub fees: Coin,
+    pub fees: Option<Coin>,
+    /// Check the proposal.json and exit without submitting it
+    #[arg(long)]
+    pub validate_only: bool,
 }
@@ -0,0 +1,72 @@
+use cosmos_gravity::{
+    query::get_pending_send_to_eth, send::cancel_send_to_eth as submit_cancel_send_to_eth,
+};
+use gravity_utils::{
+    connection_prep::{check_for_fee, create_rpc_connections},
+    error::GravityError,
+    types::GravityBridgeToolsConfig,
+};
+
+use crate::{
+    args::CancelSendToEthOpts,
+    config::{resolve_cosmos_key, resolve_fees},
+    utils::TIMEOUT,
+};
+
+pub async fn cancel_send_to_eth(
+    args: CancelSendToEthOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
+    let fee = resolve_fees(args.fee, &config.connection)?;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+
+    let cosmos_address = cosmos_key.to_address(&address_prefix).unwrap();
+    let connections =
+        create_rpc_connections(address_prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+    let contact = connections.contact.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    check_for_fee(&fee, cosmos_address, &contact).await?;
+
+    let pending = get_pending_send_to_eth(&mut grpc, cosmos_address).await?;
+    let ids: Vec<u64> = match args.id {
+        Some(id) => {
+            if pending.transfers_in_batches.iter().any(|tx| tx.id == id) {
+                return Err(GravityError::ValidationError(format!(
+                    "Transfer {id} is already in a batch and can no longer be cancelled"
+                )));
+            }
+            if !pending.unbatched_transfers.iter().any(|tx| tx.id == id) {
+                return Err(GravityError::ValidationError(format!(
+                    "No pending transfer with id {id} from {cosmos_address}"
+                )));
+            }
+            vec![id]
+        }
+        None => pending.unbatched_transfers.iter().map(|tx| tx.id).collect(),
+    };
+    if ids.is_empty() {
+        info!("No unbatched transfers from {} to cancel", cosmos_address);
+        return Ok(());
+    }
+
+    for id in ids {
+        match submit_cancel_send_to_eth(cosmos_key, fee.clone(), &contact, id).await {
+            Ok(res) => info!("Cancelled transfer {} with txid {}", id, res.txhash),
+            Err(e) => {
+                return Err(GravityError::UnrecoverableError(format!(
+                    "Failed to cancel transfer {id}! {e:?}"
+                )))
+            }
+        }
+    }
+    info!(
+        "Your funds and bridge fees have been returned to {}",
+        cosmos_address
+    );
+    Ok(())
+}
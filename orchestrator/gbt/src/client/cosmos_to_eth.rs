@@ -1,60 +1,88 @@
+use std::convert::TryFrom;
+
 use cosmos_gravity::{query::get_denom_to_erc20, send::send_to_eth};
-use gravity_proto::gravity::QueryDenomToErc20Request;
 use gravity_utils::{
+    clarity::Address as EthAddress,
     connection_prep::{check_for_fee, create_rpc_connections},
+    deep_space::{Coin, Contact},
     error::GravityError,
-    num_conversion::{print_eth, print_nom},
+    num_conversion::DecimalAmount,
+    types::GravityBridgeToolsConfig,
+    web30::client::Web3,
 };
 
-use crate::{args::CosmosToEthOpts, utils::TIMEOUT};
+use crate::{
+    args::{BridgeFeeArg, CosmosToEthOpts},
+    client::suggest_bridge_fee::{bridge_fee_suggestion, print_bridge_fee_suggestion},
+    config::{resolve_cosmos_key, resolve_fees},
+    utils::TIMEOUT,
+};
 
 pub async fn cosmos_to_eth(
     args: CosmosToEthOpts,
     address_prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let cosmos_key = args.cosmos_phrase;
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
     let gravity_coin = args.amount;
-    let fee = args.fee;
-    let cosmos_grpc = args.cosmos_grpc;
+    let fee = resolve_fees(args.fee, &config.connection)?;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
     let eth_dest = args.eth_destination;
-    let bridge_fee = args.bridge_fee;
+    // an Ethereum node is only needed to price the bridge fee
+    let ethereum_rpc = match args.bridge_fee {
+        BridgeFeeArg::Auto => Some(
+            args.ethereum_rpc
+                .unwrap_or_else(|| config.connection.ethereum_rpc.clone()),
+        ),
+        BridgeFeeArg::Amount(_) => None,
+    };
 
     let cosmos_address = cosmos_key.to_address(&address_prefix).unwrap();
 
     info!("Sending from Cosmos address {}", cosmos_address);
     let connections =
-        create_rpc_connections(address_prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+        create_rpc_connections(address_prefix, Some(cosmos_grpc), ethereum_rpc, TIMEOUT).await;
     let contact = connections.contact.unwrap();
     let mut grpc = connections.grpc.unwrap();
 
     let res = get_denom_to_erc20(&mut grpc, gravity_coin.denom.clone()).await;
-    let is_cosmos_originated = match res {
-        Ok(v) => v.cosmos_originated,
-        Err(e) => {
+    let erc20: EthAddress = match res.map(|v| v.erc20.parse()) {
+        Ok(Ok(erc20)) => erc20,
+        Ok(Err(_)) | Err(_) => {
             return Err(GravityError::UnrecoverableError(format!(
-                "Could not lookup denom is it valid? {e:?}"
+                "Asset {} has no ERC20 representation, you may need to deploy an ERC20 for it!",
+                gravity_coin.denom
             )));
         }
     };
+    info!(
+        "Asset {} has ERC20 representation {}",
+        gravity_coin.denom, erc20
+    );
+    let decimals = display_decimals(
+        &contact,
+        connections.web3.as_ref(),
+        &gravity_coin.denom,
+        erc20,
+        eth_dest,
+    )
+    .await?;
 
-    let res = grpc
-        .denom_to_erc20(QueryDenomToErc20Request {
-            denom: gravity_coin.denom.clone(),
-        })
-        .await;
-    match res {
-        Ok(val) => info!(
-            "Asset {} has ERC20 representation {}",
-            gravity_coin.denom,
-            val.into_inner().erc20
-        ),
-        Err(_) => {
-            return Err(GravityError::UnrecoverableError(format!(
-                "Asset {} has no ERC20 representation, you may need to deploy an ERC20 for it!",
-                gravity_coin.denom
-            )));
+    let bridge_fee = match args.bridge_fee {
+        BridgeFeeArg::Amount(bridge_fee) => bridge_fee,
+        BridgeFeeArg::Auto => {
+            let web3 = connections.web3.unwrap();
+            let suggestion =
+                bridge_fee_suggestion(&mut grpc, &web3, gravity_coin.denom.clone(), config).await?;
+            print_bridge_fee_suggestion(&suggestion);
+            Coin {
+                denom: gravity_coin.denom.clone(),
+                amount: suggestion.fee,
+            }
         }
-    }
+    };
 
     let amount = gravity_coin.clone();
     check_for_fee(&gravity_coin, cosmos_address, &contact).await?;
@@ -68,15 +96,13 @@ pub async fn cosmos_to_eth(
     match balance {
         Some(balance) => {
             if balance.amount < amount.amount.checked_add(bridge_fee.amount).unwrap() {
-                return if is_cosmos_originated {
-                    Err(GravityError::UnrecoverableError(
-                        format!("Your transfer of {} {} tokens is greater than your balance of {} tokens. Remember you need some to pay for fees!",
-                                print_nom(amount.amount), gravity_coin.denom, print_nom(balance.amount))))
-                } else {
-                    Err(GravityError::UnrecoverableError(
-                        format!("Your transfer of {} {} tokens is greater than your balance of {} tokens. Remember you need some to pay for fees!",
-                                print_eth(amount.amount), gravity_coin.denom, print_eth(balance.amount))))
-                };
+                return Err(GravityError::UnrecoverableError(format!(
+                    "Your transfer of {} {} tokens plus a bridge fee of {} is greater than your balance of {} tokens. Remember you need some to pay for fees!",
+                    DecimalAmount::from_base_units(amount.amount, decimals),
+                    gravity_coin.denom,
+                    DecimalAmount::from_base_units(bridge_fee.amount, decimals),
+                    DecimalAmount::from_base_units(balance.amount, decimals)
+                )));
             }
         }
         None => {
@@ -88,8 +114,10 @@ pub async fn cosmos_to_eth(
     }
 
     info!(
-        "Locking {} / {} into the batch pool",
-        amount.denom, gravity_coin.denom
+        "Locking {} {} and a bridge fee of {} into the batch pool",
+        DecimalAmount::from_base_units(amount.amount, decimals),
+        gravity_coin.denom,
+        DecimalAmount::from_base_units(bridge_fee.amount, decimals)
     );
     let res = send_to_eth(
         cosmos_key,
@@ -110,6 +138,45 @@ pub async fn cosmos_to_eth(
     }
     info!("Your funds are now waiting to be sent to Ethereum in a transaction batch!");
     info!("Depending on how much you and others attached in fees, this might take a while!");
-    info!("You can retrieve your funds using a CancelSendToEth message, up until they are sent to Ethereum");
+    info!("You can retrieve your funds with `gbt client cancel-send-to-eth`, up until they are put in a batch");
+    info!("Use `gbt client pending-sends` to find the id of your transfer and `gbt client track --send-id <id> --wait` to follow it");
     Ok(())
 }
+
+/// The number of decimals amounts of `denom` are printed with. Cosmos originated denoms have
+/// their display exponent in the denom metadata, Ethereum originated denoms have no metadata
+/// and use the decimals of their ERC20 when an Ethereum node is connected. Without either
+/// amounts are printed in base units
+async fn display_decimals(
+    contact: &Contact,
+    web3: Option<&Web3>,
+    denom: &str,
+    erc20: EthAddress,
+    caller: EthAddress,
+) -> Result<u8, GravityError> {
+    if let Ok(Some(metadata)) = contact.get_denom_metadata(denom.to_string()).await {
+        let exponent = metadata
+            .denom_units
+            .iter()
+            .find(|unit| unit.denom == metadata.display)
+            .map(|unit| unit.exponent);
+        if let Some(exponent) = exponent {
+            return u8::try_from(exponent).map_err(|_| {
+                GravityError::ValidationError(format!(
+                    "Denom {denom} has invalid display exponent {exponent}"
+                ))
+            });
+        }
+    }
+    match web3 {
+        Some(web3) => {
+            let decimals = web3.get_erc20_decimals(erc20, caller).await?;
+            decimals.to_string().parse().map_err(|_| {
+                GravityError::ValidationError(format!(
+                    "ERC20 {erc20} has invalid decimals {decimals}"
+                ))
+            })
+        }
+        None => Ok(0),
+    }
+}
@@ -0,0 +1,505 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    convert::TryFrom,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use cosmos_gravity::{
+    query::{get_denom_to_erc20, get_pending_send_to_eth},
+    send::{send_to_eth_bulk, send_to_eth_msg, simulate_send_to_eth_bulk, SendToEthTransfer},
+};
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{
+    clarity::{u256, Address as EthAddress, Uint256},
+    connection_prep::create_rpc_connections,
+    deep_space::{Address as CosmosAddress, Coin, Contact, PrivateKey as CosmosPrivateKey},
+    error::GravityError,
+    types::GravityBridgeToolsConfig,
+};
+use prost::Message;
+use tonic::transport::Channel;
+
+use crate::{
+    args::CosmosToEthBulkOpts,
+    config::{resolve_cosmos_key, resolve_fees},
+    utils::{get_block_params, TIMEOUT},
+};
+
+const RESULTS_HEADER: &str = "line,destination,amount,bridge_fee,id,txhash";
+/// Share of the block gas limit a transaction is sized to use. The simulated gas is an
+/// estimate and it is padded again when the transaction's gas limit is set
+const BLOCK_GAS_BUDGET_PERCENT: u64 = 50;
+/// Bytes of a transaction that don't depend on the number of transfers: the signature,
+/// public key, fee and memo
+const TX_OVERHEAD_BYTES: usize = 1024;
+/// Bytes the `Any` wrapping a message adds besides its type url
+const ANY_OVERHEAD_BYTES: usize = 16;
+
+/// A transfer read from the input CSV along with the line it was read from,
+/// the line number identifies the transfer in the results file
+#[derive(Debug, Clone)]
+struct BulkTransfer {
+    line: usize,
+    transfer: SendToEthTransfer,
+}
+
+impl BulkTransfer {
+    /// the transfer as written to the results file, without the id and txhash
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{}",
+            self.line,
+            self.transfer.destination,
+            coin_to_csv(&self.transfer.amount),
+            coin_to_csv(&self.transfer.bridge_fee)
+        )
+    }
+}
+
+pub async fn cosmos_to_eth_bulk(
+    args: CosmosToEthBulkOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    if args.max_transfers_per_tx == Some(0) {
+        return Err(GravityError::ValidationError(
+            "--max-transfers-per-tx must be at least 1".to_string(),
+        ));
+    }
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
+    let fee = resolve_fees(args.fee, &config.connection)?;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let results_path = args.results.unwrap_or_else(|| {
+        let mut path = args.csv.clone().into_os_string();
+        path.push(".results.csv");
+        PathBuf::from(path)
+    });
+
+    let csv = fs::read_to_string(&args.csv).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Could not read transfers file {} {e:?}",
+            args.csv.display()
+        ))
+    })?;
+    let transfers = parse_transfers_csv(&csv)?;
+    if transfers.is_empty() {
+        return Err(GravityError::ValidationError(format!(
+            "No transfers found in {}",
+            args.csv.display()
+        )));
+    }
+
+    let sent = read_results(&results_path)?;
+    let mut remaining = Vec::new();
+    for transfer in transfers {
+        match sent.get(&transfer.line) {
+            Some(recorded) if *recorded == transfer.to_csv() => {}
+            Some(recorded) => {
+                return Err(GravityError::ValidationError(format!(
+                    "Line {} of {} does not match the transfer recorded in {}: {recorded}, was the CSV edited after it was sent?",
+                    transfer.line,
+                    args.csv.display(),
+                    results_path.display()
+                )))
+            }
+            None => remaining.push(transfer),
+        }
+    }
+    if !sent.is_empty() {
+        info!(
+            "Resuming from {}, {} transfers were already sent",
+            results_path.display(),
+            sent.len()
+        );
+    }
+    if remaining.is_empty() {
+        info!("All transfers have been sent");
+        return Ok(());
+    }
+
+    let cosmos_address = cosmos_key.to_address(&address_prefix).unwrap();
+    info!("Sending from Cosmos address {}", cosmos_address);
+    let connections =
+        create_rpc_connections(address_prefix, Some(cosmos_grpc.clone()), None, TIMEOUT).await;
+    let contact = connections.contact.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    let transfers_per_tx = match args.max_transfers_per_tx {
+        Some(max) => max,
+        None => estimate_transfers_per_tx(cosmos_key, &remaining, &contact, &cosmos_grpc).await?,
+    };
+    let txs = remaining.chunks(transfers_per_tx).len();
+    let totals = total_per_denom(&remaining, &fee, txs)?;
+    let denoms: BTreeSet<&String> = remaining.iter().map(|t| &t.transfer.amount.denom).collect();
+    for denom in denoms {
+        if let Err(e) = get_denom_to_erc20(&mut grpc, denom.clone()).await {
+            return Err(GravityError::ValidationError(format!(
+                "Asset {denom} has no ERC20 representation, you may need to deploy an ERC20 for it! {e:?}"
+            )));
+        }
+    }
+    let balances = contact.get_balances(cosmos_address).await?;
+    for (denom, total) in totals.iter() {
+        let balance = balances
+            .iter()
+            .find(|b| b.denom == *denom)
+            .map(|b| b.amount)
+            .unwrap_or(u256!(0));
+        if balance < *total {
+            return Err(GravityError::ValidationError(format!(
+                "Sending {} transfers in {txs} transactions needs {total}{denom} including fees but the balance is {balance}{denom}",
+                remaining.len()
+            )));
+        }
+    }
+
+    let mut results = open_results(&results_path)?;
+    info!(
+        "Sending {} transfers in {txs} transactions, results are written to {}",
+        remaining.len(),
+        results_path.display()
+    );
+    let mut sent_count = 0;
+    for chunk in remaining.chunks(transfers_per_tx) {
+        let before = pending_ids(&mut grpc, cosmos_address).await?;
+        let chunk_transfers: Vec<SendToEthTransfer> =
+            chunk.iter().map(|t| t.transfer.clone()).collect();
+        let res = send_to_eth_bulk(cosmos_key, &chunk_transfers, fee.clone(), &contact).await;
+        let res = match res {
+            Ok(res) if res.code == 0 => Ok(res),
+            Ok(res) => Err(format!("{res:?}")),
+            Err(e) => Err(format!("{e:?}")),
+        }
+        .map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Failed to send transfers from line {} on, {sent_count} of {} transfers were sent. \
+                If the transaction timed out it may still be included, check `gbt client pending-sends` \
+                and add any transfers that were sent to {} before running this command again to resume {e}",
+                chunk[0].line,
+                remaining.len(),
+                results_path.display()
+            ))
+        })?;
+
+        // the chunk has been sent, so the ids are best effort and a failed query must not stop
+        // the chunk from being recorded, otherwise resuming would send it a second time.
+        // ids are assigned in message order, so the new ids sorted ascending line up with the chunk
+        let mut new_ids: Vec<u64> = match pending_ids(&mut grpc, cosmos_address).await {
+            Ok(after) => after.difference(&before).copied().collect(),
+            Err(e) => {
+                warn!(
+                    "Failed to query the pending transfers after {} {e:?}",
+                    res.txhash
+                );
+                Vec::new()
+            }
+        };
+        new_ids.sort_unstable();
+        if new_ids.len() != chunk.len() {
+            warn!(
+                "Could not determine the ids of the transfers sent in {}, they are left empty in the results file",
+                res.txhash
+            );
+            new_ids.clear();
+        }
+        for (i, transfer) in chunk.iter().enumerate() {
+            let id = new_ids.get(i).map(|id| id.to_string()).unwrap_or_default();
+            writeln!(results, "{},{id},{}", transfer.to_csv(), res.txhash).map_err(|e| {
+                GravityError::UnrecoverableError(format!(
+                    "Failed to write results file {} {e:?}",
+                    results_path.display()
+                ))
+            })?;
+        }
+        sent_count += chunk.len();
+        info!(
+            "Sent {sent_count} of {} transfers, txid {}",
+            remaining.len(),
+            res.txhash
+        );
+    }
+    info!("All transfers are now waiting to be sent to Ethereum in transaction batches!");
+    info!("Use `gbt client track --send-id <id> --wait` with an id from the results file to follow a transfer");
+    Ok(())
+}
+
+/// Works out how many transfers fit in one transaction. Simulating one and two transfers gives
+/// the gas of the transaction itself and of each additional transfer, which is checked against
+/// the block gas limit. The size of the largest transfer is checked against the largest
+/// transaction the chain accepts
+async fn estimate_transfers_per_tx(
+    cosmos_key: CosmosPrivateKey,
+    transfers: &[BulkTransfer],
+    contact: &Contact,
+    cosmos_grpc: &str,
+) -> Result<usize, GravityError> {
+    let block_params = get_block_params(cosmos_grpc).await?;
+    let sender = cosmos_key.to_address(&contact.get_prefix()).unwrap();
+    let transfer_bytes = transfers
+        .iter()
+        .map(|t| {
+            send_to_eth_msg(&sender, &t.transfer).encoded_len()
+                + "/gravity.v1.MsgSendToEth".len()
+                + ANY_OVERHEAD_BYTES
+        })
+        .max()
+        .unwrap_or_default();
+    let sample: Vec<SendToEthTransfer> = transfers
+        .iter()
+        .take(2)
+        .map(|t| t.transfer.clone())
+        .collect();
+    let (base_gas, transfer_gas) = match sample.len() {
+        2 => {
+            let one = simulate_send_to_eth_bulk(cosmos_key, &sample[..1], contact).await?;
+            let two = simulate_send_to_eth_bulk(cosmos_key, &sample, contact).await?;
+            let transfer_gas = two.saturating_sub(one);
+            (one.saturating_sub(transfer_gas), transfer_gas)
+        }
+        // one transfer fits in one transaction or the chain would reject it anyway
+        _ => return Ok(1),
+    };
+    let transfers_per_tx = transfers_per_tx(
+        base_gas,
+        transfer_gas,
+        block_params.max_gas,
+        block_params.max_tx_bytes(),
+        transfer_bytes,
+    );
+    info!(
+        "A transfer uses about {} gas and {} bytes, sending up to {} transfers per transaction",
+        transfer_gas, transfer_bytes, transfers_per_tx
+    );
+    Ok(transfers_per_tx)
+}
+
+/// The most transfers that keep a transaction within `BLOCK_GAS_BUDGET_PERCENT` of the block
+/// gas limit and within `max_tx_bytes`, at least one
+fn transfers_per_tx(
+    base_gas: u64,
+    transfer_gas: u64,
+    max_gas: Option<u64>,
+    max_tx_bytes: usize,
+    transfer_bytes: usize,
+) -> usize {
+    let by_bytes = max_tx_bytes.saturating_sub(TX_OVERHEAD_BYTES) / transfer_bytes.max(1);
+    let by_gas = match max_gas {
+        Some(max_gas) => {
+            let budget = max_gas / 100 * BLOCK_GAS_BUDGET_PERCENT;
+            let fits = budget.saturating_sub(base_gas) / transfer_gas.max(1);
+            usize::try_from(fits).unwrap_or(usize::MAX)
+        }
+        None => usize::MAX,
+    };
+    by_bytes.min(by_gas).max(1)
+}
+
+/// Parses transfers in the form `destination,amount,bridge_fee`, skipping blank lines,
+/// lines starting with `#` and a header line whose first field is `destination`
+fn parse_transfers_csv(csv: &str) -> Result<Vec<BulkTransfer>, GravityError> {
+    let mut transfers = Vec::new();
+    for (i, row) in csv.lines().enumerate() {
+        let line = i + 1;
+        let row = row.trim();
+        if row.is_empty() || row.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').map(|f| f.trim()).collect();
+        if fields[0].eq_ignore_ascii_case("destination") {
+            continue;
+        }
+        if fields.len() != 3 {
+            return Err(GravityError::ValidationError(format!(
+                "Line {line} has {} fields, expected destination,amount,bridge_fee",
+                fields.len()
+            )));
+        }
+        let destination: EthAddress = fields[0].parse().map_err(|_| {
+            GravityError::ValidationError(format!(
+                "Line {line} has an invalid Ethereum address {}",
+                fields[0]
+            ))
+        })?;
+        let parse_coin = |field: &str, name: &str| -> Result<Coin, GravityError> {
+            field.parse().map_err(|_| {
+                GravityError::ValidationError(format!(
+                    "Line {line} has an invalid {name} {field}, expected an amount and denom eg: 1000000000anom"
+                ))
+            })
+        };
+        let amount = parse_coin(fields[1], "amount")?;
+        let bridge_fee = parse_coin(fields[2], "bridge fee")?;
+        if amount.denom != bridge_fee.denom {
+            return Err(GravityError::ValidationError(format!(
+                "Line {line} pays its bridge fee in {} but sends {}, bridge fees must be paid in the token being sent",
+                bridge_fee.denom, amount.denom
+            )));
+        }
+        transfers.push(BulkTransfer {
+            line,
+            transfer: SendToEthTransfer {
+                destination,
+                amount,
+                bridge_fee,
+            },
+        });
+    }
+    Ok(transfers)
+}
+
+/// Reads the transfers already recorded in a results file, keyed by their line in the input CSV
+fn read_results(path: &Path) -> Result<HashMap<usize, String>, GravityError> {
+    let mut sent = HashMap::new();
+    if !path.exists() {
+        return Ok(sent);
+    }
+    let contents = fs::read_to_string(path).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Could not read results file {} {e:?}",
+            path.display()
+        ))
+    })?;
+    for row in contents.lines().map(|r| r.trim()) {
+        if row.is_empty() || row == RESULTS_HEADER {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').collect();
+        let line = fields.first().and_then(|l| l.parse::<usize>().ok());
+        match line {
+            Some(line) if fields.len() == 6 => {
+                sent.insert(line, fields[..4].join(","));
+            }
+            _ => {
+                return Err(GravityError::ValidationError(format!(
+                    "Invalid row in results file {}: {row}",
+                    path.display()
+                )))
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Opens the results file for appending, writing the header if it is new
+fn open_results(path: &Path) -> Result<fs::File, GravityError> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Could not open results file {} {e:?}",
+                path.display()
+            ))
+        })?;
+    if is_new {
+        writeln!(file, "{RESULTS_HEADER}").map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Failed to write results file {} {e:?}",
+                path.display()
+            ))
+        })?;
+    }
+    Ok(file)
+}
+
+/// The amount of every denom needed to send the transfers in `txs` transactions
+fn total_per_denom(
+    transfers: &[BulkTransfer],
+    fee: &Coin,
+    txs: usize,
+) -> Result<BTreeMap<String, Uint256>, GravityError> {
+    let overflow = || GravityError::ValidationError("Total of all transfers overflows".to_string());
+    let mut totals: BTreeMap<String, Uint256> = BTreeMap::new();
+    totals.insert(
+        fee.denom.clone(),
+        fee.amount
+            .checked_mul(Uint256::from_u64(txs as u64))
+            .ok_or_else(overflow)?,
+    );
+    for t in transfers {
+        let total = totals
+            .entry(t.transfer.amount.denom.clone())
+            .or_insert(u256!(0));
+        *total = total
+            .checked_add(t.transfer.amount.amount)
+            .and_then(|v| v.checked_add(t.transfer.bridge_fee.amount))
+            .ok_or_else(overflow)?;
+    }
+    Ok(totals)
+}
+
+/// The ids of all transfers from `address` that have not reached Ethereum yet
+async fn pending_ids(
+    grpc: &mut GravityQueryClient<Channel>,
+    address: CosmosAddress,
+) -> Result<HashSet<u64>, GravityError> {
+    let pending = get_pending_send_to_eth(grpc, address).await?;
+    Ok(pending
+        .unbatched_transfers
+        .iter()
+        .chain(pending.transfers_in_batches.iter())
+        .map(|tx| tx.id)
+        .collect())
+}
+
+fn coin_to_csv(coin: &Coin) -> String {
+    format!("{}{}", coin.amount, coin.denom)
+}
+
+#[test]
+fn test_parse_transfers_csv() {
+    let csv = "destination,amount,bridge_fee\n\
+        # payouts\n\
+        0x1bb537Aa56fFc7D608793BAFFC6c9C7De3c4F270, 1000anom, 10anom\n\
+        \n\
+        0x906313229CFB30959b39A5946099e4526625CBD4,2000anom,20anom\n";
+    let transfers = parse_transfers_csv(csv).unwrap();
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[0].line, 3);
+    assert_eq!(transfers[1].line, 5);
+    assert_eq!(
+        transfers[0].to_csv(),
+        format!("3,{},1000anom,10anom", transfers[0].transfer.destination)
+    );
+
+    let fee = Coin {
+        denom: "anom".to_string(),
+        amount: u256!(5),
+    };
+    let totals = total_per_denom(&transfers, &fee, 2).unwrap();
+    assert_eq!(totals["anom"], u256!(3040));
+
+    assert!(parse_transfers_csv("0x1bb537Aa56fFc7D608793BAFFC6c9C7De3c4F270,1000anom").is_err());
+    assert!(
+        parse_transfers_csv("0x1bb537Aa56fFc7D608793BAFFC6c9C7De3c4F270,1000anom,1stake").is_err()
+    );
+    assert!(parse_transfers_csv("notanaddress,1000anom,1anom").is_err());
+}
+
+#[test]
+fn test_transfers_per_tx() {
+    // 40M block gas, half of it budgeted: (20M - 100k) / 50k
+    assert_eq!(
+        transfers_per_tx(100_000, 50_000, Some(40_000_000), 1_048_576, 200),
+        398
+    );
+    // no block gas limit, only the transaction size counts: (1048576 - 1024) / 200
+    assert_eq!(
+        transfers_per_tx(100_000, 50_000, None, 1_048_576, 200),
+        5237
+    );
+    // the size limit is the tighter one
+    assert_eq!(
+        transfers_per_tx(100_000, 50_000, Some(40_000_000), 21_024, 200),
+        100
+    );
+    // always send at least one transfer per transaction
+    assert_eq!(transfers_per_tx(100_000, 50_000, Some(100_000), 0, 200), 1);
+    assert_eq!(transfers_per_tx(0, 0, None, 1_048_576, 0), 1_047_552);
+}
@@ -6,19 +6,29 @@ use gravity_proto::gravity::QueryDenomToErc20Request;
 use gravity_utils::{
     connection_prep::{check_for_eth, create_rpc_connections},
     error::GravityError,
+    types::GravityBridgeToolsConfig,
     web30::types::SendTxOption,
 };
 use tokio::time::sleep;
 
-use crate::{args::DeployErc20RepresentationOpts, utils::TIMEOUT};
+use crate::{
+    args::DeployErc20RepresentationOpts,
+    config::{resolve_ethereum_key, resolve_gravity_contract_address},
+    utils::TIMEOUT,
+};
 
 pub async fn deploy_erc20_representation(
     args: DeployErc20RepresentationOpts,
     address_prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let grpc_url = args.cosmos_grpc;
-    let ethereum_rpc = args.ethereum_rpc;
-    let ethereum_key = args.ethereum_key;
+    let grpc_url = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+    let ethereum_key = resolve_ethereum_key(args.ethereum_key, &config.keys)?;
     let denom = args.cosmos_denom;
 
     let connections =
@@ -31,18 +41,12 @@ pub async fn deploy_erc20_representation(
     let ethereum_public_key = ethereum_key.to_address();
     check_for_eth(ethereum_public_key, &web3).await?;
 
-    let contract_address = if let Some(c) = args.gravity_contract_address {
-        c
-    } else {
-        let params = get_gravity_params(&mut grpc).await.unwrap();
-        let c = params.bridge_ethereum_address.parse();
-        if c.is_err() {
-            return Err(GravityError::UnrecoverableError(
-                "The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into(),
-            ));
-        }
-        c.unwrap()
-    };
+    let params = get_gravity_params(&mut grpc).await?;
+    let contract_address = resolve_gravity_contract_address(
+        args.gravity_contract_address,
+        &config.connection,
+        &params,
+    )?;
 
     let res = grpc
         .denom_to_erc20(QueryDenomToErc20Request {
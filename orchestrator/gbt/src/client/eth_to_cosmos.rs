@@ -1,19 +1,48 @@
-use ethereum_gravity::{send_to_cosmos::send_to_cosmos, utils::get_valset_nonce};
+use ethereum_gravity::{
+    send_to_cosmos::{send_to_cosmos_with_approval, wrap_eth, Erc20Approval},
+    utils::get_valset_nonce,
+};
 use gravity_utils::{
     connection_prep::{check_for_eth, create_rpc_connections},
     error::GravityError,
-    num_conversion::fraction_to_exponent,
+    num_conversion::DecimalAmount,
+    types::GravityBridgeToolsConfig,
+    web30::amm::WETH_CONTRACT_ADDRESS,
 };
 
-use crate::{args::EthToCosmosOpts, utils::TIMEOUT};
+use crate::{args::EthToCosmosOpts, config::resolve_ethereum_key, utils::TIMEOUT};
 
-pub async fn eth_to_cosmos(args: EthToCosmosOpts, prefix: String) -> Result<(), GravityError> {
-    let gravity_address = args.gravity_contract_address;
-    let erc20_address = args.token_contract_address;
+pub async fn eth_to_cosmos(
+    args: EthToCosmosOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let gravity_address = match args
+        .gravity_contract_address
+        .or(config.connection.gravity_contract_address)
+    {
+        Some(address) => address,
+        None => {
+            return Err(GravityError::ValidationError(
+                "No Gravity contract address provided! Use --gravity-contract-address or set connection.gravity_contract_address".into(),
+            ))
+        }
+    };
+    let erc20_address = match args.token_contract_address {
+        Some(address) => address,
+        None => args.weth_contract_address.unwrap_or(*WETH_CONTRACT_ADDRESS),
+    };
+    let approval = if args.exact_approval {
+        Erc20Approval::Exact
+    } else {
+        Erc20Approval::Unlimited
+    };
     let cosmos_dest = args.destination;
-    let ethereum_key = args.ethereum_key;
+    let ethereum_key = resolve_ethereum_key(args.ethereum_key, &config.keys)?;
     let ethereum_public_key = ethereum_key.to_address();
-    let ethereum_rpc = args.ethereum_rpc;
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
     let amount = args.amount;
 
     let connections = create_rpc_connections(prefix, None, Some(ethereum_rpc), TIMEOUT).await;
@@ -31,34 +60,63 @@ pub async fn eth_to_cosmos(args: EthToCosmosOpts, prefix: String) -> Result<(),
         .await
         .expect("Failed to query ERC20 contract");
     let decimals: u8 = res.to_string().parse().unwrap();
-    let amount = fraction_to_exponent(amount, decimals);
+    let amount = amount.to_base_units(decimals)?;
 
-    let erc20_balance = web3
+    let mut erc20_balance = web3
         .get_erc20_balance(erc20_address, ethereum_public_key)
         .await
         .expect("Failed to get balance, check ERC20 contract address");
 
+    if args.wrap_eth && erc20_balance < amount {
+        // only the WETH that is missing is wrapped, so resending after a failed send
+        // does not wrap the amount twice
+        let to_wrap = amount.checked_sub(erc20_balance).unwrap();
+        let eth_balance = web3.eth_get_balance(ethereum_public_key).await?;
+        if eth_balance < to_wrap {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Insufficient ETH to wrap {} > {}",
+                DecimalAmount::from_base_units(to_wrap, decimals),
+                DecimalAmount::from_base_units(eth_balance, decimals)
+            )));
+        }
+        let txid = wrap_eth(erc20_address, to_wrap, ethereum_key, TIMEOUT, &web3).await?;
+        info!(
+            "Wrapped {} ETH into WETH txid: {:#066x}",
+            DecimalAmount::from_base_units(to_wrap, decimals),
+            txid
+        );
+        erc20_balance = web3
+            .get_erc20_balance(erc20_address, ethereum_public_key)
+            .await?;
+    }
+
     if erc20_balance.is_zero() {
         return Err(GravityError::UnrecoverableError(format!(
             "You have zero {erc20_address} tokens, please double check your sender and erc20 addresses!"
         )));
     } else if amount > erc20_balance {
         return Err(GravityError::UnrecoverableError(format!(
-            "Insufficient balance {amount} > {erc20_balance}"
+            "Insufficient balance {} > {}",
+            DecimalAmount::from_base_units(amount, decimals),
+            DecimalAmount::from_base_units(erc20_balance, decimals)
         )));
     }
 
     info!(
         "Sending {} / {} to Cosmos from {} to {}",
-        amount, erc20_address, ethereum_public_key, cosmos_dest
+        DecimalAmount::from_base_units(amount, decimals),
+        erc20_address,
+        ethereum_public_key,
+        cosmos_dest
     );
     // we send some erc20 tokens to the gravity contract to register a deposit
-    let res = send_to_cosmos(
+    let res = send_to_cosmos_with_approval(
         erc20_address,
         gravity_address,
         amount,
         cosmos_dest,
         ethereum_key,
+        approval,
         TIMEOUT,
         &web3,
         vec![],
@@ -76,5 +134,6 @@ pub async fn eth_to_cosmos(args: EthToCosmosOpts, prefix: String) -> Result<(),
         "Your tokens should show up in the account {} on Gravity Bridge within 10 minutes",
         cosmos_dest
     );
+    info!("Use `gbt client track --eth-txid <txid> --wait` to follow the deposit");
     Ok(())
 }
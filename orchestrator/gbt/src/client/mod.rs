@@ -1,3 +1,8 @@
+pub mod cancel_send_to_eth;
 pub mod cosmos_to_eth;
+pub mod cosmos_to_eth_bulk;
 pub mod deploy_erc20_representation;
 pub mod eth_to_cosmos;
+pub mod pending_sends;
+pub mod suggest_bridge_fee;
+pub mod track;
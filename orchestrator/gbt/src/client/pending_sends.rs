@@ -0,0 +1,79 @@
+use std::convert::TryFrom;
+
+use cosmos_gravity::query::{get_latest_transaction_batches, get_pending_send_to_eth};
+use gravity_utils::{
+    connection_prep::create_rpc_connections,
+    error::GravityError,
+    types::{BatchTransaction, GravityBridgeToolsConfig},
+};
+
+use crate::{args::PendingSendsOpts, config::resolve_cosmos_key, utils::TIMEOUT};
+
+pub async fn pending_sends(
+    args: PendingSendsOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let address = match args.address {
+        Some(address) => address,
+        None => resolve_cosmos_key(args.cosmos_phrase, &config.keys)?
+            .to_address(&address_prefix)
+            .unwrap(),
+    };
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+
+    let connections =
+        create_rpc_connections(address_prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+    let mut grpc = connections.grpc.unwrap();
+
+    let pending = get_pending_send_to_eth(&mut grpc, address).await?;
+    if pending.unbatched_transfers.is_empty() && pending.transfers_in_batches.is_empty() {
+        info!("No pending transfers to Ethereum from {}", address);
+        return Ok(());
+    }
+
+    if !pending.unbatched_transfers.is_empty() {
+        info!(
+            "Unbatched transfers from {}, these can be cancelled with `gbt client cancel-send-to-eth`",
+            address
+        );
+    }
+    for tx in pending.unbatched_transfers {
+        print_transfer(&BatchTransaction::try_from(tx)?, None);
+    }
+
+    if !pending.transfers_in_batches.is_empty() {
+        info!(
+            "Transfers from {} in batches waiting to be relayed to Ethereum",
+            address
+        );
+        let batches = get_latest_transaction_batches(&mut grpc).await?;
+        for tx in pending.transfers_in_batches {
+            let tx = BatchTransaction::try_from(tx)?;
+            let batch_nonce = batches
+                .iter()
+                .find(|b| b.transactions.iter().any(|t| t.id == tx.id))
+                .map(|b| b.nonce);
+            print_transfer(&tx, batch_nonce);
+        }
+    }
+    Ok(())
+}
+
+fn print_transfer(tx: &BatchTransaction, batch_nonce: Option<u64>) {
+    let batch = match batch_nonce {
+        Some(nonce) => format!(" in batch {nonce}"),
+        None => String::new(),
+    };
+    info!(
+        "Id {}{}: {} of token {} to {} with bridge fee {}",
+        tx.id,
+        batch,
+        tx.erc20_token.amount,
+        tx.erc20_token.token_contract_address,
+        tx.destination,
+        tx.erc20_fee.amount
+    );
+}
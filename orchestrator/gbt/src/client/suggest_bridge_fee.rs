@@ -0,0 +1,84 @@
+use cosmos_gravity::fees::{
+    suggest_bridge_fee as get_bridge_fee_suggestion, BridgeFeeSuggestion, DEFAULT_BRIDGE_FEE_MARGIN,
+};
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{
+    clarity::constants::ZERO_ADDRESS,
+    connection_prep::create_rpc_connections,
+    error::GravityError,
+    num_conversion::{print_eth, print_gwei, DecimalAmount},
+    types::{BatchRelayingMode, GravityBridgeToolsConfig},
+    web30::client::Web3,
+};
+use tonic::transport::Channel;
+
+use crate::{args::SuggestBridgeFeeOpts, utils::TIMEOUT};
+
+pub async fn suggest_bridge_fee(
+    args: SuggestBridgeFeeOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+    let connections = create_rpc_connections(
+        address_prefix,
+        Some(cosmos_grpc),
+        Some(ethereum_rpc),
+        TIMEOUT,
+    )
+    .await;
+    let web3 = connections.web3.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    let suggestion = bridge_fee_suggestion(&mut grpc, &web3, args.denom, config).await?;
+    print_bridge_fee_suggestion(&suggestion);
+    Ok(())
+}
+
+/// Gets a bridge fee suggestion using the margin of the local relayer config as an
+/// estimate of what relayers expect
+pub async fn bridge_fee_suggestion(
+    grpc: &mut GravityQueryClient<Channel>,
+    web3: &Web3,
+    denom: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<BridgeFeeSuggestion, GravityError> {
+    let margin = match &config.relayer.batch_relaying_mode {
+        BatchRelayingMode::ProfitableOnly { margin }
+        | BatchRelayingMode::ProfitableWithWhitelist { margin, .. } => *margin,
+        BatchRelayingMode::EveryBatch => DEFAULT_BRIDGE_FEE_MARGIN,
+    };
+    // the caller is only used for read only calls
+    get_bridge_fee_suggestion(grpc, web3, denom, ZERO_ADDRESS, margin).await
+}
+
+pub fn print_bridge_fee_suggestion(suggestion: &BridgeFeeSuggestion) {
+    info!(
+        "{} is bridged as ERC20 {}",
+        suggestion.denom, suggestion.token
+    );
+    info!(
+        "A batch including your transfer is estimated to cost {} Gas @ {} gwei / {} ETH to relay",
+        suggestion.batch_gas,
+        print_gwei(suggestion.gas_price),
+        print_eth(suggestion.batch_cost)
+    );
+    info!(
+        "{} transfers are waiting to be batched with {} {} in fees worth {} ETH",
+        suggestion.pool_txs,
+        DecimalAmount::from_base_units(suggestion.pool_fees, suggestion.decimals),
+        suggestion.denom,
+        print_eth(suggestion.pool_fees_weth)
+    );
+    info!(
+        "Suggested bridge fee: {}{} ({} tokens)",
+        suggestion.fee,
+        suggestion.denom,
+        DecimalAmount::from_base_units(suggestion.fee, suggestion.decimals)
+    );
+}
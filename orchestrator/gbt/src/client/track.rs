@@ -0,0 +1,490 @@
+use std::{
+    fmt,
+    time::{Duration, Instant, SystemTime},
+};
+
+use cosmos_gravity::query::{
+    get_attestations, get_erc20_to_denom, get_gravity_params, get_last_observed_event_nonce,
+    get_latest_transaction_batches, get_pending_send_to_eth, get_transaction_batch_signatures,
+};
+use ethereum_gravity::utils::get_tx_batch_nonce;
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{
+    clarity::{u256, utils::hex_str_to_bytes, Address as EthAddress, Uint256},
+    connection_prep::create_rpc_connections,
+    deep_space::{Address as CosmosAddress, Contact},
+    error::GravityError,
+    types::{
+        event_signatures::{SENT_TO_COSMOS_EVENT_SIG, TRANSACTION_BATCH_EXECUTED_EVENT_SIG},
+        GravityBridgeToolsConfig, SendToCosmosEvent, TransactionBatchExecutedEvent,
+    },
+    web30::{client::Web3, types::Log},
+};
+use relayer::find_latest_valset::find_latest_valset;
+use tokio::time::sleep;
+use tonic::transport::Channel;
+
+use crate::{
+    args::TrackOpts,
+    config::{resolve_cosmos_key, resolve_gravity_contract_address},
+    status::{attestation_event_nonce, signature_power, SignaturePower},
+    utils::TIMEOUT,
+};
+
+/// How often the transfer is checked again when waiting for it to arrive
+const TRACK_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// The number of blocks requested from the Ethereum node in a single log query
+const BLOCKS_TO_SEARCH: Uint256 = u256!(5_000);
+/// How far back from the latest block events are searched for, roughly a week of Ethereum blocks
+const MAX_BLOCKS_TO_SEARCH: Uint256 = u256!(50_000);
+
+/// The stages an Ethereum to Cosmos deposit goes through
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DepositStage {
+    /// the deposit event could not be found in recent Ethereum blocks
+    NotFound,
+    /// the deposit is on Ethereum but no validator has attested to it yet
+    Deposited { event_nonce: u64, block: Uint256 },
+    /// some validators have attested to the deposit, but not enough to observe it
+    Attesting { event_nonce: u64, votes: usize },
+    /// the deposit was observed and the tokens credited to the destination
+    Credited {
+        event_nonce: u64,
+        destination: String,
+        balance: Option<String>,
+    },
+}
+
+impl DepositStage {
+    fn is_final(&self) -> bool {
+        matches!(self, DepositStage::Credited { .. })
+    }
+}
+
+impl fmt::Display for DepositStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DepositStage::NotFound => write!(
+                f,
+                "Deposit not found on Ethereum yet, it may still be waiting to be mined"
+            ),
+            DepositStage::Deposited { event_nonce, block } => write!(
+                f,
+                "Deposited on Ethereum in block {block} with event nonce {event_nonce}, waiting for validators to attest to it"
+            ),
+            DepositStage::Attesting { event_nonce, votes } => write!(
+                f,
+                "Event nonce {event_nonce} has {votes} validator votes, waiting for enough to observe it"
+            ),
+            DepositStage::Credited {
+                event_nonce,
+                destination,
+                balance,
+            } => match balance {
+                Some(balance) => write!(
+                    f,
+                    "Event nonce {event_nonce} observed, tokens credited to {destination} which now holds {balance}"
+                ),
+                None => write!(
+                    f,
+                    "Event nonce {event_nonce} observed, tokens credited to {destination}"
+                ),
+            },
+        }
+    }
+}
+
+/// The stages a Cosmos to Ethereum transfer goes through
+#[derive(Debug, Clone, PartialEq)]
+enum WithdrawStage {
+    /// the transfer is not in a batch and not pending for the given sender, it may not be
+    /// visible yet, or it was cancelled or completed before we started tracking it
+    NotFound,
+    /// the transfer is in the pool waiting to be put in a batch
+    Unbatched,
+    /// the transfer is in a batch that validators are signing
+    Batched {
+        token: EthAddress,
+        nonce: u64,
+        signatures: SignaturePower,
+    },
+    /// the batch has been submitted to Ethereum but not yet observed on Cosmos
+    Relayed { token: EthAddress, nonce: u64 },
+    /// the batch executed on Ethereum and was observed on Cosmos
+    Executed {
+        token: EthAddress,
+        nonce: u64,
+        block: Option<Uint256>,
+    },
+}
+
+impl WithdrawStage {
+    fn is_final(&self) -> bool {
+        matches!(self, WithdrawStage::Executed { .. })
+    }
+}
+
+impl fmt::Display for WithdrawStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WithdrawStage::NotFound => write!(
+                f,
+                "Transfer not found in the pool or any batch yet, it may have been cancelled or already sent to Ethereum"
+            ),
+            WithdrawStage::Unbatched => write!(f, "Pending, waiting to be put in a batch"),
+            WithdrawStage::Batched {
+                token,
+                nonce,
+                signatures,
+            } => {
+                if signatures.enough_power {
+                    write!(
+                        f,
+                        "In batch {nonce} for {token}, signed with {:.2}% power, waiting for a relayer",
+                        signatures.percent
+                    )
+                } else {
+                    write!(
+                        f,
+                        "In batch {nonce} for {token}, signed with {:.2}% power, waiting for signatures",
+                        signatures.percent
+                    )
+                }
+            }
+            WithdrawStage::Relayed { token, nonce } => write!(
+                f,
+                "Batch {nonce} for {token} relayed to Ethereum, waiting for validators to observe it"
+            ),
+            WithdrawStage::Executed { token, nonce, block } => match block {
+                Some(block) => write!(
+                    f,
+                    "Batch {nonce} for {token} executed on Ethereum in block {block}, transfer complete"
+                ),
+                None => write!(
+                    f,
+                    "Batch {nonce} for {token} executed on Ethereum, transfer complete"
+                ),
+            },
+        }
+    }
+}
+
+pub async fn track(
+    args: TrackOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+
+    let connections = create_rpc_connections(
+        address_prefix.clone(),
+        Some(cosmos_grpc),
+        Some(ethereum_rpc),
+        TIMEOUT,
+    )
+    .await;
+    let contact = connections.contact.unwrap();
+    let web3 = connections.web3.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    let params = get_gravity_params(&mut grpc).await?;
+    let gravity_contract_address = resolve_gravity_contract_address(
+        args.gravity_contract_address,
+        &config.connection,
+        &params,
+    )?;
+
+    if let Some(txid) = args.eth_txid {
+        let txid = hex_str_to_bytes(&txid).map_err(|e| {
+            GravityError::ValidationError(format!("Invalid Ethereum txid {txid}: {e:?}"))
+        })?;
+        let mut deposit = None;
+        let mut last_stage = None;
+        let started = Instant::now();
+        loop {
+            if deposit.is_none() {
+                deposit = find_deposit(&web3, gravity_contract_address, &txid).await?;
+            }
+            let stage = match &deposit {
+                Some(deposit) => get_deposit_stage(&mut grpc, &contact, deposit).await?,
+                None => DepositStage::NotFound,
+            };
+            if last_stage.as_ref() != Some(&stage) {
+                log_stage(started, &stage);
+            }
+            if stage.is_final() || !args.wait {
+                break;
+            }
+            check_timeout(started, args.timeout, &stage)?;
+            last_stage = Some(stage);
+            sleep(TRACK_POLL_INTERVAL).await;
+        }
+    } else if let Some(send_id) = args.send_id {
+        let sender = match args.sender {
+            Some(sender) => Some(sender),
+            None => resolve_cosmos_key(None, &config.keys)
+                .ok()
+                .map(|k| k.to_address(&address_prefix).unwrap()),
+        };
+        if sender.is_none() {
+            warn!("No --sender given, transfers that are not yet in a batch can not be found");
+        }
+        let mut last_stage: Option<WithdrawStage> = None;
+        let started = Instant::now();
+        loop {
+            let stage = get_withdraw_stage(
+                &mut grpc,
+                &web3,
+                gravity_contract_address,
+                send_id,
+                sender,
+                last_stage.as_ref(),
+            )
+            .await?;
+            if last_stage.as_ref() != Some(&stage) {
+                log_stage(started, &stage);
+            }
+            if stage.is_final() || !args.wait {
+                break;
+            }
+            check_timeout(started, args.timeout, &stage)?;
+            last_stage = Some(stage);
+            sleep(TRACK_POLL_INTERVAL).await;
+        }
+    }
+    Ok(())
+}
+
+/// Prints a stage the transfer reached with the time and how long tracking has been running
+fn log_stage(started: Instant, stage: &impl fmt::Display) {
+    info!(
+        "[{} +{}s] {}",
+        humantime::format_rfc3339_seconds(SystemTime::now()),
+        started.elapsed().as_secs(),
+        stage
+    );
+}
+
+fn check_timeout(
+    started: Instant,
+    timeout: u64,
+    stage: &impl fmt::Display,
+) -> Result<(), GravityError> {
+    if started.elapsed() >= Duration::from_secs(timeout) {
+        return Err(GravityError::UnrecoverableError(format!(
+            "Gave up waiting after {timeout}s, the transfer is still at: {stage}"
+        )));
+    }
+    Ok(())
+}
+
+/// Finds the deposit event emitted by the given Ethereum transaction
+async fn find_deposit(
+    web3: &Web3,
+    gravity_contract_address: EthAddress,
+    txid: &[u8],
+) -> Result<Option<SendToCosmosEvent>, GravityError> {
+    find_recent_event(
+        web3,
+        gravity_contract_address,
+        SENT_TO_COSMOS_EVENT_SIG,
+        |log| match &log.transaction_hash {
+            Some(hash) if hash[..] == txid[..] => Ok(Some(SendToCosmosEvent::from_log(log)?)),
+            _ => Ok(None),
+        },
+    )
+    .await
+}
+
+async fn get_deposit_stage(
+    grpc: &mut GravityQueryClient<Channel>,
+    contact: &Contact,
+    deposit: &SendToCosmosEvent,
+) -> Result<DepositStage, GravityError> {
+    // events are observed in order, so anything up to the last observed nonce has been observed
+    if deposit.event_nonce > get_last_observed_event_nonce(grpc).await? {
+        let attestations = get_attestations(grpc, None).await?;
+        let attestation = attestations
+            .iter()
+            .find(|a| attestation_event_nonce(a) == Some(deposit.event_nonce));
+        match attestation {
+            // the attestation itself is the final word if the power changed since it was observed
+            Some(attestation) if attestation.observed => (),
+            Some(attestation) => {
+                return Ok(DepositStage::Attesting {
+                    event_nonce: deposit.event_nonce,
+                    votes: attestation.votes.len(),
+                })
+            }
+            None => {
+                return Ok(DepositStage::Deposited {
+                    event_nonce: deposit.event_nonce,
+                    block: deposit.block_height,
+                })
+            }
+        }
+    }
+
+    let balance = match deposit.validated_destination {
+        Some(destination) => {
+            let denom = get_erc20_to_denom(grpc, deposit.erc20).await?.denom;
+            contact
+                .get_balance(destination, denom)
+                .await?
+                .map(|c| format!("{}{}", c.amount, c.denom))
+        }
+        // invalid destinations are sent to the community pool
+        None => None,
+    };
+    Ok(DepositStage::Credited {
+        event_nonce: deposit.event_nonce,
+        destination: match deposit.validated_destination {
+            Some(destination) => destination.to_string(),
+            None => "the community pool".to_string(),
+        },
+        balance,
+    })
+}
+
+async fn get_withdraw_stage(
+    grpc: &mut GravityQueryClient<Channel>,
+    web3: &Web3,
+    gravity_contract_address: EthAddress,
+    send_id: u64,
+    sender: Option<CosmosAddress>,
+    last_stage: Option<&WithdrawStage>,
+) -> Result<WithdrawStage, GravityError> {
+    if let Some(sender) = sender {
+        let pending = get_pending_send_to_eth(grpc, sender).await?;
+        if pending
+            .unbatched_transfers
+            .iter()
+            .any(|tx| tx.id == send_id)
+        {
+            return Ok(WithdrawStage::Unbatched);
+        }
+    }
+
+    let batches = get_latest_transaction_batches(grpc).await?;
+    if let Some(batch) = batches
+        .iter()
+        .find(|b| b.transactions.iter().any(|tx| tx.id == send_id))
+    {
+        let token = batch.token_contract;
+        let nonce = batch.nonce;
+        let last_nonce = get_tx_batch_nonce(
+            gravity_contract_address,
+            token,
+            gravity_contract_address,
+            web3,
+        )
+        .await?;
+        if last_nonce >= nonce {
+            return Ok(WithdrawStage::Relayed { token, nonce });
+        }
+
+        let ethereum_valset = find_latest_valset(grpc, gravity_contract_address, web3).await?;
+        let signers: Vec<EthAddress> = get_transaction_batch_signatures(grpc, nonce, token)
+            .await?
+            .iter()
+            .map(|s| s.ethereum_signer)
+            .collect();
+        return Ok(WithdrawStage::Batched {
+            token,
+            nonce,
+            signatures: signature_power(&ethereum_valset, &signers),
+        });
+    }
+
+    // the transfer has left the chain, we can only tell where it went if we saw its batch earlier
+    match last_stage {
+        Some(WithdrawStage::Batched { token, nonce, .. })
+        | Some(WithdrawStage::Relayed { token, nonce }) => {
+            let (token, nonce) = (*token, *nonce);
+            let last_nonce = get_tx_batch_nonce(
+                gravity_contract_address,
+                token,
+                gravity_contract_address,
+                web3,
+            )
+            .await?;
+            if last_nonce < nonce {
+                // the batch timed out and the transfer went back into the pool
+                return Ok(WithdrawStage::Unbatched);
+            }
+            let executed = find_recent_event(
+                web3,
+                gravity_contract_address,
+                TRANSACTION_BATCH_EXECUTED_EVENT_SIG,
+                |log| {
+                    let event = TransactionBatchExecutedEvent::from_log(log)?;
+                    if event.erc20 == token && event.batch_nonce == nonce {
+                        Ok(Some(event))
+                    } else {
+                        Ok(None)
+                    }
+                },
+            )
+            .await?;
+            Ok(WithdrawStage::Executed {
+                token,
+                nonce,
+                block: executed.map(|e| e.block_height),
+            })
+        }
+        _ => Ok(WithdrawStage::NotFound),
+    }
+}
+
+/// Searches the Gravity contract logs for an event, starting at the latest block and working
+/// backwards, returning the first one accepted by `filter`
+async fn find_recent_event<T, F>(
+    web3: &Web3,
+    gravity_contract_address: EthAddress,
+    event_sig: &str,
+    filter: F,
+) -> Result<Option<T>, GravityError>
+where
+    F: Fn(&Log) -> Result<Option<T>, GravityError>,
+{
+    let latest_block = web3.eth_block_number().await?;
+    let oldest_block = latest_block
+        .checked_sub(MAX_BLOCKS_TO_SEARCH)
+        .unwrap_or(u256!(0));
+    let mut current_block = latest_block;
+
+    while current_block > oldest_block {
+        let start_block = match current_block.checked_sub(BLOCKS_TO_SEARCH) {
+            Some(block) if block > oldest_block => block,
+            _ => oldest_block,
+        };
+        let logs = web3
+            .check_for_events(
+                start_block,
+                Some(current_block),
+                vec![gravity_contract_address],
+                vec![event_sig],
+            )
+            .await?;
+        for log in logs.iter().rev() {
+            if let Some(event) = filter(log)? {
+                return Ok(Some(event));
+            }
+        }
+        current_block = start_block;
+    }
+    Ok(None)
+}
+
+#[test]
+fn test_check_timeout() {
+    let started = Instant::now();
+    assert!(check_timeout(started, 3600, &WithdrawStage::NotFound).is_ok());
+    let err = check_timeout(started, 0, &WithdrawStage::NotFound).unwrap_err();
+    assert!(err.to_string().contains("not found in the pool"), "{}", err);
+}
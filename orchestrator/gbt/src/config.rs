@@ -1,16 +1,36 @@
 //! Handles configuration structs + saving and loading for Gravity bridge tools
 
 use std::{
+    convert::TryInto,
+    env,
+    fmt::Debug,
     fs::{self, create_dir},
     path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime},
 };
 
+use gravity_proto::gravity::Params;
 use gravity_utils::{
+    clarity::{constants::ZERO_ADDRESS, Address as EthAddress, PrivateKey as EthPrivateKey},
+    deep_space::{Coin, PrivateKey as CosmosPrivateKey},
     error::GravityError,
-    types::{GravityBridgeToolsConfig, TomlGravityBridgeToolsConfig},
+    types::{ConnectionConfig, GravityBridgeToolsConfig, KeysConfig, TomlGravityBridgeToolsConfig},
 };
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::{
+        mpsc::{self, error::TrySendError},
+        watch,
+    },
+    time::sleep,
+};
+use toml::{value::Table, Value};
 
-use crate::args::InitOpts;
+use crate::{
+    args::{ConfigCheckOpts, ConfigMigrateOpts, InitOpts},
+    utils::print_relaying_explanation,
+};
 
 /// The name of the config file, this file is copied
 /// from default-config.toml when generated so that we
@@ -18,6 +38,19 @@ use crate::args::InitOpts;
 pub const CONFIG_NAME: &str = "config.toml";
 /// The folder name for the config
 pub const CONFIG_FOLDER: &str = ".gbt";
+/// The prefix of environment variables that override config file settings
+pub const ENV_PREFIX: &str = "GBT_";
+/// How often the config file is checked for changes while running, sending the process a
+/// SIGHUP reloads it right away
+pub const CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+/// The current version of the config file format, config files without a
+/// version predate versioning and are treated as version 0
+pub const CONFIG_VERSION: i64 = 1;
+
+/// Steps upgrading the config file format, the step at index `n` upgrades
+/// a version `n` config to version `n + 1`. Any change that renames or moves
+/// a key must bump `CONFIG_VERSION` and add a step here
+const MIGRATIONS: &[fn(&mut Table)] = &[migrate_v0_to_v1];
 
 /// Creates the config directory and default config file if it does
 /// not already exist
@@ -61,26 +94,487 @@ pub fn get_home_dir(home_arg: Option<PathBuf>) -> Result<PathBuf, GravityError>
     }
 }
 
-/// Load the config file, this operates at runtime
-pub fn load_config(home_dir: &Path) -> Result<GravityBridgeToolsConfig, GravityError> {
-    let config_file = home_dir.join(CONFIG_FOLDER).with_file_name(CONFIG_NAME);
-    if !config_file.exists() {
-        return Ok(GravityBridgeToolsConfig::default());
+/// Load the config file, this operates at runtime. Settings are resolved in layers, the
+/// built in defaults are overridden by the config file, which is in turn overridden by
+/// `GBT_*` environment variables. Command line flags are applied last by each command.
+/// If the home directory was explicitly provided the config file must exist.
+pub fn load_config(
+    home_dir: &Path,
+    explicit_home: bool,
+) -> Result<GravityBridgeToolsConfig, GravityError> {
+    let config_file = config_file_path(home_dir);
+    let mut config = if config_file.exists() {
+        load_config_file(&config_file)?
+    } else if explicit_home {
+        return Err(GravityError::UnrecoverableError(format!(
+            "No config file found at {}, run `gbt --home {} init` to create one",
+            config_file.display(),
+            home_dir.display()
+        )));
+    } else {
+        info!(
+            "No config file found at {}, using the default config",
+            config_file.display()
+        );
+        GravityBridgeToolsConfig::default()
+    };
+    apply_env_overrides(&mut config)?;
+    Ok(config)
+}
+
+/// The location of the config file used by `load_config`
+pub fn config_file_path(home_dir: &Path) -> PathBuf {
+    home_dir.join(CONFIG_NAME)
+}
+
+/// Reloads the config file when it changes or a reload is requested on `reloads`, and shares
+/// the new config with the running loops, the relayer applies its section on its next loop
+/// iteration. A config that fails to load or validate is logged and ignored, everything keeps
+/// running with the last valid config
+pub async fn watch_config(
+    home_dir: PathBuf,
+    batch_requests: bool,
+    sender: watch::Sender<GravityBridgeToolsConfig>,
+    reloads: mpsc::Receiver<()>,
+) {
+    let config_file = config_file_path(&home_dir);
+    let mut last_modified = modified_time(&config_file);
+    let mut reloads = Some(reloads);
+    loop {
+        let reload_requested = tokio::select! {
+            _ = sleep(CONFIG_RELOAD_INTERVAL) => false,
+            requested = recv_reload(&mut reloads) => requested,
+        };
+        let modified = modified_time(&config_file);
+        if !reload_requested && modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+        if reload_requested {
+            info!("Reload requested, reloading {}", config_file.display());
+        }
+        if !reload_config(&home_dir, batch_requests, &sender) {
+            // the loops have stopped, there is nobody left to update
+            return;
+        }
+    }
+}
+
+/// Reload requests for `watch_config`, one for each SIGHUP the process receives. Without
+/// SIGHUP the config is only reloaded when the file changes
+pub fn hangup_reloads() -> mpsc::Receiver<()> {
+    let (sender, receiver) = mpsc::channel(1);
+    match signal(SignalKind::hangup()) {
+        Ok(mut hangup) => {
+            tokio::spawn(async move {
+                while hangup.recv().await.is_some() {
+                    info!("Received SIGHUP");
+                    // a reload that is already pending picks up this change as well
+                    if let Err(TrySendError::Closed(_)) = sender.try_send(()) {
+                        return;
+                    }
+                }
+            });
+        }
+        Err(e) => warn!(
+            "Could not listen for SIGHUP, the config is only reloaded when it changes: {:?}",
+            e
+        ),
+    }
+    receiver
+}
+
+/// Waits for the next reload request, returns false once no more can arrive
+async fn recv_reload(reloads: &mut Option<mpsc::Receiver<()>>) -> bool {
+    match reloads {
+        Some(receiver) => {
+            if receiver.recv().await.is_some() {
+                return true;
+            }
+            *reloads = None;
+            false
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Loads the config file and sends it if it differs from the current config, returns false
+/// once nothing is listening for changes anymore
+fn reload_config(
+    home_dir: &Path,
+    batch_requests: bool,
+    sender: &watch::Sender<GravityBridgeToolsConfig>,
+) -> bool {
+    let config_file = config_file_path(home_dir);
+    let config = match load_config(home_dir, true) {
+        Ok(config) => config,
+        Err(e) => {
+            error!(
+                "Rejected changes to {}, keeping the current config: {}",
+                config_file.display(),
+                e
+            );
+            return !sender.is_closed();
+        }
+    };
+    let current = sender.borrow().clone();
+    if current == config {
+        return !sender.is_closed();
+    }
+    info!("Reloaded config from {}", config_file.display());
+    if current.relayer != config.relayer {
+        print_relaying_explanation(&config.relayer, batch_requests);
+    }
+    let other_sections_changed = GravityBridgeToolsConfig {
+        relayer: current.relayer.clone(),
+        ..config.clone()
+    } != current;
+    if other_sections_changed {
+        warn!("Only changes to the [relayer] section are applied while running, restart to apply the rest");
+    }
+    sender.send(config).is_ok()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Parses and validates a specific config file, config files in an older
+/// format are upgraded in memory
+pub fn load_config_file(config_file: &Path) -> Result<GravityBridgeToolsConfig, GravityError> {
+    let mut value = read_config_value(config_file)?;
+    let version = config_version(&value)?;
+    if version < CONFIG_VERSION {
+        warn!(
+            "Config file {} is version {}, run `gbt config migrate` to upgrade it to version {}",
+            config_file.display(),
+            version,
+            CONFIG_VERSION
+        );
+        migrate(&mut value, version);
+    }
+    parse_config_value(value)
+}
+
+fn read_config_value(config_file: &Path) -> Result<Value, GravityError> {
+    let config = match fs::read_to_string(config_file) {
+        Ok(config) => config,
+        Err(e) => {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Could not read config file {}! Run `gbt init` {e:?}",
+                config_file.display()
+            )))
+        }
+    };
+    match toml::from_str(&config) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(GravityError::UnrecoverableError(format!(
+            "Invalid config! {e:?}"
+        ))),
     }
+}
 
-    let config =
-        fs::read_to_string(config_file).expect("Could not find config file! Run `gbt init`");
-    let val: Result<TomlGravityBridgeToolsConfig, _> = toml::from_str(&config);
+fn parse_config_value(value: Value) -> Result<GravityBridgeToolsConfig, GravityError> {
+    let val: Result<TomlGravityBridgeToolsConfig, _> = value.try_into();
     match val {
-        Ok(v) => Ok(v.into()),
+        Ok(v) => v.try_into(),
         Err(e) => Err(GravityError::UnrecoverableError(format!(
             "Invalid config! {e:?}"
         ))),
     }
 }
 
+/// Reads the format version of a parsed config file
+fn config_version(value: &Value) -> Result<i64, GravityError> {
+    match value.get("version") {
+        None => Ok(0),
+        Some(Value::Integer(v)) if (0..=CONFIG_VERSION).contains(v) => Ok(*v),
+        Some(Value::Integer(v)) => Err(GravityError::ValidationError(format!(
+            "Config version {v} is not supported, this gbt supports up to version {CONFIG_VERSION}. Upgrade gbt"
+        ))),
+        Some(v) => Err(GravityError::ValidationError(format!(
+            "Invalid version {v}, it must be an integer"
+        ))),
+    }
+}
+
+/// Upgrades a parsed config file from `version` to `CONFIG_VERSION`
+fn migrate(value: &mut Value, version: i64) {
+    if let Value::Table(table) = value {
+        for (from, step) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            step(table);
+            table.insert("version".to_string(), Value::Integer(from as i64 + 1));
+        }
+    }
+}
+
+/// Version 1 dropped the redundant prefix of the metrics keys
+fn migrate_v0_to_v1(config: &mut Table) {
+    rename_key(config, "metrics", "metrics_enabled", "enabled");
+    rename_key(config, "metrics", "metrics_bind", "bind");
+}
+
+/// Renames a key within a section, if the new key is already set the old one is dropped
+fn rename_key(config: &mut Table, section: &str, from: &str, to: &str) {
+    if let Some(Value::Table(section)) = config.get_mut(section) {
+        if let Some(v) = section.remove(from) {
+            if !section.contains_key(to) {
+                section.insert(to.to_string(), v);
+            }
+        }
+    }
+}
+
+/// Upgrades a config file to the current format in place, the original file
+/// is first copied to a backup
+pub fn migrate_config(opts: ConfigMigrateOpts, home_dir: PathBuf) -> Result<(), GravityError> {
+    let config_file = opts
+        .config_file
+        .unwrap_or_else(|| config_file_path(&home_dir));
+    let mut value = read_config_value(&config_file)?;
+    let version = config_version(&value)?;
+    if version == CONFIG_VERSION {
+        info!(
+            "Config file {} is already version {}, nothing to migrate",
+            config_file.display(),
+            CONFIG_VERSION
+        );
+        return Ok(());
+    }
+
+    migrate(&mut value, version);
+    // never replace a working config with one that won't load
+    parse_config_value(value.clone())?;
+    let migrated = match toml::to_string_pretty(&value) {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Failed to serialize the migrated config {e:?}"
+            )))
+        }
+    };
+
+    let backup = config_file.with_extension(format!("toml.v{version}.bak"));
+    if let Err(e) = fs::copy(&config_file, &backup) {
+        return Err(GravityError::UnrecoverableError(format!(
+            "Failed to back up config file to {} {e:?}",
+            backup.display()
+        )));
+    }
+    if let Err(e) = fs::write(&config_file, migrated) {
+        return Err(GravityError::UnrecoverableError(format!(
+            "Failed to write migrated config file {} {e:?}",
+            config_file.display()
+        )));
+    }
+    info!(
+        "Migrated {} from version {} to {}, the original is saved as {}",
+        config_file.display(),
+        version,
+        CONFIG_VERSION,
+        backup.display()
+    );
+    info!("Comments are not preserved by migration, see the backup for them");
+    Ok(())
+}
+
+/// Loads and validates the config file, then prints the settings gbt will actually use
+pub fn check_config(opts: ConfigCheckOpts, home_dir: PathBuf) -> Result<(), GravityError> {
+    let config_file = opts
+        .config_file
+        .unwrap_or_else(|| home_dir.join(CONFIG_NAME));
+    let mut config = load_config_file(&config_file)?;
+    apply_env_overrides(&mut config)?;
+
+    info!("Config file {} is valid", config_file.display());
+    print_relaying_explanation(&config.relayer, true);
+    match serde_json::to_string_pretty(&config) {
+        Ok(resolved) => println!("{resolved}"),
+        Err(e) => {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Failed to serialize the resolved config {e:?}"
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Overrides config file settings with any `GBT_*` environment variables that are set
+pub fn apply_env_overrides(config: &mut GravityBridgeToolsConfig) -> Result<(), GravityError> {
+    apply_overrides(config, env_var)
+}
+
+/// Overrides config file settings with the values `var` finds for the `GBT_*` variable
+/// names, without the prefix
+fn apply_overrides(
+    config: &mut GravityBridgeToolsConfig,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<(), GravityError> {
+    if let Some(v) = var("COSMOS_GRPC") {
+        config.connection.cosmos_grpc = v;
+    }
+    if let Some(v) = var("ETHEREUM_RPC") {
+        config.connection.ethereum_rpc = v;
+    }
+    if let Some(v) = var("ADDRESS_PREFIX") {
+        config.connection.address_prefix = v;
+    }
+    if let Some(v) = var("GRAVITY_CONTRACT_ADDRESS") {
+        config.connection.gravity_contract_address =
+            Some(parse_env("GRAVITY_CONTRACT_ADDRESS", &v)?);
+    }
+    if let Some(v) = var("FEES") {
+        config.connection.fees = Some(parse_env("FEES", &v)?);
+    }
+    if let Some(v) = var("COSMOS_PHRASE_FILE") {
+        config.keys.cosmos_phrase_file = Some(PathBuf::from(v));
+    }
+    if let Some(v) = var("ETHEREUM_KEY_FILE") {
+        config.keys.ethereum_key_file = Some(PathBuf::from(v));
+    }
+    if let Some(v) = var("RELAYER_ENABLED") {
+        config.orchestrator.relayer_enabled = parse_env("RELAYER_ENABLED", &v)?;
+    }
+    if let Some(v) = var("METRICS_ENABLED") {
+        config.metrics.enabled = parse_env("METRICS_ENABLED", &v)?;
+    }
+    if let Some(v) = var("METRICS_BIND") {
+        config.metrics.bind = v;
+    }
+    if let Some(v) = var("METRICS_READINESS_LOOP_PERIODS") {
+        config.metrics.readiness_loop_periods = parse_env("METRICS_READINESS_LOOP_PERIODS", &v)?;
+    }
+    if let Some(v) = var("METRICS_LIVENESS_LOOP_PERIODS") {
+        config.metrics.liveness_loop_periods = parse_env("METRICS_LIVENESS_LOOP_PERIODS", &v)?;
+    }
+    if let Some(v) = var("METRICS_MIN_ETH_BALANCE") {
+        config.metrics.min_eth_balance = parse_env("METRICS_MIN_ETH_BALANCE", &v)?;
+    }
+    if let Some(v) = var("METRICS_MIN_FEE_BALANCE") {
+        config.metrics.min_fee_balance = parse_env("METRICS_MIN_FEE_BALANCE", &v)?;
+    }
+    if let Some(v) = var("WATCHDOG_ENABLED") {
+        config.watchdog.enabled = parse_env("WATCHDOG_ENABLED", &v)?;
+    }
+    if let Some(v) = var("WATCHDOG_WEBHOOK") {
+        config.watchdog.webhook = Some(v);
+    }
+    if let Some(v) = var("WATCHDOG_COMMAND") {
+        config.watchdog.command = Some(v);
+    }
+    Ok(())
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(format!("{ENV_PREFIX}{name}")).ok()
+}
+
+fn parse_env<T: FromStr>(name: &str, value: &str) -> Result<T, GravityError>
+where
+    T::Err: Debug,
+{
+    value.parse().map_err(|e| {
+        GravityError::ValidationError(format!("Invalid {ENV_PREFIX}{name} \"{value}\" {e:?}"))
+    })
+}
+
+/// Resolves the Cosmos key from, in order of precedence, the command line, the
+/// `GBT_COSMOS_PHRASE` environment variable or the file in `keys.cosmos_phrase_file`
+pub fn resolve_cosmos_key(
+    arg: Option<CosmosPrivateKey>,
+    keys: &KeysConfig,
+) -> Result<CosmosPrivateKey, GravityError> {
+    resolve_key(
+        arg,
+        "COSMOS_PHRASE",
+        &keys.cosmos_phrase_file,
+        "--cosmos-phrase",
+    )
+}
+
+/// Resolves the Ethereum key from, in order of precedence, the command line, the
+/// `GBT_ETHEREUM_KEY` environment variable or the file in `keys.ethereum_key_file`
+pub fn resolve_ethereum_key(
+    arg: Option<EthPrivateKey>,
+    keys: &KeysConfig,
+) -> Result<EthPrivateKey, GravityError> {
+    resolve_key(
+        arg,
+        "ETHEREUM_KEY",
+        &keys.ethereum_key_file,
+        "--ethereum-key",
+    )
+}
+
+fn resolve_key<T: FromStr>(
+    arg: Option<T>,
+    env_name: &str,
+    key_file: &Option<PathBuf>,
+    flag: &str,
+) -> Result<T, GravityError>
+where
+    T::Err: Debug,
+{
+    if let Some(key) = arg {
+        return Ok(key);
+    }
+    // the key itself is never included in error messages
+    if let Some(v) = env_var(env_name) {
+        return v.trim().parse().map_err(|_| {
+            GravityError::ValidationError(format!("Invalid key in {ENV_PREFIX}{env_name}"))
+        });
+    }
+    match key_file {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(v) => v.trim().parse().map_err(|_| {
+                GravityError::ValidationError(format!("Invalid key in {}", path.display()))
+            }),
+            Err(e) => Err(GravityError::UnrecoverableError(format!(
+                "Could not read key file {} {e:?}",
+                path.display()
+            ))),
+        },
+        None => Err(GravityError::ValidationError(format!(
+            "No key provided! Use {flag}, {ENV_PREFIX}{env_name} or set a key file in the [keys] section of config.toml"
+        ))),
+    }
+}
+
+/// Resolves the Cosmos chain fee from the command line or the config
+pub fn resolve_fees(
+    arg: Option<Coin>,
+    connection: &ConnectionConfig,
+) -> Result<Coin, GravityError> {
+    match arg.or_else(|| connection.fees.clone()) {
+        Some(fees) => Ok(fees),
+        None => Err(GravityError::ValidationError(format!(
+            "No fees provided! Use --fees, {ENV_PREFIX}FEES or set connection.fees in config.toml"
+        ))),
+    }
+}
+
+/// Resolves the Gravity contract address from the command line or the config, falling back
+/// to the address set in the Gravity module parameters
+pub fn resolve_gravity_contract_address(
+    arg: Option<EthAddress>,
+    connection: &ConnectionConfig,
+    params: &Params,
+) -> Result<EthAddress, GravityError> {
+    if let Some(c) = arg.or(connection.gravity_contract_address) {
+        return Ok(c);
+    }
+    match params.bridge_ethereum_address.parse() {
+        Ok(v) if v != ZERO_ADDRESS => Ok(v),
+        _ => Err(GravityError::UnrecoverableError(
+            "The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into(),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
     /// Test that the config is both valid toml for the struct and that it's values are
@@ -89,7 +583,201 @@ mod tests {
     fn test_default_config() {
         // make sure the default config default-config.toml is the same as the default config struct
         let res: TomlGravityBridgeToolsConfig = toml::from_str(&get_default_config()).unwrap();
-        let res: GravityBridgeToolsConfig = res.into();
+        let res: GravityBridgeToolsConfig = res.try_into().unwrap();
         assert_eq!(res, GravityBridgeToolsConfig::default());
+
+        let value: Value = toml::from_str(&get_default_config()).unwrap();
+        assert_eq!(config_version(&value).unwrap(), CONFIG_VERSION);
+    }
+
+    /// Test that an unversioned config is upgraded to the current version
+    #[test]
+    fn test_migrate_unversioned_config() {
+        let mut value: Value = toml::from_str(
+            r#"
+            [orchestrator]
+            relayer_enabled = true
+
+            [metrics]
+            metrics_enabled = true
+            metrics_bind = "0.0.0.0:6631"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config_version(&value).unwrap(), 0);
+
+        migrate(&mut value, 0);
+        assert_eq!(config_version(&value).unwrap(), CONFIG_VERSION);
+        let res = parse_config_value(value).unwrap();
+        assert!(res.orchestrator.relayer_enabled);
+        assert!(res.metrics.enabled);
+        assert_eq!(res.metrics.bind, "0.0.0.0:6631");
+    }
+
+    /// Test that the old metrics key names are still read from an up to date config
+    #[test]
+    fn test_old_metrics_keys() {
+        let value: Value = toml::from_str(&format!(
+            r#"
+            version = {CONFIG_VERSION}
+
+            [metrics]
+            metrics_enabled = true
+            metrics_bind = "0.0.0.0:6631"
+            "#
+        ))
+        .unwrap();
+        let res = parse_config_value(value).unwrap();
+        assert!(res.metrics.enabled);
+        assert_eq!(res.metrics.bind, "0.0.0.0:6631");
+    }
+
+    fn write_test_config(home_dir: &Path, relayer_loop_speed: u64) {
+        fs::create_dir_all(home_dir).unwrap();
+        fs::write(
+            config_file_path(home_dir),
+            format!(
+                "version = {CONFIG_VERSION}\n[relayer]\nrelayer_loop_speed = {relayer_loop_speed}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    /// Test that a reload shares a valid config and keeps the current one when the file is invalid
+    #[test]
+    fn test_reload_config() {
+        let home_dir = env::temp_dir().join(format!("gbt-reload-{}", std::process::id()));
+        let (sender, mut receiver) = watch::channel(GravityBridgeToolsConfig::default());
+
+        write_test_config(&home_dir, 42);
+        assert!(reload_config(&home_dir, false, &sender));
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(receiver.borrow_and_update().relayer.relayer_loop_speed, 42);
+
+        // nothing changed, so nothing is sent
+        assert!(reload_config(&home_dir, false, &sender));
+        assert!(!receiver.has_changed().unwrap());
+
+        fs::write(
+            config_file_path(&home_dir),
+            "[relayer]\nrelayer_loop_speed = 0\n",
+        )
+        .unwrap();
+        assert!(reload_config(&home_dir, false, &sender));
+        assert!(!receiver.has_changed().unwrap());
+        assert_eq!(receiver.borrow().relayer.relayer_loop_speed, 42);
+
+        drop(receiver);
+        write_test_config(&home_dir, 43);
+        assert!(!reload_config(&home_dir, false, &sender));
+        fs::remove_dir_all(&home_dir).unwrap();
+    }
+
+    /// Test that a reload request reloads the config without waiting for the file to change
+    #[tokio::test]
+    async fn test_requested_reload() {
+        let home_dir = env::temp_dir().join(format!("gbt-requested-{}", std::process::id()));
+        // written before the watcher starts, so only a reload request makes it reload
+        write_test_config(&home_dir, 42);
+        let (sender, mut receiver) = watch::channel(GravityBridgeToolsConfig::default());
+        let (reload, reloads) = mpsc::channel(1);
+        tokio::spawn(watch_config(home_dir.clone(), false, sender, reloads));
+
+        reload.send(()).await.unwrap();
+        let reloaded = tokio::time::timeout(Duration::from_secs(5), receiver.changed()).await;
+        assert!(reloaded.is_ok(), "the config was not reloaded on request");
+        assert_eq!(receiver.borrow().relayer.relayer_loop_speed, 42);
+        fs::remove_dir_all(&home_dir).unwrap();
+    }
+
+    /// Test that environment variables override the config file, and the command line
+    /// overrides both
+    #[test]
+    fn test_config_precedence() {
+        let value: Value = toml::from_str(&format!(
+            r#"
+            version = {CONFIG_VERSION}
+
+            [connection]
+            cosmos_grpc = "http://file:9090"
+            ethereum_rpc = "http://file:8545"
+            gravity_contract_address = "0x1111111111111111111111111111111111111111"
+            fees = "1anom"
+
+            [metrics]
+            enabled = false
+            "#
+        ))
+        .unwrap();
+        let mut config = parse_config_value(value).unwrap();
+        let env: HashMap<&str, &str> = [
+            ("COSMOS_GRPC", "http://env:9090"),
+            (
+                "GRAVITY_CONTRACT_ADDRESS",
+                "0x2222222222222222222222222222222222222222",
+            ),
+            ("FEES", "2anom"),
+            ("METRICS_ENABLED", "true"),
+        ]
+        .iter()
+        .copied()
+        .collect();
+        apply_overrides(&mut config, |name| env.get(name).map(|v| v.to_string())).unwrap();
+
+        // set in the environment
+        assert_eq!(config.connection.cosmos_grpc, "http://env:9090");
+        assert!(config.metrics.enabled);
+        // only set in the file
+        assert_eq!(config.connection.ethereum_rpc, "http://file:8545");
+
+        let env_address: EthAddress = "0x2222222222222222222222222222222222222222"
+            .parse()
+            .unwrap();
+        let arg_address: EthAddress = "0x3333333333333333333333333333333333333333"
+            .parse()
+            .unwrap();
+        let params_address: EthAddress = "0x4444444444444444444444444444444444444444"
+            .parse()
+            .unwrap();
+        let params = Params {
+            bridge_ethereum_address: params_address.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_gravity_contract_address(None, &config.connection, &params).unwrap(),
+            env_address
+        );
+        assert_eq!(
+            resolve_gravity_contract_address(Some(arg_address), &config.connection, &params)
+                .unwrap(),
+            arg_address
+        );
+        let arg_fees: Coin = "3anom".parse().unwrap();
+        assert_eq!(
+            resolve_fees(None, &config.connection).unwrap(),
+            "2anom".parse::<Coin>().unwrap()
+        );
+        assert_eq!(
+            resolve_fees(Some(arg_fees.clone()), &config.connection).unwrap(),
+            arg_fees
+        );
+
+        // the chain parameters are the last resort, an unset address is an error
+        config.connection.gravity_contract_address = None;
+        assert_eq!(
+            resolve_gravity_contract_address(None, &config.connection, &params).unwrap(),
+            params_address
+        );
+        let unset = Params {
+            bridge_ethereum_address: ZERO_ADDRESS.to_string(),
+            ..Default::default()
+        };
+        assert!(resolve_gravity_contract_address(None, &config.connection, &unset).is_err());
+
+        // an invalid value in the environment is an error rather than ignored
+        let res = apply_overrides(&mut config, |name| {
+            (name == "METRICS_ENABLED").then(|| "maybe".to_string())
+        });
+        assert!(res.is_err());
     }
 }
@@ -0,0 +1,178 @@
+//! Airdrops built from a CSV of recipients and the manifest recording which proposal
+//! pays which recipients when an airdrop is split into several proposals
+
+use std::{collections::HashMap, fs, path::Path};
+
+use cosmos_gravity::proposals::AirdropProposalJsonUnparsed;
+use gravity_proto::cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use gravity_utils::{deep_space::Address, error::GravityError};
+use serde::Serialize;
+
+/// One submitted part of an airdrop as written to the manifest
+#[derive(Serialize, Debug, Clone)]
+pub struct AirdropManifestEntry {
+    pub part: usize,
+    /// None if the id could not be found in the transaction logs
+    pub proposal_id: Option<u64>,
+    pub txhash: String,
+    pub title: String,
+    pub denom: String,
+    /// a string since the total may not fit in a JSON number
+    pub total: String,
+    pub recipients: Vec<String>,
+    pub amounts: Vec<u64>,
+}
+
+impl AirdropManifestEntry {
+    pub fn new(part: usize, proposal: &AirdropProposalJsonUnparsed, res: &TxResponse) -> Self {
+        AirdropManifestEntry {
+            part,
+            proposal_id: proposal_id_from_logs(res),
+            txhash: res.txhash.clone(),
+            title: proposal.title.clone(),
+            denom: proposal.denom.clone(),
+            total: proposal.total().to_string(),
+            recipients: proposal.recipients.clone(),
+            amounts: proposal.amounts.clone(),
+        }
+    }
+}
+
+/// Reads `address,amount` rows into an airdrop, rows for the same address are merged
+/// into one recipient at the position the address first appears
+pub fn read_airdrop_csv(
+    path: &Path,
+    title: String,
+    description: String,
+    denom: String,
+) -> Result<AirdropProposalJsonUnparsed, GravityError> {
+    let csv = fs::read_to_string(path).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Failed to read {}, check the file path! {e:?}",
+            path.display()
+        ))
+    })?;
+    let (recipients, amounts) = parse_airdrop_csv(&csv)?;
+    Ok(AirdropProposalJsonUnparsed {
+        title,
+        denom,
+        description,
+        amounts,
+        recipients,
+    })
+}
+
+fn parse_airdrop_csv(csv: &str) -> Result<(Vec<String>, Vec<u64>), GravityError> {
+    let mut recipients: Vec<String> = Vec::new();
+    let mut amounts: Vec<u64> = Vec::new();
+    // address bytes to the index of the recipient
+    let mut index: HashMap<Vec<u8>, usize> = HashMap::new();
+    for (i, row) in csv.lines().enumerate() {
+        let line = i + 1;
+        let row = row.trim();
+        if row.is_empty() || row.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').map(|f| f.trim()).collect();
+        if fields[0].eq_ignore_ascii_case("address") {
+            continue;
+        }
+        if fields.len() != 2 {
+            return Err(GravityError::ValidationError(format!(
+                "Line {line} has {} fields, expected address,amount",
+                fields.len()
+            )));
+        }
+        let address: Address = fields[0].parse().map_err(|e| {
+            GravityError::ValidationError(format!(
+                "Line {line} has an invalid address {} {e:?}",
+                fields[0]
+            ))
+        })?;
+        let amount: u64 = fields[1].parse().map_err(|_| {
+            GravityError::ValidationError(format!(
+                "Line {line} has an invalid amount {}, expected an integer in the smallest unit of the denom",
+                fields[1]
+            ))
+        })?;
+        match index.get(address.as_bytes()) {
+            Some(i) => {
+                amounts[*i] = amounts[*i].checked_add(amount).ok_or_else(|| {
+                    GravityError::ValidationError(format!(
+                        "Line {line} overflows the total for {address}"
+                    ))
+                })?;
+            }
+            None => {
+                index.insert(address.as_bytes().to_vec(), recipients.len());
+                recipients.push(address.to_string());
+                amounts.push(amount);
+            }
+        }
+    }
+    Ok((recipients, amounts))
+}
+
+/// Writes the whole manifest, it is rewritten after every proposal so it is accurate
+/// even if a later proposal fails
+pub fn write_manifest(path: &Path, entries: &[AirdropManifestEntry]) -> Result<(), GravityError> {
+    fs::write(path, serde_json::to_string_pretty(entries).unwrap()).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Failed to write manifest {} {e:?}",
+            path.display()
+        ))
+    })
+}
+
+/// Finds the id of a proposal in the logs of the transaction that submitted it
+fn proposal_id_from_logs(res: &TxResponse) -> Option<u64> {
+    res.logs
+        .iter()
+        .flat_map(|log| log.events.iter())
+        .filter(|event| event.r#type == "submit_proposal")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == "proposal_id")
+        .and_then(|attribute| attribute.value.parse().ok())
+}
+
+#[test]
+fn test_parse_airdrop_csv() {
+    let csv = "address,amount\n\
+        # duplicates are merged\n\
+        onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe,100\n\
+        \n\
+        onomy1z5tpwxqergd3c8g7ruszzg3rysjjvfegjxvxl0, 200\n\
+        onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe,50\n";
+    let (recipients, amounts) = parse_airdrop_csv(csv).unwrap();
+    assert_eq!(
+        recipients,
+        vec![
+            "onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe",
+            "onomy1z5tpwxqergd3c8g7ruszzg3rysjjvfegjxvxl0"
+        ]
+    );
+    assert_eq!(amounts, vec![150, 200]);
+
+    assert!(parse_airdrop_csv("onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe,1anom").is_err());
+    assert!(parse_airdrop_csv("onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghf,1").is_err());
+
+    let airdrop = AirdropProposalJsonUnparsed {
+        title: "t".to_string(),
+        denom: "anom".to_string(),
+        description: "d".to_string(),
+        recipients: vec![recipients[0].clone(); 1000],
+        amounts: vec![1_000_000; 1000],
+    };
+    let parts = airdrop.clone().split(10_000).unwrap();
+    assert!(parts.len() > 1);
+    for part in parts.iter() {
+        assert!(part.estimated_tx_size() <= 10_000);
+    }
+    assert_eq!(parts[0].title, format!("t (part 1 of {})", parts.len()));
+    assert_eq!(
+        parts.iter().map(|p| p.total()).sum::<u128>(),
+        airdrop.total()
+    );
+    assert_eq!(airdrop.clone().split(10_000_000).unwrap()[0].title, "t");
+    assert!(airdrop.split(100).is_err());
+}
@@ -1,2 +1,6 @@
+pub mod airdrop;
+pub mod params;
 pub mod proposals;
 pub mod queries;
+pub mod template;
+pub mod vote;
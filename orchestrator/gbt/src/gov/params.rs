@@ -0,0 +1,284 @@
+//! Gravity module parameters as they appear in parameter change proposals, used to check
+//! a proposal.json against the current parameters before it is submitted
+
+use std::collections::BTreeMap;
+
+use gravity_proto::{cosmos_sdk_proto::cosmos::params::v1beta1::ParamChange, gravity::Params};
+use gravity_utils::{
+    clarity::{Address as EthAddress, Uint256},
+    error::GravityError,
+    num_conversion::DecimalAmount,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The params subspace of the Gravity module
+pub const GRAVITY_SUBSPACE: &str = "gravity";
+
+/// sdk.Dec values are stored as integers scaled by 10^18
+const DEC_PRECISION: u8 = 18;
+
+/// The proposal.json for a Gravity parameter change, `changes` maps parameter keys
+/// such as `SignedValsetsWindow` to their new values eg: "10" or true
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParamChangeProposalJson {
+    pub title: String,
+    pub description: String,
+    pub changes: BTreeMap<String, Value>,
+}
+
+/// The JSON type a parameter is set with in a parameter change proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamKind {
+    /// a uint64, encoded as a string
+    Uint,
+    /// an sdk.Dec such as a slashing fraction, encoded as a decimal string
+    Dec,
+    Bool,
+    Text,
+    Coin,
+    AddressList,
+    Erc20ToDenom,
+}
+
+/// Every key in the Gravity params subspace
+const GRAVITY_PARAMS: &[(&str, ParamKind)] = &[
+    ("GravityID", ParamKind::Text),
+    ("ContractHash", ParamKind::Text),
+    ("BridgeEthereumAddress", ParamKind::Text),
+    ("BridgeChainID", ParamKind::Uint),
+    ("SignedValsetsWindow", ParamKind::Uint),
+    ("SignedBatchesWindow", ParamKind::Uint),
+    ("SignedLogicCallsWindow", ParamKind::Uint),
+    ("TargetBatchTimeout", ParamKind::Uint),
+    ("AverageBlockTime", ParamKind::Uint),
+    ("AverageEthereumBlockTime", ParamKind::Uint),
+    ("SlashFractionValset", ParamKind::Dec),
+    ("SlashFractionBatch", ParamKind::Dec),
+    ("UnbondSlashingValsetsWindow", ParamKind::Uint),
+    ("SlashFractionBadEthSignature", ParamKind::Dec),
+    ("ValsetReward", ParamKind::Coin),
+    ("BridgeActive", ParamKind::Bool),
+    ("EthereumBlacklist", ParamKind::AddressList),
+    ("Erc20ToDenomPermanentSwap", ParamKind::Erc20ToDenom),
+];
+
+/// A validated change of a single parameter along with its current value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamDiff {
+    pub key: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl ParamChangeProposalJson {
+    /// Checks the keys and value types of every change without contacting the chain
+    pub fn validate(&self) -> Result<(), GravityError> {
+        // the current values are only used for display, so defaults do for a check
+        self.diff(&Params::default()).map(|_| ())
+    }
+
+    /// Checks every change against the known Gravity parameters and returns them
+    /// next to the current values in `params`
+    pub fn diff(&self, params: &Params) -> Result<Vec<ParamDiff>, GravityError> {
+        if self.title.trim().is_empty() || self.description.trim().is_empty() {
+            return Err(GravityError::ValidationError(
+                "The proposal needs a title and a description".to_string(),
+            ));
+        }
+        if self.changes.is_empty() {
+            return Err(GravityError::ValidationError(
+                "The proposal does not change any parameters".to_string(),
+            ));
+        }
+        let mut diffs = Vec::new();
+        for (key, value) in self.changes.iter() {
+            let kind = match GRAVITY_PARAMS.iter().find(|(k, _)| k == key) {
+                Some((_, kind)) => *kind,
+                None => {
+                    let known: Vec<&str> = GRAVITY_PARAMS.iter().map(|(k, _)| *k).collect();
+                    return Err(GravityError::ValidationError(format!(
+                        "Unknown Gravity parameter {key}, expected one of {}",
+                        known.join(", ")
+                    )));
+                }
+            };
+            diffs.push(ParamDiff {
+                key: key.clone(),
+                old: current_param_value(params, key)?,
+                new: normalize_param_value(kind, key, value)?,
+            });
+        }
+        Ok(diffs)
+    }
+}
+
+/// Builds the changes of a parameter change proposal from validated diffs
+pub fn to_param_changes(diffs: &[ParamDiff]) -> Vec<ParamChange> {
+    diffs
+        .iter()
+        .map(|d| ParamChange {
+            subspace: GRAVITY_SUBSPACE.to_string(),
+            key: d.key.clone(),
+            value: d.new.to_string(),
+        })
+        .collect()
+}
+
+/// Gets the current value of a Gravity parameter in the form it is set with
+pub fn current_param_value(params: &Params, key: &str) -> Result<Value, GravityError> {
+    let dec = |bytes: &[u8]| -> Result<Value, GravityError> {
+        let scaled = String::from_utf8_lossy(bytes);
+        if scaled.is_empty() {
+            return Ok(Value::String("0".to_string()));
+        }
+        let scaled = Uint256::from_dec_or_hex_str_restricted(&scaled)?;
+        Ok(Value::String(
+            DecimalAmount::from_base_units(scaled, DEC_PRECISION).to_string(),
+        ))
+    };
+    let uint = |v: u64| Value::String(v.to_string());
+    Ok(match key {
+        "GravityID" => Value::String(params.gravity_id.clone()),
+        "ContractHash" => Value::String(params.contract_source_hash.clone()),
+        "BridgeEthereumAddress" => Value::String(params.bridge_ethereum_address.clone()),
+        "BridgeChainID" => uint(params.bridge_chain_id),
+        "SignedValsetsWindow" => uint(params.signed_valsets_window),
+        "SignedBatchesWindow" => uint(params.signed_batches_window),
+        "SignedLogicCallsWindow" => uint(params.signed_logic_calls_window),
+        "TargetBatchTimeout" => uint(params.target_batch_timeout),
+        "AverageBlockTime" => uint(params.average_block_time),
+        "AverageEthereumBlockTime" => uint(params.average_ethereum_block_time),
+        "SlashFractionValset" => dec(&params.slash_fraction_valset)?,
+        "SlashFractionBatch" => dec(&params.slash_fraction_batch)?,
+        "UnbondSlashingValsetsWindow" => uint(params.unbond_slashing_valsets_window),
+        "SlashFractionBadEthSignature" => dec(&params.slash_fraction_bad_eth_signature)?,
+        "ValsetReward" => match &params.valset_reward {
+            Some(c) => json!({"denom": c.denom, "amount": c.amount}),
+            None => Value::Null,
+        },
+        "BridgeActive" => Value::Bool(params.bridge_active),
+        "EthereumBlacklist" => json!(params.ethereum_blacklist),
+        "Erc20ToDenomPermanentSwap" => match &params.erc20_to_denom_permanent_swap {
+            Some(s) => json!({"erc20": s.erc20, "denom": s.denom}),
+            None => Value::Null,
+        },
+        _ => {
+            return Err(GravityError::ValidationError(format!(
+                "Unknown Gravity parameter {key}"
+            )))
+        }
+    })
+}
+
+/// Checks that `value` has the type of the parameter, integers given as JSON numbers
+/// are converted into the string form the params module expects and decimals are normalized
+/// so they compare equal to the current value
+fn normalize_param_value(kind: ParamKind, key: &str, value: &Value) -> Result<Value, GravityError> {
+    let invalid = |expected: &str| {
+        GravityError::ValidationError(format!(
+            "Invalid value {value} for {key}, expected {expected}"
+        ))
+    };
+    match kind {
+        ParamKind::Uint => {
+            let v = match value {
+                Value::String(s) => s.parse::<u64>().ok(),
+                Value::Number(n) => n.as_u64(),
+                _ => None,
+            };
+            v.map(|v| Value::String(v.to_string()))
+                .ok_or_else(|| invalid("an unsigned integer eg: \"10\""))
+        }
+        ParamKind::Dec => match value {
+            Value::String(s) => match s.parse::<DecimalAmount>() {
+                Ok(d) if d.to_base_units(DEC_PRECISION).is_ok() => Ok(Value::String(d.to_string())),
+                _ => Err(invalid(
+                    "a decimal with at most 18 decimal places eg: \"0.001\"",
+                )),
+            },
+            _ => Err(invalid("a decimal string eg: \"0.001\"")),
+        },
+        ParamKind::Bool => match value {
+            Value::Bool(_) => Ok(value.clone()),
+            _ => Err(invalid("true or false")),
+        },
+        ParamKind::Text => match value {
+            Value::String(_) => Ok(value.clone()),
+            _ => Err(invalid("a string")),
+        },
+        ParamKind::Coin => {
+            let denom = value.get("denom").and_then(|d| d.as_str());
+            let amount = value
+                .get("amount")
+                .and_then(|a| a.as_str())
+                .filter(|a| Uint256::from_dec_or_hex_str_restricted(a).is_ok());
+            match (denom, amount) {
+                (Some(_), Some(_)) => Ok(value.clone()),
+                _ => Err(invalid(
+                    "a coin eg: {\"denom\": \"anom\", \"amount\": \"1000\"}",
+                )),
+            }
+        }
+        ParamKind::AddressList => {
+            let addresses = value
+                .as_array()
+                .ok_or_else(|| invalid("a list of addresses"))?;
+            for address in addresses {
+                match address.as_str().map(|a| a.parse::<EthAddress>()) {
+                    Some(Ok(_)) => {}
+                    _ => return Err(invalid("a list of Ethereum addresses")),
+                }
+            }
+            Ok(value.clone())
+        }
+        ParamKind::Erc20ToDenom => {
+            let erc20 = value
+                .get("erc20")
+                .and_then(|e| e.as_str())
+                .filter(|e| e.parse::<EthAddress>().is_ok());
+            let denom = value.get("denom").and_then(|d| d.as_str());
+            match (erc20, denom) {
+                (Some(_), Some(_)) => Ok(value.clone()),
+                _ => Err(invalid(
+                    "an ERC20 and denom pair eg: {\"erc20\": \"0x...\", \"denom\": \"anom\"}",
+                )),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_param_change_diff() {
+    let params = Params {
+        signed_valsets_window: 10_000,
+        slash_fraction_valset: b"1000000000000000".to_vec(),
+        bridge_active: true,
+        ..Default::default()
+    };
+    let proposal: ParamChangeProposalJson = serde_json::from_str(
+        r#"{"title": "t", "description": "d", "changes": {
+            "SignedValsetsWindow": 20000,
+            "SlashFractionValset": "0.002",
+            "BridgeActive": false
+        }}"#,
+    )
+    .unwrap();
+    let diffs = proposal.diff(&params).unwrap();
+    assert_eq!(diffs[0].key, "BridgeActive");
+    assert_eq!(diffs[0].old, json!(true));
+    assert_eq!(diffs[0].new, json!(false));
+    assert_eq!(diffs[1].old, json!("0.001"));
+    assert_eq!(diffs[2].old, json!("10000"));
+    assert_eq!(diffs[2].new, json!("20000"));
+    let changes = to_param_changes(&diffs);
+    assert_eq!(changes[2].value, r#""20000""#);
+
+    let mut bad = proposal.clone();
+    bad.changes = BTreeMap::from([("SignedValsetWindow".to_string(), json!("1"))]);
+    assert!(bad.diff(&params).is_err());
+    bad.changes = BTreeMap::from([("BridgeActive".to_string(), json!("false"))]);
+    assert!(bad.diff(&params).is_err());
+    bad.changes = BTreeMap::from([("SlashFractionBatch".to_string(), json!("1e-3"))]);
+    assert!(bad.diff(&params).is_err());
+}
@@ -1,201 +1,388 @@
-use std::{convert::TryInto, fs};
+use std::{convert::TryInto, fs, path::Path};
 
-use cosmos_gravity::proposals::{
-    submit_airdrop_proposal, submit_ibc_metadata_proposal, submit_pause_bridge_proposal,
-    submit_unhalt_bridge_proposal, AirdropProposalJsonUnparsed, IbcMetadataProposalJson,
-    PauseBridgeProposalJson, UnhaltBridgeProposalJson,
+use cosmos_gravity::{
+    proposals::{
+        submit_airdrop_proposal, submit_ibc_metadata_proposal, submit_parameter_change_proposal,
+        submit_pause_bridge_proposal, submit_unhalt_bridge_proposal, AirdropProposalJsonUnparsed,
+        IbcMetadataProposalJson, PauseBridgeProposalJson, UnhaltBridgeProposalJson,
+    },
+    query::get_gravity_params,
+};
+use gravity_proto::cosmos_sdk_proto::cosmos::{
+    base::abci::v1beta1::TxResponse,
+    distribution::v1beta1::{
+        query_client::QueryClient as DistributionQueryClient, QueryCommunityPoolRequest,
+    },
+    params::v1beta1::ParameterChangeProposal,
+};
+use gravity_utils::{
+    clarity::{u256, Uint256},
+    connection_prep::create_rpc_connections,
+    deep_space::error::CosmosGrpcError,
+    error::GravityError,
+    types::GravityBridgeToolsConfig,
 };
-use gravity_utils::{connection_prep::create_rpc_connections, error::GravityError};
+use serde::de::DeserializeOwned;
 
 use crate::{
     args::{
         AirdropProposalOpts, EmergencyBridgeHaltProposalOpts, IbcMetadataProposalOpts,
-        OracleUnhaltProposalOpts,
+        OracleUnhaltProposalOpts, ParamChangeProposalOpts,
     },
-    utils::TIMEOUT,
+    config::{resolve_cosmos_key, resolve_fees},
+    gov::{
+        airdrop::{read_airdrop_csv, write_manifest, AirdropManifestEntry},
+        params::{to_param_changes, ParamChangeProposalJson},
+    },
+    utils::{get_block_params, DEFAULT_MEMPOOL_MAX_TX_BYTES, TIMEOUT},
 };
 
 pub async fn submit_ibc_metadata(
     opts: IbcMetadataProposalOpts,
     prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let connections = create_rpc_connections(prefix, Some(opts.cosmos_grpc), None, TIMEOUT).await;
+    let proposal_json: IbcMetadataProposalJson = read_proposal_json(&opts.json)?;
+    proposal_json.validate()?;
+    if opts.validate_only {
+        info!("Your proposal.json is valid");
+        return Ok(());
+    }
+
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
 
-    match fs::read_to_string(opts.json) {
-        Ok(file_contents) => {
-            let proposal: Result<IbcMetadataProposalJson, _> = serde_json::from_str(&file_contents);
-            match proposal {
-                Ok(proposal_json) => {
-                    let res = submit_ibc_metadata_proposal(
-                        proposal_json.into(),
-                        opts.deposit,
-                        opts.fees,
-                        &contact,
-                        opts.cosmos_phrase,
-                        Some(TIMEOUT),
-                    )
-                    .await;
-                    match res {
-                        Ok(r) => info!("Successfully submitted proposal with txid {}", r.txhash),
-                        Err(e) => {
-                            return Err(GravityError::UnrecoverableError(format!(
-                                "Failed to submit proposal with {e:?}"
-                            )))
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(GravityError::UnrecoverableError(format!(
-                        "Failed to deserialize your proposal.json, check the contents! {e:?}"
-                    )))
-                }
-            }
+    let res = submit_ibc_metadata_proposal(
+        proposal_json.into(),
+        opts.deposit,
+        fees,
+        &contact,
+        cosmos_key,
+        Some(TIMEOUT),
+    )
+    .await;
+    print_submit_result(res)
+}
+
+pub async fn submit_airdrop(
+    opts: AirdropProposalOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let (proposal_json, input) = match (opts.json, opts.csv) {
+        (Some(json), _) => (
+            read_proposal_json::<AirdropProposalJsonUnparsed>(&json)?,
+            json,
+        ),
+        (None, Some(csv)) => {
+            // clap requires these alongside --csv
+            let proposal = read_airdrop_csv(
+                &csv,
+                opts.title.unwrap(),
+                opts.description.unwrap(),
+                opts.denom.unwrap(),
+            )?;
+            (proposal, csv)
         }
-        Err(e) => {
-            return Err(GravityError::UnrecoverableError(format!(
-                "Failed to read your proposal.json check the file path! {e:?}"
-            )))
+        (None, None) => unreachable!("clap requires --json or --csv"),
+    };
+    proposal_json.validate()?;
+    info!(
+        "Airdrop of {}{} to {} recipients",
+        proposal_json.total(),
+        proposal_json.denom,
+        proposal_json.recipients.len()
+    );
+
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    // the community pool is the one check that needs the chain, it is still done
+    // with --validate-only when the chain can be reached
+    let pool = get_community_pool_balance(&cosmos_grpc, &proposal_json.denom).await;
+    match pool {
+        Ok(pool) if pool < Uint256::from_u128(proposal_json.total()) => {
+            return Err(GravityError::ValidationError(format!(
+                "The airdrop total of {}{} is more than the community pool holds {pool}{}",
+                proposal_json.total(),
+                proposal_json.denom,
+                proposal_json.denom
+            )));
         }
+        Ok(pool) => info!("The community pool holds {}{}", pool, proposal_json.denom),
+        Err(e) if opts.validate_only => warn!(
+            "Could not check the airdrop total against the community pool {:?}",
+            e
+        ),
+        Err(e) => return Err(e),
+    }
+
+    let max_tx_bytes = match opts.max_tx_bytes {
+        Some(max_tx_bytes) => max_tx_bytes,
+        None => match get_block_params(&cosmos_grpc).await {
+            Ok(block_params) => block_params.max_tx_bytes(),
+            Err(e) if opts.validate_only => {
+                warn!(
+                    "Could not query the block size limit, assuming {} bytes {:?}",
+                    DEFAULT_MEMPOOL_MAX_TX_BYTES, e
+                );
+                DEFAULT_MEMPOOL_MAX_TX_BYTES
+            }
+            Err(e) => return Err(e),
+        },
+    };
+    let parts = proposal_json.split(max_tx_bytes)?;
+    if parts.len() > 1 {
+        info!(
+            "The airdrop is too large for one transaction of {} bytes, it will be submitted as {} proposals",
+            max_tx_bytes,
+            parts.len()
+        );
+        for part in parts.iter() {
+            info!(
+                "{}: {} recipients, {}{}, about {} bytes",
+                part.title,
+                part.recipients.len(),
+                part.total(),
+                part.denom,
+                part.estimated_tx_size()
+            );
+        }
+    }
+    if opts.validate_only {
+        info!("Your airdrop is valid");
+        return Ok(());
     }
-    Ok(())
-}
 
-pub async fn submit_airdrop(opts: AirdropProposalOpts, prefix: String) -> Result<(), GravityError> {
-    let connections = create_rpc_connections(prefix, Some(opts.cosmos_grpc), None, TIMEOUT).await;
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
 
-    match fs::read_to_string(opts.json) {
-        Ok(file_contents) => {
-            let proposal: Result<AirdropProposalJsonUnparsed, _> =
-                serde_json::from_str(&file_contents);
-            match proposal {
-                Ok(proposal_json) => {
-                    let res = submit_airdrop_proposal(
-                        proposal_json
-                            .try_into()
-                            .expect("Invalid address in proposal.json"),
-                        opts.deposit,
-                        opts.fees,
-                        &contact,
-                        opts.cosmos_phrase,
-                        Some(TIMEOUT),
-                    )
-                    .await;
-                    match res {
-                        Ok(r) => info!("Successfully submitted proposal with txid {}", r.txhash),
-                        Err(e) => {
-                            return Err(GravityError::UnrecoverableError(format!(
-                                "Failed to submit proposal with {e:?}"
-                            )))
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(GravityError::UnrecoverableError(format!(
-                        "Failed to deserialize your proposal.json, check the contents! {e:?}"
-                    )))
+    let manifest_path = opts.manifest.unwrap_or_else(|| {
+        let mut path = input.into_os_string();
+        path.push(".manifest.json");
+        path.into()
+    });
+    let mut manifest = Vec::new();
+    for (i, part) in parts.into_iter().enumerate() {
+        let res = submit_airdrop_proposal(
+            part.clone()
+                .try_into()
+                .expect("Invalid address in proposal.json"),
+            opts.deposit.clone(),
+            fees.clone(),
+            &contact,
+            cosmos_key,
+            Some(TIMEOUT),
+        )
+        .await;
+        let res = match res {
+            Ok(res) => res,
+            Err(e) => {
+                if !manifest.is_empty() {
+                    warn!(
+                        "Proposals submitted before this failure are recorded in {}, remove their recipients before trying again",
+                        manifest_path.display()
+                    );
                 }
+                return Err(GravityError::UnrecoverableError(format!(
+                    "Failed to submit {} with {e:?}",
+                    part.title
+                )));
             }
+        };
+        let entry = AirdropManifestEntry::new(i + 1, &part, &res);
+        match entry.proposal_id {
+            Some(id) => info!(
+                "Successfully submitted {} as proposal {} with txid {}",
+                part.title, id, res.txhash
+            ),
+            None => warn!(
+                "Submitted {} with txid {} but could not find its proposal id",
+                part.title, res.txhash
+            ),
         }
-        Err(e) => {
-            return Err(GravityError::UnrecoverableError(format!(
-                "Failed to read your proposal.json check the file path! {e:?}"
-            )))
-        }
+        manifest.push(entry);
+        write_manifest(&manifest_path, &manifest)?;
     }
+    info!(
+        "The proposal ids and their recipients are recorded in {}",
+        manifest_path.display()
+    );
     Ok(())
 }
 
 pub async fn submit_emergency_bridge_halt(
     opts: EmergencyBridgeHaltProposalOpts,
     prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let connections = create_rpc_connections(prefix, Some(opts.cosmos_grpc), None, TIMEOUT).await;
+    let proposal_json: PauseBridgeProposalJson = read_proposal_json(&opts.json)?;
+    proposal_json.validate()?;
+    if opts.validate_only {
+        info!("Your proposal.json is valid");
+        return Ok(());
+    }
+
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
 
-    match fs::read_to_string(opts.json) {
-        Ok(file_contents) => {
-            let proposal: Result<PauseBridgeProposalJson, _> = serde_json::from_str(&file_contents);
-            match proposal {
-                Ok(proposal_json) => {
-                    let res = submit_pause_bridge_proposal(
-                        proposal_json,
-                        opts.deposit,
-                        opts.fees,
-                        &contact,
-                        opts.cosmos_phrase,
-                        Some(TIMEOUT),
-                    )
-                    .await;
-                    match res {
-                        Ok(r) => info!("Successfully submitted proposal with txid {}", r.txhash),
-                        Err(e) => {
-                            return Err(GravityError::UnrecoverableError(format!(
-                                "Failed to submit proposal with {e:?}"
-                            )))
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(GravityError::UnrecoverableError(format!(
-                        "Failed to deserialize your proposal.json, check the contents! {e:?}"
-                    )))
-                }
-            }
-        }
-        Err(e) => {
-            return Err(GravityError::UnrecoverableError(format!(
-                "Failed to read your proposal.json check the file path! {e:?}"
-            )))
-        }
-    }
-    Ok(())
+    let res = submit_pause_bridge_proposal(
+        proposal_json,
+        opts.deposit,
+        fees,
+        &contact,
+        cosmos_key,
+        Some(TIMEOUT),
+    )
+    .await;
+    print_submit_result(res)
 }
 
 pub async fn submit_oracle_unhalt(
     opts: OracleUnhaltProposalOpts,
     prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let connections = create_rpc_connections(prefix, Some(opts.cosmos_grpc), None, TIMEOUT).await;
+    let proposal_json: UnhaltBridgeProposalJson = read_proposal_json(&opts.json)?;
+    proposal_json.validate()?;
+    if opts.validate_only {
+        info!("Your proposal.json is valid");
+        return Ok(());
+    }
+
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
 
-    match fs::read_to_string(opts.json) {
-        Ok(file_contents) => {
-            let proposal: Result<UnhaltBridgeProposalJson, _> =
-                serde_json::from_str(&file_contents);
-            match proposal {
-                Ok(proposal_json) => {
-                    let res = submit_unhalt_bridge_proposal(
-                        proposal_json.into(),
-                        opts.deposit,
-                        opts.fees,
-                        &contact,
-                        opts.cosmos_phrase,
-                        Some(TIMEOUT),
-                    )
-                    .await;
-                    match res {
-                        Ok(r) => info!("Successfully submitted proposal with txid {}", r.txhash),
-                        Err(e) => {
-                            return Err(GravityError::UnrecoverableError(format!(
-                                "Failed to submit proposal with {e:?}"
-                            )))
-                        }
-                    }
-                }
-                Err(e) => {
-                    return Err(GravityError::UnrecoverableError(format!(
-                        "Failed to deserialize your proposal.json, check the contents! {e:?}"
-                    )))
-                }
-            }
+    let res = submit_unhalt_bridge_proposal(
+        proposal_json.into(),
+        opts.deposit,
+        fees,
+        &contact,
+        cosmos_key,
+        Some(TIMEOUT),
+    )
+    .await;
+    print_submit_result(res)
+}
+
+pub async fn submit_param_change(
+    opts: ParamChangeProposalOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let proposal_json: ParamChangeProposalJson = read_proposal_json(&opts.json)?;
+    proposal_json.validate()?;
+    if opts.validate_only {
+        info!("Your proposal.json is valid");
+        return Ok(());
+    }
+
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+    let contact = connections.contact.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    let params = get_gravity_params(&mut grpc).await?;
+    let diffs = proposal_json.diff(&params)?;
+    info!("Proposing the following Gravity parameter changes");
+    for diff in diffs.iter() {
+        if diff.old == diff.new {
+            warn!("{}: {} is unchanged", diff.key, diff.old);
+        } else {
+            info!("{}: {} -> {}", diff.key, diff.old, diff.new);
         }
+    }
+
+    let proposal = ParameterChangeProposal {
+        title: proposal_json.title,
+        description: proposal_json.description,
+        changes: to_param_changes(&diffs),
+    };
+    let res = submit_parameter_change_proposal(
+        proposal,
+        opts.deposit,
+        fees,
+        &contact,
+        cosmos_key,
+        Some(TIMEOUT),
+    )
+    .await;
+    print_submit_result(res)
+}
+
+/// Reads and deserializes a proposal.json
+fn read_proposal_json<T: DeserializeOwned>(path: &Path) -> Result<T, GravityError> {
+    let file_contents = fs::read_to_string(path).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Failed to read your proposal.json check the file path! {e:?}"
+        ))
+    })?;
+    serde_json::from_str(&file_contents).map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Failed to deserialize your proposal.json, check the contents! {e:?}"
+        ))
+    })
+}
+
+fn print_submit_result(res: Result<TxResponse, CosmosGrpcError>) -> Result<(), GravityError> {
+    match res {
+        Ok(r) => info!("Successfully submitted proposal with txid {}", r.txhash),
         Err(e) => {
             return Err(GravityError::UnrecoverableError(format!(
-                "Failed to read your proposal.json check the file path! {e:?}"
+                "Failed to submit proposal with {e:?}"
             )))
         }
     }
     Ok(())
 }
+
+/// Gets the whole units of `denom` held by the community pool, the pool keeps fractional
+/// amounts which are served over gRPC as integers scaled by 10^18
+async fn get_community_pool_balance(
+    cosmos_grpc: &str,
+    denom: &str,
+) -> Result<Uint256, GravityError> {
+    let mut distribution = DistributionQueryClient::connect(cosmos_grpc.to_string())
+        .await
+        .map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Failed to connect to {cosmos_grpc}, check your cosmos gRPC {e:?}"
+            ))
+        })?;
+    let pool = distribution
+        .community_pool(QueryCommunityPoolRequest {})
+        .await?
+        .into_inner()
+        .pool;
+    let amount = match pool.iter().find(|c| c.denom == denom) {
+        Some(coin) => coin.amount.clone(),
+        None => return Ok(u256!(0)),
+    };
+    let integer = match amount.split_once('.') {
+        Some((integer, _)) => integer,
+        None if amount.len() > 18 => &amount[..amount.len() - 18],
+        None => "0",
+    };
+    Ok(Uint256::from_dec_or_hex_str_restricted(integer)?)
+}
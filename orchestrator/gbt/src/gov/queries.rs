@@ -1,15 +1,44 @@
-use cosmos_gravity::send::TIMEOUT;
-use gravity_proto::gravity::AirdropProposal;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cosmos_gravity::{
+    proposals::{
+        AIRDROP_PROPOSAL_TYPE_URL, IBC_METADATA_PROPOSAL_TYPE_URL,
+        PARAMETER_CHANGE_PROPOSAL_TYPE_URL, UNHALT_BRIDGE_PROPOSAL_TYPE_URL,
+    },
+    send::TIMEOUT,
+};
+use gravity_proto::{
+    cosmos_sdk_proto::cosmos::{
+        gov::v1beta1::{
+            query_client::QueryClient as GovQueryClient, Proposal, ProposalStatus,
+            QueryProposalRequest, QueryProposalsRequest, QueryTallyResultRequest,
+        },
+        params::v1beta1::ParameterChangeProposal,
+    },
+    gravity::{AirdropProposal, IbcMetadataProposal, UnhaltBridgeProposal},
+};
 use gravity_utils::{
     connection_prep::create_rpc_connections, deep_space::Address, error::GravityError,
+    types::GravityBridgeToolsConfig,
 };
-use prost::{bytes::BytesMut, Message};
+use prost::Message;
+use tonic::transport::Channel;
 
-use crate::args::AirdropQueryOpts;
+use crate::{
+    args::{AirdropQueryOpts, ProposalStatusArg, ProposalsQueryOpts},
+    gov::params::GRAVITY_SUBSPACE,
+};
 
-pub async fn query_airdrops(opts: AirdropQueryOpts, prefix: String) -> Result<(), GravityError> {
+pub async fn query_airdrops(
+    opts: AirdropQueryOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
     let connections =
-        create_rpc_connections(prefix.clone(), Some(opts.cosmos_grpc), None, TIMEOUT).await;
+        create_rpc_connections(prefix.clone(), Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
 
     info!("Getting details for active airdrop proposals");
@@ -20,59 +49,252 @@ pub async fn query_airdrops(opts: AirdropQueryOpts, prefix: String) -> Result<()
         contact.get_governance_proposals_in_voting_period().await
     };
 
+    let proposals = proposals.map_err(|e| {
+        GravityError::UnrecoverableError(format!(
+            "Failed to get proposals, check your cosmos gRPC {e:?}"
+        ))
+    })?;
     let mut found = false;
-    match proposals {
-        Ok(proposals) => {
-            for proposal in proposals.proposals {
-                if let Some(content) = proposal.content {
-                    let mut buf = BytesMut::with_capacity(content.value.len());
-                    buf.extend_from_slice(&content.value);
-                    let res = AirdropProposal::decode(buf);
-                    if let Ok(airdrop) = res {
-                        found = true;
+    for proposal in proposals.proposals {
+        let content = match &proposal.content {
+            Some(content) if content.type_url == AIRDROP_PROPOSAL_TYPE_URL => content,
+            _ => continue,
+        };
+        found = true;
+        info!("Found Airdrop proposal {}", proposal.proposal_id);
+        print_proposal_content(&content.type_url, &content.value, &prefix, opts.full_list)?;
+    }
+    if !found {
+        info!("No Airdrop proposals meeting the criteria where found!")
+    }
+    Ok(())
+}
 
-                        info!("Found Airdrop proposal");
-                        info!("Title: {}", airdrop.title);
-                        info!("Description: {}", airdrop.description);
-                        info!("Number of Participants: {}", airdrop.amounts.len());
-                        let mut sum = 0;
-                        for amount in airdrop.amounts.iter() {
-                            sum += amount;
-                        }
-                        info!("Total value: {}{}", sum, airdrop.denom);
+pub async fn query_proposals(
+    opts: ProposalsQueryOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let mut gov = GovQueryClient::connect(cosmos_grpc.clone())
+        .await
+        .map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Failed to connect to {cosmos_grpc}, check your cosmos gRPC {e:?}"
+            ))
+        })?;
 
-                        if airdrop.amounts.len() < 100 || opts.full_list {
-                            info!("Participants list");
+    let proposals = match opts.id {
+        Some(proposal_id) => gov
+            .proposal(QueryProposalRequest { proposal_id })
+            .await?
+            .into_inner()
+            .proposal
+            .into_iter()
+            .collect(),
+        None => {
+            let proposal_status = match opts.status {
+                ProposalStatusArg::Deposit => ProposalStatus::DepositPeriod,
+                ProposalStatusArg::Voting => ProposalStatus::VotingPeriod,
+                ProposalStatusArg::Passed => ProposalStatus::Passed,
+                ProposalStatusArg::Rejected => ProposalStatus::Rejected,
+                ProposalStatusArg::Failed => ProposalStatus::Failed,
+                ProposalStatusArg::All => ProposalStatus::Unspecified,
+            };
+            gov.proposals(QueryProposalsRequest {
+                proposal_status: proposal_status as i32,
+                voter: String::new(),
+                depositor: String::new(),
+                pagination: None,
+            })
+            .await?
+            .into_inner()
+            .proposals
+        }
+    };
 
-                            let mut unpacked = Vec::new();
-                            // each address is 20 bytes
-                            assert_eq!(airdrop.recipients.len() / 20, airdrop.amounts.len());
-                            for i in 0..(airdrop.recipients.len() / 20) {
-                                let mut buf = [0; 20];
-                                let addr_bytes = &airdrop.recipients[(i * 20)..((i * 20) + 20)];
-                                buf.copy_from_slice(addr_bytes);
-                                let addr = Address::from_bytes(buf, prefix.clone()).unwrap();
-                                unpacked.push(addr);
-                            }
-                            assert_eq!(unpacked.len(), airdrop.amounts.len());
-                            for (i, _) in unpacked.iter().enumerate() {
-                                info!("{} {}{}", unpacked[i], airdrop.amounts[i], airdrop.denom)
-                            }
+    let mut found = false;
+    for proposal in proposals {
+        let content = match &proposal.content {
+            Some(content) => content,
+            None => continue,
+        };
+        let value = &content.value[..];
+        let title = match content.type_url.as_str() {
+            AIRDROP_PROPOSAL_TYPE_URL => "Airdrop",
+            UNHALT_BRIDGE_PROPOSAL_TYPE_URL => "Oracle unhalt",
+            IBC_METADATA_PROPOSAL_TYPE_URL => "IBC metadata",
+            PARAMETER_CHANGE_PROPOSAL_TYPE_URL => {
+                match ParameterChangeProposal::decode(value) {
+                    // only parameter changes to the gravity module are of interest here
+                    Ok(p) if p.changes.iter().any(|c| c.subspace == GRAVITY_SUBSPACE) => {
+                        if p.changes.iter().any(|c| c.key == "BridgeActive") {
+                            "Emergency bridge halt"
                         } else {
-                            info!("Participants list is greater than 100 addresses, use --full-list to display it");
+                            "Gravity parameter change"
                         }
                     }
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        found = true;
+        info!("{} proposal {}", title, proposal.proposal_id);
+        print_proposal_content(&content.type_url, value, &prefix, opts.full_list)?;
+        print_proposal_status(&proposal, &mut gov).await?;
+    }
+    if !found {
+        info!("No Gravity proposals meeting the criteria where found!")
+    }
+    Ok(())
+}
+
+/// Decodes and prints the content of a Gravity proposal
+fn print_proposal_content(
+    type_url: &str,
+    value: &[u8],
+    prefix: &str,
+    full_list: bool,
+) -> Result<(), GravityError> {
+    let invalid = |e: prost::DecodeError| {
+        GravityError::UnrecoverableError(format!("Failed to decode {type_url} proposal {e:?}"))
+    };
+    match type_url {
+        AIRDROP_PROPOSAL_TYPE_URL => {
+            let airdrop = AirdropProposal::decode(value).map_err(invalid)?;
+            info!("Title: {}", airdrop.title);
+            info!("Description: {}", airdrop.description);
+            info!("Number of Participants: {}", airdrop.amounts.len());
+            let sum: u128 = airdrop.amounts.iter().map(|a| *a as u128).sum();
+            info!("Total value: {}{}", sum, airdrop.denom);
+            if airdrop.recipients.len() != airdrop.amounts.len() * 20 {
+                warn!(
+                    "The proposal has {} bytes of recipients for {} amounts, it will fail to execute",
+                    airdrop.recipients.len(),
+                    airdrop.amounts.len()
+                );
+            } else if airdrop.amounts.len() < 100 || full_list {
+                info!("Participants list");
+                for (recipient, amount) in airdrop.recipients.chunks(20).zip(airdrop.amounts) {
+                    let mut buf = [0; 20];
+                    buf.copy_from_slice(recipient);
+                    let addr = Address::from_bytes(buf, prefix.to_string()).unwrap();
+                    info!("{} {}{}", addr, amount, airdrop.denom)
                 }
+            } else {
+                info!("Participants list is greater than 100 addresses, use --full-list to display it");
             }
         }
-        Err(e) => {
-            return Err(GravityError::UnrecoverableError(format!(
-                "Failed to get proposals, check your cosmos gRPC {e:?}"
-            )))
+        UNHALT_BRIDGE_PROPOSAL_TYPE_URL => {
+            let unhalt = UnhaltBridgeProposal::decode(value).map_err(invalid)?;
+            info!("Title: {}", unhalt.title);
+            info!("Description: {}", unhalt.description);
+            info!(
+                "Resets the oracle to event nonce {}, later claims are discarded",
+                unhalt.target_nonce
+            );
         }
+        IBC_METADATA_PROPOSAL_TYPE_URL => {
+            let ibc = IbcMetadataProposal::decode(value).map_err(invalid)?;
+            info!("Title: {}", ibc.title);
+            info!("Description: {}", ibc.description);
+            info!("IBC denom: {}", ibc.ibc_denom);
+            if let Some(metadata) = ibc.metadata {
+                info!(
+                    "Name: {} Symbol: {} Display: {} Base: {}",
+                    metadata.name, metadata.symbol, metadata.display, metadata.base
+                );
+                for unit in metadata.denom_units {
+                    info!("Denom unit {} exponent {}", unit.denom, unit.exponent);
+                }
+            }
+        }
+        PARAMETER_CHANGE_PROPOSAL_TYPE_URL => {
+            let change = ParameterChangeProposal::decode(value).map_err(invalid)?;
+            info!("Title: {}", change.title);
+            info!("Description: {}", change.description);
+            for c in change.changes {
+                info!("{}/{} = {}", c.subspace, c.key, c.value);
+            }
+        }
+        _ => {}
     }
-    if !found {
-        info!("No Airdrop proposals meeting the criteria where found!")
+    Ok(())
+}
+
+/// Prints the status, deposit, tally and deadline of a proposal, proposals in the voting
+/// period have no final tally yet so the current tally is queried
+async fn print_proposal_status(
+    proposal: &Proposal,
+    gov: &mut GovQueryClient<Channel>,
+) -> Result<(), GravityError> {
+    let status = ProposalStatus::from_i32(proposal.status).unwrap_or(ProposalStatus::Unspecified);
+    info!("Status: {:?}", status);
+    let deposit: Vec<String> = proposal
+        .total_deposit
+        .iter()
+        .map(|c| format!("{}{}", c.amount, c.denom))
+        .collect();
+    info!("Total deposit: {}", deposit.join(", "));
+    match status {
+        ProposalStatus::DepositPeriod => {
+            if let Some(end) = &proposal.deposit_end_time {
+                info!("Deposit period ends {}", format_deadline(end.seconds));
+            }
+        }
+        ProposalStatus::VotingPeriod => {
+            let tally = gov
+                .tally_result(QueryTallyResultRequest {
+                    proposal_id: proposal.proposal_id,
+                })
+                .await?
+                .into_inner()
+                .tally;
+            if let Some(t) = tally {
+                info!(
+                    "Current tally: yes {} no {} abstain {} no with veto {}",
+                    t.yes, t.no, t.abstain, t.no_with_veto
+                );
+            }
+            if let Some(end) = &proposal.voting_end_time {
+                info!("Voting period ends {}", format_deadline(end.seconds));
+            }
+        }
+        _ => {
+            if let Some(t) = &proposal.final_tally_result {
+                info!(
+                    "Final tally: yes {} no {} abstain {} no with veto {}",
+                    t.yes, t.no, t.abstain, t.no_with_veto
+                );
+            }
+            if let Some(end) = &proposal.voting_end_time {
+                info!("Voting period ended {}", format_deadline(end.seconds));
+            }
+        }
     }
     Ok(())
 }
+
+/// Formats a unix timestamp relative to now, eg: in 1d 4h 12m
+fn format_deadline(timestamp: i64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let diff = timestamp - now;
+    let secs = diff.abs();
+    let relative = format!(
+        "{}d {}h {}m",
+        secs / 86400,
+        (secs % 86400) / 3600,
+        (secs % 3600) / 60
+    );
+    if diff >= 0 {
+        format!("in {relative} (unix time {timestamp})")
+    } else {
+        format!("{relative} ago (unix time {timestamp})")
+    }
+}
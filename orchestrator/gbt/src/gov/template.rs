@@ -0,0 +1,137 @@
+use gravity_utils::error::GravityError;
+use serde_json::{json, Value};
+
+use crate::args::{GovTemplateOpts, ProposalTypeArg};
+
+/// Prints an example proposal.json to stdout so it can be redirected into a file,
+/// the notes on each field are logged so they do not end up in the file
+pub fn gov_template(opts: GovTemplateOpts) -> Result<(), GravityError> {
+    let (template, notes) = proposal_template(opts.proposal_type);
+    for note in notes {
+        info!("{}", note);
+    }
+    println!("{}", serde_json::to_string_pretty(&template).unwrap());
+    Ok(())
+}
+
+fn proposal_template(proposal_type: ProposalTypeArg) -> (Value, &'static [&'static str]) {
+    match proposal_type {
+        ProposalTypeArg::IbcMetadata => (
+            json!({
+                "title": "Set metadata for ATOM",
+                "description": "Sets the denom metadata of ATOM so it can be bridged to Ethereum",
+                "ibc_denom": "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                "metadata": {
+                    "description": "The native staking token of the Cosmos Hub",
+                    "denom_units": [
+                        {
+                            "denom": "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                            "exponent": 0,
+                            "aliases": []
+                        },
+                        {"denom": "atom", "exponent": 6, "aliases": []}
+                    ],
+                    "base": "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                    "display": "atom",
+                    "name": "Atom",
+                    "symbol": "ATOM"
+                }
+            }),
+            &[
+                "ibc_denom: the ibc/ hash of the token on this chain",
+                "metadata.base: must be the ibc_denom",
+                "metadata.denom_units: start with the base denom at exponent 0, ordered by increasing exponent",
+                "metadata.display: one of the denom_units, its exponent becomes the ERC20 decimals",
+                "metadata.name and metadata.symbol: the name and symbol of the ERC20",
+            ],
+        ),
+        ProposalTypeArg::Airdrop => (
+            json!({
+                "title": "Community airdrop",
+                "description": "Airdrops tokens from the community pool",
+                "denom": "anom",
+                "amounts": [1000000, 2000000],
+                "recipients": [
+                    "onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe",
+                    "onomy1z5tpwxqergd3c8g7ruszzg3rysjjvfegjxvxl0"
+                ]
+            }),
+            &[
+                "denom: the denom paid from the community pool",
+                "amounts: the amount for each recipient in the smallest unit of the denom, in the same order as recipients",
+                "recipients: bech32 addresses, each may only appear once",
+                "the sum of all amounts must be held by the community pool",
+            ],
+        ),
+        ProposalTypeArg::EmergencyBridgeHalt => (
+            json!({
+                "title": "Halt the bridge",
+                "description": "Halts the bridge while an incident is investigated",
+                "paused": true
+            }),
+            &[
+                "paused: true halts batches and oracle claims, false resumes the bridge",
+            ],
+        ),
+        ProposalTypeArg::OracleUnhalt => (
+            json!({
+                "title": "Unhalt the oracle",
+                "description": "Resets the oracle after an Ethereum fork",
+                "target_nonce": 1234
+            }),
+            &[
+                "target_nonce: the last event nonce all validators agree on, later claims are discarded and observed again",
+            ],
+        ),
+        ProposalTypeArg::ParamChange => (
+            json!({
+                "title": "Longer signing windows",
+                "description": "Gives validators more time to sign valsets and batches",
+                "changes": {
+                    "SignedValsetsWindow": "20000",
+                    "SignedBatchesWindow": "20000",
+                    "SlashFractionBatch": "0.001"
+                }
+            }),
+            &[
+                "changes: Gravity parameter keys mapped to their new values",
+                "integers are strings eg: \"10\", fractions are decimal strings eg: \"0.001\" and booleans are true or false",
+                "use `gbt status` or `gbt gov submit param-change --validate-only` to check the keys",
+            ],
+        ),
+    }
+}
+
+#[test]
+fn test_templates_are_valid() {
+    use cosmos_gravity::proposals::{
+        AirdropProposalJsonUnparsed, IbcMetadataProposalJson, PauseBridgeProposalJson,
+        UnhaltBridgeProposalJson,
+    };
+
+    use crate::gov::params::ParamChangeProposalJson;
+
+    let template = |t| proposal_template(t).0;
+    serde_json::from_value::<IbcMetadataProposalJson>(template(ProposalTypeArg::IbcMetadata))
+        .unwrap()
+        .validate()
+        .unwrap();
+    serde_json::from_value::<AirdropProposalJsonUnparsed>(template(ProposalTypeArg::Airdrop))
+        .unwrap()
+        .validate()
+        .unwrap();
+    serde_json::from_value::<PauseBridgeProposalJson>(template(
+        ProposalTypeArg::EmergencyBridgeHalt,
+    ))
+    .unwrap()
+    .validate()
+    .unwrap();
+    serde_json::from_value::<UnhaltBridgeProposalJson>(template(ProposalTypeArg::OracleUnhalt))
+        .unwrap()
+        .validate()
+        .unwrap();
+    serde_json::from_value::<ParamChangeProposalJson>(template(ProposalTypeArg::ParamChange))
+        .unwrap()
+        .validate()
+        .unwrap();
+}
@@ -0,0 +1,103 @@
+use cosmos_gravity::send::MEMO;
+use gravity_proto::cosmos_sdk_proto::cosmos::gov::v1beta1::{MsgDeposit, VoteOption};
+use gravity_utils::{
+    connection_prep::create_rpc_connections, deep_space::Msg, error::GravityError,
+    types::GravityBridgeToolsConfig,
+};
+
+use crate::{
+    args::{GovDepositOpts, GovVoteOpts, VoteOptionArg},
+    config::{resolve_cosmos_key, resolve_fees},
+    utils::TIMEOUT,
+};
+
+impl From<VoteOptionArg> for VoteOption {
+    fn from(v: VoteOptionArg) -> Self {
+        match v {
+            VoteOptionArg::Yes => VoteOption::Yes,
+            VoteOptionArg::No => VoteOption::No,
+            VoteOptionArg::Abstain => VoteOption::Abstain,
+            VoteOptionArg::NoWithVeto => VoteOption::NoWithVeto,
+        }
+    }
+}
+
+pub async fn vote(
+    opts: GovVoteOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+    let contact = connections.contact.unwrap();
+
+    info!("Voting {:?} on proposal {}", opts.option, opts.proposal_id);
+    let res = contact
+        .vote_on_gov_proposal(
+            opts.proposal_id,
+            opts.option.into(),
+            fees,
+            cosmos_key,
+            Some(TIMEOUT),
+        )
+        .await;
+    match res {
+        Ok(r) => info!("Successfully voted with txid {}", r.txhash),
+        Err(e) => {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Failed to vote on proposal {} with {e:?}",
+                opts.proposal_id
+            )))
+        }
+    }
+    Ok(())
+}
+
+pub async fn deposit(
+    opts: GovDepositOpts,
+    prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_key = resolve_cosmos_key(opts.cosmos_phrase, &config.keys)?;
+    let fees = resolve_fees(opts.fees, &config.connection)?;
+    let cosmos_grpc = opts
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let depositor = cosmos_key.to_address(&prefix).unwrap();
+    let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
+    let contact = connections.contact.unwrap();
+
+    info!(
+        "Depositing {}{} on proposal {} from {}",
+        opts.deposit.amount, opts.deposit.denom, opts.proposal_id, depositor
+    );
+    let msg_deposit = MsgDeposit {
+        proposal_id: opts.proposal_id,
+        depositor: depositor.to_string(),
+        amount: vec![opts.deposit.into()],
+    };
+    let msg = Msg::new("/cosmos.gov.v1beta1.MsgDeposit", msg_deposit);
+    let res = contact
+        .send_message(
+            &[msg],
+            Some(MEMO.to_string()),
+            &[fees],
+            Some(TIMEOUT),
+            cosmos_key,
+        )
+        .await;
+    match res {
+        Ok(r) => info!("Successfully deposited with txid {}", r.txhash),
+        Err(e) => {
+            return Err(GravityError::UnrecoverableError(format!(
+                "Failed to deposit on proposal {} with {e:?}",
+                opts.proposal_id
+            )))
+        }
+    }
+    Ok(())
+}
@@ -2,19 +2,27 @@ use cosmos_gravity::send::set_gravity_delegate_addresses;
 use gravity_utils::{
     connection_prep::{check_for_fee, create_rpc_connections, wait_for_cosmos_node_ready},
     error::GravityError,
+    types::GravityBridgeToolsConfig,
 };
 
-use crate::{args::RegisterOrchestratorAddressOpts, utils::TIMEOUT};
+use crate::{
+    args::RegisterOrchestratorAddressOpts,
+    config::{resolve_cosmos_key, resolve_ethereum_key, resolve_fees},
+    utils::TIMEOUT,
+};
 
 pub async fn register_orchestrator_address(
     args: RegisterOrchestratorAddressOpts,
     prefix: String,
+    config: &GravityBridgeToolsConfig,
 ) -> Result<(), GravityError> {
-    let fee = args.fees;
-    let cosmos_grpc = args.cosmos_grpc;
+    let fee = resolve_fees(args.fees, &config.connection)?;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
     let validator_key = args.validator_phrase;
-    let ethereum_key = args.ethereum_key;
-    let cosmos_key = args.cosmos_phrase;
+    let ethereum_key = resolve_ethereum_key(args.ethereum_key, &config.keys)?;
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
 
     let connections = create_rpc_connections(prefix, Some(cosmos_grpc), None, TIMEOUT).await;
     let contact = connections.contact.unwrap();
@@ -0,0 +1,93 @@
+//! Log output for gbt, `--log-format json` writes one JSON object per line so that log
+//! pipelines do not have to parse free text. The key values of a record, such as `nonce`,
+//! `token_contract`, `event_nonce`, `txid`, `gas`, `cost_wei` and `outcome`, become fields
+//! of the object and `subsystem` defaults to the crate the record came from
+
+use std::io::Write;
+
+use clap::ValueEnum;
+use env_logger::Env;
+use log::{
+    kv::{self, Key, Value},
+    Record,
+};
+use serde_json::{json, Map, Value as JsonValue};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Sets up the global logger, `RUST_LOG` overrides the default `info` level
+pub fn init_logger(format: LogFormat) {
+    let mut builder = env_logger::Builder::from_env(Env::default().default_filter_or("info"));
+    if format == LogFormat::Json {
+        builder.format(|buf, record| {
+            let timestamp = buf.timestamp_millis().to_string();
+            writeln!(buf, "{}", json_record(record, timestamp))
+        });
+    }
+    builder.init();
+}
+
+fn json_record(record: &Record, timestamp: String) -> JsonValue {
+    let mut fields = Map::new();
+    // a record that fails to visit still has its message
+    let _ = record.key_values().visit(&mut JsonVisitor(&mut fields));
+    let crate_name = record.target().split("::").next().unwrap_or_default();
+    fields
+        .entry("subsystem")
+        .or_insert_with(|| json!(crate_name));
+    fields.insert("timestamp".to_string(), json!(timestamp));
+    fields.insert("level".to_string(), json!(record.level().as_str()));
+    fields.insert("target".to_string(), json!(record.target()));
+    fields.insert("message".to_string(), json!(record.args().to_string()));
+    JsonValue::Object(fields)
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, JsonValue>);
+
+impl<'kvs> kv::Visitor<'kvs> for JsonVisitor<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        // numbers that fit stay numbers, anything larger such as a Uint256 is logged as a string
+        let value = match (value.to_u64(), value.to_bool()) {
+            (Some(v), _) => json!(v),
+            (None, Some(v)) => json!(v),
+            (None, None) => json!(value.to_string()),
+        };
+        self.0.insert(key.as_str().to_string(), value);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_json_record() {
+    // format_args can not outlive the statement, so each record is built in place
+    let kvs: &[(&str, u64)] = &[("nonce", 5)];
+    let line = json_record(
+        &Record::builder()
+            .args(format_args!("Sent batch"))
+            .level(log::Level::Info)
+            .target("relayer::batch_relaying")
+            .key_values(&kvs)
+            .build(),
+        "2023-01-01T00:00:00.000Z".to_string(),
+    );
+    assert_eq!(line["message"], "Sent batch");
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(line["nonce"], 5);
+    assert_eq!(line["subsystem"], "relayer");
+
+    let kvs: &[(&str, &str)] = &[("subsystem", "eth_signer")];
+    let line = json_record(
+        &Record::builder()
+            .args(format_args!("Sending confirms"))
+            .target("orchestrator::main_loop")
+            .key_values(&kvs)
+            .build(),
+        String::new(),
+    );
+    assert_eq!(line["subsystem"], "eth_signer");
+}
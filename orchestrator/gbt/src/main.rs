@@ -3,28 +3,34 @@ extern crate log;
 
 use std::process::exit;
 
-use args::{GovQuerySubcommand, GovSubcommand, GovSubmitSubcommand, Opts};
+use args::{ConfigSubcommand, GovQuerySubcommand, GovSubcommand, GovSubmitSubcommand, Opts};
 use clap::Parser;
 use client::{
-    cosmos_to_eth::cosmos_to_eth, deploy_erc20_representation::deploy_erc20_representation,
-    eth_to_cosmos::eth_to_cosmos,
+    cancel_send_to_eth::cancel_send_to_eth, cosmos_to_eth::cosmos_to_eth,
+    cosmos_to_eth_bulk::cosmos_to_eth_bulk,
+    deploy_erc20_representation::deploy_erc20_representation, eth_to_cosmos::eth_to_cosmos,
+    pending_sends::pending_sends, suggest_bridge_fee::suggest_bridge_fee, track::track,
 };
-use config::{get_home_dir, load_config};
-use env_logger::Env;
+use config::{check_config, get_home_dir, load_config, migrate_config};
 use gov::{
     proposals::{
         submit_airdrop, submit_emergency_bridge_halt, submit_ibc_metadata, submit_oracle_unhalt,
+        submit_param_change,
     },
-    queries::query_airdrops,
+    queries::{query_airdrops, query_proposals},
+    template::gov_template,
+    vote::{deposit, vote},
 };
-use gravity_utils::error::GravityError;
+use gravity_utils::{error::GravityError, types::GravityBridgeToolsConfig};
 use keys::register_orchestrator_address::register_orchestrator_address;
+use logging::init_logger;
 
 use crate::{
     args::{ClientSubcommand, KeysSubcommand, SubCommand},
     config::init_config,
     orchestrator::orchestrator,
     relayer::relayer,
+    status::status,
 };
 
 mod args;
@@ -32,20 +38,24 @@ mod client;
 mod config;
 mod gov;
 mod keys;
+mod logging;
 mod orchestrator;
 mod relayer;
+mod status;
 mod utils;
 
 #[tokio::main]
 async fn main() {
-    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
+    // parse the arguments
+    let opts: Opts = Opts::parse();
+    init_logger(opts.log_format);
     // On Linux static builds we need to probe ssl certs path to be able to
     // do TLS stuff.
     openssl_probe::init_ssl_cert_env_vars();
 
-    if let Err(gravity_error) = run_gbt().await {
+    if let Err(gravity_error) = run_gbt(opts).await {
         match gravity_error {
-            // exit only on unrecoverable errors
+            // exit only on unrecoverable errors or invalid user input
             GravityError::UnrecoverableError(error) => {
                 error!("{}", error);
                 exit(1);
@@ -55,6 +65,7 @@ async fn main() {
             }
             GravityError::ValidationError(error) => {
                 error!("{}", error);
+                exit(1);
             }
             GravityError::RpcError(error) => {
                 error!("{}", error);
@@ -63,56 +74,96 @@ async fn main() {
     }
 }
 
-async fn run_gbt() -> Result<(), GravityError> {
-    // parse the arguments
-    let opts: Opts = Opts::parse();
-
+async fn run_gbt(opts: Opts) -> Result<(), GravityError> {
     // handle global config here
-    let address_prefix = opts.address_prefix;
+    let explicit_home = opts.home.is_some();
     let home_dir = get_home_dir(opts.home)?;
-    let config = load_config(&home_dir)?;
+    let config = match opts.subcmd {
+        // these operate on the config file itself, so it must not be required to be valid
+        SubCommand::Init(_) | SubCommand::Config(_) => GravityBridgeToolsConfig::default(),
+        _ => load_config(&home_dir, explicit_home)?,
+    };
+    let address_prefix = opts
+        .address_prefix
+        .unwrap_or_else(|| config.connection.address_prefix.clone());
 
     // control flow for the command structure
     match opts.subcmd {
         SubCommand::Client(client_opts) => match client_opts.subcmd {
             ClientSubcommand::EthToCosmos(eth_to_cosmos_opts) => {
-                eth_to_cosmos(eth_to_cosmos_opts, address_prefix).await
+                eth_to_cosmos(eth_to_cosmos_opts, address_prefix, &config).await
             }
             ClientSubcommand::CosmosToEth(cosmos_to_eth_opts) => {
-                cosmos_to_eth(cosmos_to_eth_opts, address_prefix).await
+                cosmos_to_eth(cosmos_to_eth_opts, address_prefix, &config).await
+            }
+            ClientSubcommand::CosmosToEthBulk(bulk_opts) => {
+                cosmos_to_eth_bulk(bulk_opts, address_prefix, &config).await
             }
             ClientSubcommand::DeployErc20Representation(deploy_erc20_opts) => {
-                deploy_erc20_representation(deploy_erc20_opts, address_prefix).await
+                deploy_erc20_representation(deploy_erc20_opts, address_prefix, &config).await
+            }
+            ClientSubcommand::PendingSends(pending_sends_opts) => {
+                pending_sends(pending_sends_opts, address_prefix, &config).await
+            }
+            ClientSubcommand::CancelSendToEth(cancel_opts) => {
+                cancel_send_to_eth(cancel_opts, address_prefix, &config).await
+            }
+            ClientSubcommand::Track(track_opts) => track(track_opts, address_prefix, &config).await,
+            ClientSubcommand::SuggestBridgeFee(suggest_opts) => {
+                suggest_bridge_fee(suggest_opts, address_prefix, &config).await
             }
         },
         SubCommand::Keys(key_opts) => match key_opts.subcmd {
             KeysSubcommand::RegisterOrchestratorAddress(set_orchestrator_address_opts) => {
-                register_orchestrator_address(set_orchestrator_address_opts, address_prefix).await
+                register_orchestrator_address(
+                    set_orchestrator_address_opts,
+                    address_prefix,
+                    &config,
+                )
+                .await
             }
         },
         SubCommand::Orchestrator(orchestrator_opts) => {
-            orchestrator(orchestrator_opts, address_prefix, config).await
+            orchestrator(orchestrator_opts, address_prefix, config, home_dir).await
         }
         SubCommand::Relayer(relayer_opts) => {
-            relayer(relayer_opts, address_prefix, &config.relayer).await
+            relayer(relayer_opts, address_prefix, &config, home_dir).await
         }
+        SubCommand::Status(status_opts) => status(status_opts, address_prefix, &config).await,
         SubCommand::Init(init_opts) => init_config(init_opts, home_dir),
+        SubCommand::Config(config_opts) => match config_opts.subcmd {
+            ConfigSubcommand::Check(check_opts) => check_config(check_opts, home_dir),
+            ConfigSubcommand::Migrate(migrate_opts) => migrate_config(migrate_opts, home_dir),
+        },
         SubCommand::Gov(gov_opts) => match gov_opts.subcmd {
             GovSubcommand::Submit(submit_opts) => match submit_opts {
                 GovSubmitSubcommand::IbcMetadata(opts) => {
-                    submit_ibc_metadata(opts, address_prefix).await
+                    submit_ibc_metadata(opts, address_prefix, &config).await
+                }
+                GovSubmitSubcommand::Airdrop(opts) => {
+                    submit_airdrop(opts, address_prefix, &config).await
                 }
-                GovSubmitSubcommand::Airdrop(opts) => submit_airdrop(opts, address_prefix).await,
                 GovSubmitSubcommand::EmergencyBridgeHalt(opts) => {
-                    submit_emergency_bridge_halt(opts, address_prefix).await
+                    submit_emergency_bridge_halt(opts, address_prefix, &config).await
                 }
                 GovSubmitSubcommand::OracleUnhalt(opts) => {
-                    submit_oracle_unhalt(opts, address_prefix).await
+                    submit_oracle_unhalt(opts, address_prefix, &config).await
+                }
+                GovSubmitSubcommand::ParamChange(opts) => {
+                    submit_param_change(opts, address_prefix, &config).await
                 }
             },
             GovSubcommand::Query(query_opts) => match query_opts {
-                GovQuerySubcommand::Airdrop(opts) => query_airdrops(opts, address_prefix).await,
+                GovQuerySubcommand::Airdrop(opts) => {
+                    query_airdrops(opts, address_prefix, &config).await
+                }
+                GovQuerySubcommand::Proposals(opts) => {
+                    query_proposals(opts, address_prefix, &config).await
+                }
             },
+            GovSubcommand::Vote(opts) => vote(opts, address_prefix, &config).await,
+            GovSubcommand::Deposit(opts) => deposit(opts, address_prefix, &config).await,
+            GovSubcommand::Template(opts) => gov_template(opts),
         },
     }
 }
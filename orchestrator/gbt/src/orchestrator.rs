@@ -1,8 +1,7 @@
-use std::{cmp::min, time::Duration};
+use std::{cmp::min, path::PathBuf, time::Duration};
 
 use cosmos_gravity::query::get_gravity_params;
 use gravity_utils::{
-    clarity::constants::ZERO_ADDRESS,
     connection_prep::{
         check_delegate_addresses, check_for_eth, check_for_fee, create_rpc_connections,
         wait_for_cosmos_node_ready,
@@ -13,23 +12,36 @@ use gravity_utils::{
     types::{BatchRequestMode, GravityBridgeToolsConfig},
     TEST_ETH_CHAIN_ID, USE_FINALIZATION,
 };
-use metrics_exporter::metrics_server;
+use metrics_exporter::{health_balance_threshold, metrics_server};
 use orchestrator::main_loop::{
     orchestrator_main_loop, ETH_ORACLE_LOOP_SPEED, ETH_SIGNER_LOOP_SPEED,
 };
+use tokio::sync::watch;
 
-use crate::{args::OrchestratorOpts, utils::print_relaying_explanation};
+use crate::{
+    args::OrchestratorOpts,
+    config::{
+        hangup_reloads, resolve_cosmos_key, resolve_ethereum_key, resolve_fees,
+        resolve_gravity_contract_address, watch_config,
+    },
+    utils::print_relaying_explanation,
+};
 
 pub async fn orchestrator(
     args: OrchestratorOpts,
     address_prefix: String,
     config: GravityBridgeToolsConfig,
+    home_dir: PathBuf,
 ) -> Result<(), GravityError> {
-    let fee = args.fees;
-    let cosmos_grpc = args.cosmos_grpc;
-    let ethereum_rpc = args.ethereum_rpc;
-    let ethereum_key = args.ethereum_key;
-    let cosmos_key = args.cosmos_phrase;
+    let fee = resolve_fees(args.fees, &config.connection)?;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+    let ethereum_key = resolve_ethereum_key(args.ethereum_key, &config.keys)?;
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
 
     let timeout = min(
         min(ETH_SIGNER_LOOP_SPEED, ETH_ORACLE_LOOP_SPEED),
@@ -104,27 +116,13 @@ pub async fn orchestrator(
         .expect("Failed to get Gravity Bridge module parameters!");
 
     // get the gravity contract address, if not provided
-    let contract_address = if let Some(c) = args.gravity_contract_address {
-        c
-    } else {
-        let c = params.bridge_ethereum_address.parse();
-        match c {
-            Ok(v) => {
-                if v == ZERO_ADDRESS {
-                    return Err(GravityError::UnrecoverableError(
-                        "The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into(),
-                    ));
-                }
-                c.unwrap()
-            }
-            Err(_) => {
-                return Err(GravityError::UnrecoverableError(
-                    "The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into(),
-                ));
-            }
-        }
-    };
+    let contract_address = resolve_gravity_contract_address(
+        args.gravity_contract_address,
+        &config.connection,
+        &params,
+    )?;
 
+    let (config_sender, shared_config) = watch::channel(config.clone());
     if config.orchestrator.relayer_enabled {
         // setup and explain relayer settings
         if config.relayer.batch_request_mode != BatchRequestMode::None {
@@ -133,10 +131,19 @@ pub async fn orchestrator(
         } else {
             print_relaying_explanation(&config.relayer, false)
         }
+        // the orchestrator always has fees, so batches may be requested after a reload
+        tokio::spawn(watch_config(
+            home_dir,
+            true,
+            config_sender,
+            hangup_reloads(),
+        ));
     }
 
     // Start monitiring if enabled on config.toml
-    if config.metrics.metrics_enabled {
+    if config.metrics.enabled {
+        health_balance_threshold("eth", config.metrics.min_eth_balance);
+        health_balance_threshold(&fee.denom, config.metrics.min_fee_balance);
         metrics_server(&config.metrics);
     };
 
@@ -149,7 +156,7 @@ pub async fn orchestrator(
         contract_address,
         params.gravity_id,
         fee,
-        config,
+        shared_config,
     )
     .await
 }
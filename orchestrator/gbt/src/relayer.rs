@@ -1,25 +1,41 @@
+use std::path::PathBuf;
+
 use cosmos_gravity::query::get_gravity_params;
 use gravity_utils::{
-    clarity::constants::ZERO_ADDRESS,
     connection_prep::{
         check_for_eth, check_for_fee, create_rpc_connections, wait_for_cosmos_node_ready,
     },
     error::GravityError,
-    types::{BatchRequestMode, RelayerConfig},
+    types::{BatchRequestMode, GravityBridgeToolsConfig},
 };
+use metrics_exporter::{health_balance_threshold, metrics_server};
 use relayer::main_loop::{relayer_main_loop, TIMEOUT};
+use tokio::sync::watch;
 
-use crate::{args::RelayerOpts, utils::print_relaying_explanation};
+use crate::{
+    args::RelayerOpts,
+    config::{
+        hangup_reloads, resolve_cosmos_key, resolve_ethereum_key, resolve_gravity_contract_address,
+        watch_config,
+    },
+    utils::print_relaying_explanation,
+};
 
 pub async fn relayer(
     args: RelayerOpts,
     address_prefix: String,
-    config: &RelayerConfig,
+    config: &GravityBridgeToolsConfig,
+    home_dir: PathBuf,
 ) -> Result<(), GravityError> {
-    let cosmos_grpc = args.cosmos_grpc;
-    let ethereum_rpc = args.ethereum_rpc;
-    let ethereum_key = args.ethereum_key;
-    let cosmos_key = args.cosmos_phrase;
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+    let ethereum_key = resolve_ethereum_key(args.ethereum_key, &config.keys)?;
+    let cosmos_key = resolve_cosmos_key(args.cosmos_phrase, &config.keys)?;
+    let fees = args.fees.or_else(|| config.connection.fees.clone());
 
     let connections = create_rpc_connections(
         address_prefix,
@@ -49,51 +65,50 @@ pub async fn relayer(
         .expect("Failed to get Gravity Bridge module parameters!");
 
     // get the gravity contract address, if not provided
-    let contract_address = if let Some(c) = args.gravity_contract_address {
-        c
-    } else {
-        let c = params.bridge_ethereum_address.parse();
-
-        match c {
-            Ok(v) => {
-                if v == ZERO_ADDRESS {
-                    return Err(GravityError::UnrecoverableError(
-                        "The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into(),
-                    ));
-                }
-
-                v
-            }
-            Err(_) => {
-                return Err(GravityError::UnrecoverableError("The Gravity address is not yet set as a chain parameter! You must specify --gravity-contract-address".into()));
-            }
-        }
-    };
+    let contract_address = resolve_gravity_contract_address(
+        args.gravity_contract_address,
+        &config.connection,
+        &params,
+    )?;
     info!("Gravity contract address {}", contract_address);
 
     // setup and explain relayer settings
-    if let Some(fee) = args.fees.clone() {
-        if config.batch_request_mode != BatchRequestMode::None {
+    if let Some(fee) = fees.clone() {
+        if config.relayer.batch_request_mode != BatchRequestMode::None {
             let public_cosmos_key = cosmos_key.to_address(&contact.get_prefix()).unwrap();
             check_for_fee(&fee, public_cosmos_key, &contact).await?;
-            print_relaying_explanation(config, true)
+            print_relaying_explanation(&config.relayer, true)
         } else {
-            print_relaying_explanation(config, false)
+            print_relaying_explanation(&config.relayer, false)
         }
     } else {
-        print_relaying_explanation(config, false)
+        print_relaying_explanation(&config.relayer, false)
     }
 
+    let (config_sender, shared_config) = watch::channel(config.clone());
+    tokio::spawn(watch_config(
+        home_dir,
+        fees.is_some(),
+        config_sender,
+        hangup_reloads(),
+    ));
+
+    // Start the metrics server if enabled in config.toml
+    if config.metrics.enabled {
+        health_balance_threshold("eth", config.metrics.min_eth_balance);
+        metrics_server(&config.metrics);
+    };
+
     relayer_main_loop(
         ethereum_key,
         Some(cosmos_key),
-        args.fees,
+        fees,
         web3,
         contact,
         grpc,
         contract_address,
         params.gravity_id,
-        config,
+        shared_config,
     )
     .await
 }
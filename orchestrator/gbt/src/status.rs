@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use cosmos_gravity::query::{
+    get_current_valset, get_gravity_params, get_last_event_nonce_for_validator,
+    get_last_observed_event_nonce, get_latest_logic_calls, get_latest_transaction_batches,
+    get_logic_call_signatures, get_pending_batch_fees, get_transaction_batch_signatures,
+};
+use ethereum_gravity::utils::get_event_nonce;
+use gravity_proto::gravity::{
+    query_client::QueryClient as GravityQueryClient, Attestation, MsgBatchSendToEthClaim,
+    MsgErc20DeployedClaim, MsgLogicCallExecutedClaim, MsgSendToCosmosClaim, MsgValsetUpdatedClaim,
+    Params, QueryDelegateKeysByValidatorAddress,
+};
+use gravity_utils::{
+    clarity::{utils::bytes_to_hex_str, Address as EthAddress, Uint256},
+    connection_prep::create_rpc_connections,
+    deep_space::Address as CosmosAddress,
+    error::GravityError,
+    types::{GravityBridgeToolsConfig, Valset, GRAVITY_POWER_TO_PASS, TOTAL_GRAVITY_POWER},
+    web30::client::Web3,
+};
+use prost::{bytes::BytesMut, Message};
+use relayer::find_latest_valset::find_latest_valset;
+use serde::Serialize;
+use tonic::transport::Channel;
+
+use crate::{args::StatusOpts, config::resolve_gravity_contract_address, utils::TIMEOUT};
+
+/// A snapshot of the bridge state on both chains
+#[derive(Serialize, Debug, Clone)]
+pub struct BridgeStatus {
+    pub params: ParamsStatus,
+    pub valset: ValsetStatus,
+    pub event_nonce: EventNonceStatus,
+    pub batch_fees: Vec<BatchFeeStatus>,
+    pub batches: Vec<BatchStatus>,
+    pub logic_calls: Vec<LogicCallStatus>,
+    pub validator: Option<ValidatorStatus>,
+}
+
+/// The subset of the Gravity module parameters relevant to bridge operation
+#[derive(Serialize, Debug, Clone)]
+pub struct ParamsStatus {
+    pub gravity_id: String,
+    pub bridge_ethereum_address: String,
+    pub bridge_chain_id: u64,
+    /// false if the bridge has been paused by governance
+    pub bridge_active: bool,
+    pub signed_valsets_window: u64,
+    pub signed_batches_window: u64,
+    pub signed_logic_calls_window: u64,
+    pub target_batch_timeout: u64,
+    pub average_block_time: u64,
+    pub average_ethereum_block_time: u64,
+}
+
+impl From<&Params> for ParamsStatus {
+    fn from(input: &Params) -> Self {
+        ParamsStatus {
+            gravity_id: input.gravity_id.clone(),
+            bridge_ethereum_address: input.bridge_ethereum_address.clone(),
+            bridge_chain_id: input.bridge_chain_id,
+            bridge_active: input.bridge_active,
+            signed_valsets_window: input.signed_valsets_window,
+            signed_batches_window: input.signed_batches_window,
+            signed_logic_calls_window: input.signed_logic_calls_window,
+            target_batch_timeout: input.target_batch_timeout,
+            average_block_time: input.average_block_time,
+            average_ethereum_block_time: input.average_ethereum_block_time,
+        }
+    }
+}
+
+/// The current validator set on Cosmos compared to the last one submitted to Ethereum
+#[derive(Serialize, Debug, Clone)]
+pub struct ValsetStatus {
+    pub cosmos_nonce: u64,
+    pub cosmos_members: usize,
+    pub ethereum_nonce: u64,
+    pub ethereum_members: usize,
+    /// the fraction of voting power that differs between the two sets, see Valset::power_diff
+    pub power_diff: f32,
+}
+
+/// The last event nonce in the Gravity contract compared to the last one observed on Cosmos
+#[derive(Serialize, Debug, Clone)]
+pub struct EventNonceStatus {
+    pub ethereum: u64,
+    pub cosmos: u64,
+}
+
+/// Fees for transactions waiting in the pool for a token, along with how many
+/// batches of this token are still missing signatures
+#[derive(Serialize, Debug, Clone)]
+pub struct BatchFeeStatus {
+    pub token: String,
+    pub total_fees: String,
+    pub unbatched_txs: u64,
+    pub unsigned_batches: usize,
+}
+
+/// A batch that has not yet been observed as executed on Ethereum
+#[derive(Serialize, Debug, Clone)]
+pub struct BatchStatus {
+    pub token_contract: EthAddress,
+    pub nonce: u64,
+    pub txs: usize,
+    pub total_fee: Uint256,
+    pub timeout: u64,
+    pub timed_out: bool,
+    pub signatures: SignaturePower,
+}
+
+/// A logic call that has not yet been observed as executed on Ethereum
+#[derive(Serialize, Debug, Clone)]
+pub struct LogicCallStatus {
+    pub invalidation_id: String,
+    pub invalidation_nonce: u64,
+    pub logic_contract_address: EthAddress,
+    pub timeout: u64,
+    pub timed_out: bool,
+    pub signatures: SignaturePower,
+}
+
+/// The voting power of the signatures collected for a batch or logic call, measured
+/// against the validator set currently in the Gravity contract
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct SignaturePower {
+    pub signers: usize,
+    pub power: u64,
+    pub percent: f32,
+    pub enough_power: bool,
+}
+
+/// The delegate keys and oracle progress of a single validator
+#[derive(Serialize, Debug, Clone)]
+pub struct ValidatorStatus {
+    pub validator_address: String,
+    pub orchestrator_address: String,
+    pub eth_address: String,
+    pub last_event_nonce: u64,
+}
+
+pub async fn status(
+    args: StatusOpts,
+    address_prefix: String,
+    config: &GravityBridgeToolsConfig,
+) -> Result<(), GravityError> {
+    let cosmos_grpc = args
+        .cosmos_grpc
+        .unwrap_or_else(|| config.connection.cosmos_grpc.clone());
+    let ethereum_rpc = args
+        .ethereum_rpc
+        .unwrap_or_else(|| config.connection.ethereum_rpc.clone());
+
+    let connections = create_rpc_connections(
+        address_prefix.clone(),
+        Some(cosmos_grpc),
+        Some(ethereum_rpc),
+        TIMEOUT,
+    )
+    .await;
+    let web3 = connections.web3.unwrap();
+    let mut grpc = connections.grpc.unwrap();
+
+    let params = get_gravity_params(&mut grpc).await?;
+    let gravity_contract_address = resolve_gravity_contract_address(
+        args.gravity_contract_address,
+        &config.connection,
+        &params,
+    )?;
+
+    let status = get_bridge_status(
+        &mut grpc,
+        &web3,
+        gravity_contract_address,
+        &params,
+        args.validator,
+        address_prefix,
+    )
+    .await?;
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status)
+                .map_err(|e| GravityError::UnrecoverableError(format!("{e:?}")))?
+        );
+    } else {
+        print_bridge_status(&status);
+    }
+    Ok(())
+}
+
+async fn get_bridge_status(
+    grpc: &mut GravityQueryClient<Channel>,
+    web3: &Web3,
+    gravity_contract_address: EthAddress,
+    params: &Params,
+    validator: Option<String>,
+    address_prefix: String,
+) -> Result<BridgeStatus, GravityError> {
+    let current_block = web3.eth_block_number().await?;
+
+    let cosmos_valset = get_current_valset(grpc).await?;
+    let ethereum_valset = find_latest_valset(grpc, gravity_contract_address, web3).await?;
+    let valset = ValsetStatus {
+        cosmos_nonce: cosmos_valset.nonce,
+        cosmos_members: cosmos_valset.members.len(),
+        ethereum_nonce: ethereum_valset.nonce,
+        ethereum_members: ethereum_valset.members.len(),
+        power_diff: cosmos_valset.power_diff(&ethereum_valset),
+    };
+
+    // read only calls, so the contract itself is as good a caller as any
+    let ethereum_event_nonce =
+        get_event_nonce(gravity_contract_address, gravity_contract_address, web3).await?;
+    let event_nonce = EventNonceStatus {
+        ethereum: ethereum_event_nonce,
+        cosmos: get_last_observed_event_nonce(grpc).await?,
+    };
+
+    let mut batches = Vec::new();
+    for batch in get_latest_transaction_batches(grpc).await? {
+        let sigs =
+            get_transaction_batch_signatures(grpc, batch.nonce, batch.token_contract).await?;
+        let signers: Vec<EthAddress> = sigs.iter().map(|s| s.ethereum_signer).collect();
+        batches.push(BatchStatus {
+            token_contract: batch.token_contract,
+            nonce: batch.nonce,
+            txs: batch.transactions.len(),
+            total_fee: batch.total_fee.amount,
+            timeout: batch.batch_timeout,
+            timed_out: current_block >= Uint256::from_u64(batch.batch_timeout),
+            signatures: signature_power(&ethereum_valset, &signers),
+        });
+    }
+
+    let mut logic_calls = Vec::new();
+    for call in get_latest_logic_calls(grpc).await? {
+        let sigs =
+            get_logic_call_signatures(grpc, call.invalidation_id.clone(), call.invalidation_nonce)
+                .await?;
+        let signers: Vec<EthAddress> = sigs.iter().map(|s| s.ethereum_signer).collect();
+        logic_calls.push(LogicCallStatus {
+            invalidation_id: bytes_to_hex_str(&call.invalidation_id),
+            invalidation_nonce: call.invalidation_nonce,
+            logic_contract_address: call.logic_contract_address,
+            timeout: call.timeout,
+            timed_out: current_block >= Uint256::from_u64(call.timeout),
+            signatures: signature_power(&ethereum_valset, &signers),
+        });
+    }
+
+    let mut batch_fees = Vec::new();
+    for fee in get_pending_batch_fees(grpc).await?.batch_fees {
+        let token: Option<EthAddress> = fee.token.parse().ok();
+        let unsigned_batches = batches
+            .iter()
+            .filter(|b| Some(b.token_contract) == token && !b.signatures.enough_power)
+            .count();
+        batch_fees.push(BatchFeeStatus {
+            token: fee.token,
+            total_fees: fee.total_fees,
+            unbatched_txs: fee.tx_count,
+            unsigned_batches,
+        });
+    }
+
+    let validator = match validator {
+        Some(validator_address) => {
+            Some(get_validator_status(grpc, validator_address, address_prefix).await?)
+        }
+        None => None,
+    };
+
+    Ok(BridgeStatus {
+        params: params.into(),
+        valset,
+        event_nonce,
+        batch_fees,
+        batches,
+        logic_calls,
+        validator,
+    })
+}
+
+async fn get_validator_status(
+    grpc: &mut GravityQueryClient<Channel>,
+    validator_address: String,
+    address_prefix: String,
+) -> Result<ValidatorStatus, GravityError> {
+    let keys = grpc
+        .get_delegate_key_by_validator(QueryDelegateKeysByValidatorAddress {
+            validator_address: validator_address.clone(),
+        })
+        .await
+        .map_err(|e| {
+            GravityError::ValidationError(format!(
+                "Could not find delegate keys for validator {validator_address}: {}",
+                e.message()
+            ))
+        })?
+        .into_inner();
+    let orchestrator: CosmosAddress = keys.orchestrator_address.parse()?;
+    let last_event_nonce =
+        get_last_event_nonce_for_validator(grpc, orchestrator, address_prefix).await?;
+    Ok(ValidatorStatus {
+        validator_address,
+        orchestrator_address: keys.orchestrator_address,
+        eth_address: keys.eth_address,
+        last_event_nonce,
+    })
+}
+
+/// Sums the power of the given signers in the provided validator set. Signatures are
+/// checked by the chain when they are submitted so they are not verified again here
+pub fn signature_power(valset: &Valset, signers: &[EthAddress]) -> SignaturePower {
+    let powers: HashMap<EthAddress, u64> = valset.to_hashmap();
+    let power: u64 = signers.iter().filter_map(|s| powers.get(s)).sum();
+    SignaturePower {
+        signers: signers.len(),
+        power,
+        percent: (power as f32 / TOTAL_GRAVITY_POWER as f32) * 100f32,
+        enough_power: power > GRAVITY_POWER_TO_PASS,
+    }
+}
+
+/// Decodes the event nonce out of an attestation claim, returns None for unknown claim types
+pub fn attestation_event_nonce(attestation: &Attestation) -> Option<u64> {
+    let claim = attestation.claim.as_ref()?;
+    let mut buf = BytesMut::with_capacity(claim.value.len());
+    buf.extend_from_slice(&claim.value);
+    match claim.type_url.as_str() {
+        "/gravity.v1.MsgSendToCosmosClaim" => MsgSendToCosmosClaim::decode(buf)
+            .ok()
+            .map(|c| c.event_nonce),
+        "/gravity.v1.MsgBatchSendToEthClaim" => MsgBatchSendToEthClaim::decode(buf)
+            .ok()
+            .map(|c| c.event_nonce),
+        "/gravity.v1.MsgErc20DeployedClaim" => MsgErc20DeployedClaim::decode(buf)
+            .ok()
+            .map(|c| c.event_nonce),
+        "/gravity.v1.MsgLogicCallExecutedClaim" => MsgLogicCallExecutedClaim::decode(buf)
+            .ok()
+            .map(|c| c.event_nonce),
+        "/gravity.v1.MsgValsetUpdatedClaim" => MsgValsetUpdatedClaim::decode(buf)
+            .ok()
+            .map(|c| c.event_nonce),
+        _ => None,
+    }
+}
+
+fn print_bridge_status(status: &BridgeStatus) {
+    let params = &status.params;
+    info!("Gravity ID: {}", params.gravity_id);
+    info!(
+        "Gravity contract: {} on chain id {}",
+        params.bridge_ethereum_address, params.bridge_chain_id
+    );
+    if params.bridge_active {
+        info!("Bridge is active");
+    } else {
+        warn!("Bridge is paused! Deposits and withdrawals will not be processed");
+    }
+
+    let valset = &status.valset;
+    info!(
+        "Valset on Cosmos: nonce {} with {} members",
+        valset.cosmos_nonce, valset.cosmos_members
+    );
+    info!(
+        "Valset on Ethereum: nonce {} with {} members",
+        valset.ethereum_nonce, valset.ethereum_members
+    );
+    info!("Valset power difference: {:.4}", valset.power_diff);
+
+    let nonce = &status.event_nonce;
+    if nonce.ethereum == nonce.cosmos {
+        info!("Event nonce: {} on both chains", nonce.ethereum);
+    } else {
+        warn!(
+            "Event nonce: {} on Ethereum, {} observed on Cosmos",
+            nonce.ethereum, nonce.cosmos
+        );
+    }
+
+    if status.batch_fees.is_empty() {
+        info!("No transactions waiting to be batched");
+    }
+    for fee in status.batch_fees.iter() {
+        info!(
+            "Token {}: {} unbatched txs with {} total fees, {} batches missing signatures",
+            fee.token, fee.unbatched_txs, fee.total_fees, fee.unsigned_batches
+        );
+    }
+
+    if status.batches.is_empty() {
+        info!("No batches waiting to be relayed");
+    }
+    for batch in status.batches.iter() {
+        info!(
+            "Batch {} for {}: {} txs, {} total fee, timeout block {}{}, signed by {} ({:.2}% power)",
+            batch.nonce,
+            batch.token_contract,
+            batch.txs,
+            batch.total_fee,
+            batch.timeout,
+            if batch.timed_out { " (timed out)" } else { "" },
+            batch.signatures.signers,
+            batch.signatures.percent
+        );
+    }
+
+    if status.logic_calls.is_empty() {
+        info!("No logic calls waiting to be relayed");
+    }
+    for call in status.logic_calls.iter() {
+        info!(
+            "Logic call {}/{} to {}: timeout block {}{}, signed by {} ({:.2}% power)",
+            call.invalidation_id,
+            call.invalidation_nonce,
+            call.logic_contract_address,
+            call.timeout,
+            if call.timed_out { " (timed out)" } else { "" },
+            call.signatures.signers,
+            call.signatures.percent
+        );
+    }
+
+    if let Some(validator) = &status.validator {
+        info!("Validator {}", validator.validator_address);
+        info!("Orchestrator address: {}", validator.orchestrator_address);
+        info!("Ethereum address: {}", validator.eth_address);
+        if validator.last_event_nonce < nonce.ethereum {
+            warn!(
+                "Last submitted event nonce: {}, {} behind Ethereum",
+                validator.last_event_nonce,
+                nonce.ethereum - validator.last_event_nonce
+            );
+        } else {
+            info!("Last submitted event nonce: {}", validator.last_event_nonce);
+        }
+    }
+}
@@ -1,9 +1,94 @@
-use std::time::Duration;
+use std::{convert::TryFrom, time::Duration};
 
-use gravity_utils::types::{BatchRequestMode, RelayerConfig, ValsetRelayingMode};
+use gravity_proto::cosmos_sdk_proto::cosmos::params::v1beta1::{
+    query_client::QueryClient as ParamsQueryClient, QueryParamsRequest,
+};
+use gravity_utils::{
+    error::GravityError,
+    types::{BatchRequestMode, RelayerConfig, ValsetRelayingMode},
+};
+use serde::Deserialize;
 
 pub const TIMEOUT: Duration = Duration::from_secs(60);
 
+/// The Tendermint default for the mempool `max_tx_bytes`, a node setting that can't be queried
+pub const DEFAULT_MEMPOOL_MAX_TX_BYTES: usize = 1_048_576;
+
+/// The block size consensus params
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockParams {
+    pub max_bytes: u64,
+    /// None when blocks have no gas limit
+    pub max_gas: Option<u64>,
+}
+
+impl BlockParams {
+    /// The largest transaction the chain accepts, the block size capped at the default mempool
+    /// `max_tx_bytes` since the mempool limit is local to each node
+    pub fn max_tx_bytes(&self) -> usize {
+        usize::try_from(self.max_bytes)
+            .unwrap_or(usize::MAX)
+            .min(DEFAULT_MEMPOOL_MAX_TX_BYTES)
+    }
+}
+
+/// `BlockParams` as stored in JSON by the baseapp params subspace
+#[derive(Deserialize)]
+struct BlockParamsJson {
+    max_bytes: String,
+    max_gas: String,
+}
+
+/// Gets the block size consensus params
+pub async fn get_block_params(cosmos_grpc: &str) -> Result<BlockParams, GravityError> {
+    let mut params = ParamsQueryClient::connect(cosmos_grpc.to_string())
+        .await
+        .map_err(|e| {
+            GravityError::UnrecoverableError(format!(
+                "Failed to connect to {cosmos_grpc}, check your cosmos gRPC {e:?}"
+            ))
+        })?;
+    let param = params
+        .params(QueryParamsRequest {
+            subspace: "baseapp".to_string(),
+            key: "BlockParams".to_string(),
+        })
+        .await?
+        .into_inner()
+        .param;
+    let block_params = parse_block_params(&param.map(|p| p.value).unwrap_or_default())?;
+    info!(
+        "The chain accepts blocks of up to {} bytes and {} gas",
+        block_params.max_bytes,
+        block_params
+            .max_gas
+            .map(|g| g.to_string())
+            .unwrap_or_else(|| "unlimited".to_string())
+    );
+    Ok(block_params)
+}
+
+fn parse_block_params(value: &str) -> Result<BlockParams, GravityError> {
+    let invalid = |e: String| {
+        GravityError::ValidationError(format!("Invalid BlockParams consensus param {value} {e}"))
+    };
+    let params: BlockParamsJson =
+        serde_json::from_str(value).map_err(|e| invalid(e.to_string()))?;
+    let max_bytes = params
+        .max_bytes
+        .parse()
+        .map_err(|e| invalid(format!("{e}")))?;
+    // -1 means blocks have no gas limit
+    let max_gas: i64 = params
+        .max_gas
+        .parse()
+        .map_err(|e| invalid(format!("{e}")))?;
+    Ok(BlockParams {
+        max_bytes,
+        max_gas: u64::try_from(max_gas).ok(),
+    })
+}
+
 /// Explains the relaying config to users
 pub fn print_relaying_explanation(input: &RelayerConfig, batch_requests: bool) {
     info!("Relaying from Cosmos => Ethereum is enabled, this will cost ETH");
@@ -41,3 +126,21 @@ pub fn print_relaying_explanation(input: &RelayerConfig, batch_requests: bool) {
             info!("This relayer will relay profitable matches with {} margin, and the following tokens with the provided amounts {:?}", margin, whitelist)
     }
 }
+
+#[test]
+fn test_parse_block_params() {
+    let params = parse_block_params(r#"{"max_bytes":"22020096","max_gas":"-1"}"#).unwrap();
+    assert_eq!(
+        params,
+        BlockParams {
+            max_bytes: 22_020_096,
+            max_gas: None
+        }
+    );
+    assert_eq!(params.max_tx_bytes(), DEFAULT_MEMPOOL_MAX_TX_BYTES);
+    let params = parse_block_params(r#"{"max_bytes":"200000","max_gas":"40000000"}"#).unwrap();
+    assert_eq!(params.max_gas, Some(40_000_000));
+    assert_eq!(params.max_tx_bytes(), 200_000);
+    assert!(parse_block_params("").is_err());
+    assert!(parse_block_params(r#"{"max_bytes":"-1","max_gas":"-1"}"#).is_err());
+}
@@ -1,83 +0,0 @@
-/// Params defines the parameters for the bank module.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Params {
-    #[prost(message, repeated, tag="1")]
-    pub send_enabled: ::prost::alloc::vec::Vec<SendEnabled>,
-    #[prost(bool, tag="2")]
-    pub default_send_enabled: bool,
-}
-/// SendEnabled maps coin denom to a send_enabled status (whether a denom is
-/// sendable).
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SendEnabled {
-    #[prost(string, tag="1")]
-    pub denom: ::prost::alloc::string::String,
-    #[prost(bool, tag="2")]
-    pub enabled: bool,
-}
-/// Input models transaction input.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Input {
-    #[prost(string, tag="1")]
-    pub address: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag="2")]
-    pub coins: ::prost::alloc::vec::Vec<super::super::base::v1beta1::Coin>,
-}
-/// Output models transaction outputs.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Output {
-    #[prost(string, tag="1")]
-    pub address: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag="2")]
-    pub coins: ::prost::alloc::vec::Vec<super::super::base::v1beta1::Coin>,
-}
-/// Supply represents a struct that passively keeps track of the total supply
-/// amounts in the network.
-/// This message is deprecated now that supply is indexed by denom.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Supply {
-    #[prost(message, repeated, tag="1")]
-    pub total: ::prost::alloc::vec::Vec<super::super::base::v1beta1::Coin>,
-}
-/// DenomUnit represents a struct that describes a given
-/// denomination unit of the basic token.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DenomUnit {
-    /// denom represents the string name of the given denom unit (e.g anom).
-    #[prost(string, tag="1")]
-    pub denom: ::prost::alloc::string::String,
-    /// exponent represents power of 10 exponent that one must
-    /// raise the base_denom to in order to equal the given DenomUnit's denom
-    /// 1 denom = 1^exponent base_denom
-    /// (e.g. with a base_denom of anom, one can create a DenomUnit of 'nom' with
-    /// exponent = 18, thus: 1 nom = 10^18 anom).
-    #[prost(uint32, tag="2")]
-    pub exponent: u32,
-    /// aliases is a list of string aliases for the given denom
-    #[prost(string, repeated, tag="3")]
-    pub aliases: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-}
-/// Metadata represents a struct that describes
-/// a basic token.
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Metadata {
-    #[prost(string, tag="1")]
-    pub description: ::prost::alloc::string::String,
-    /// denom_units represents the list of DenomUnit's for a given coin
-    #[prost(message, repeated, tag="2")]
-    pub denom_units: ::prost::alloc::vec::Vec<DenomUnit>,
-    /// base represents the base denom (should be the DenomUnit with exponent = 0).
-    #[prost(string, tag="3")]
-    pub base: ::prost::alloc::string::String,
-    /// display indicates the suggested denom that should be
-    /// displayed in clients.
-    #[prost(string, tag="4")]
-    pub display: ::prost::alloc::string::String,
-    /// name defines the name of the token (eg: Onomy Nom)
-    #[prost(string, tag="5")]
-    pub name: ::prost::alloc::string::String,
-    /// symbol is the token symbol usually shown on exchanges (eg: NOM). This can
-    /// be the same as the display.
-    #[prost(string, tag="6")]
-    pub symbol: ::prost::alloc::string::String,
-}
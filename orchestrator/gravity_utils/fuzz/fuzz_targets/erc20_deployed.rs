@@ -0,0 +1,9 @@
+#![no_main]
+
+use gravity_utils::types::Erc20DeployedEvent;
+use gravity_utils_fuzz::FuzzLog;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: FuzzLog| {
+    let _ = Erc20DeployedEvent::from_log(&input.to_log());
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use gravity_utils::types::TransactionBatchExecutedEvent;
+use gravity_utils_fuzz::FuzzLog;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: FuzzLog| {
+    let _ = TransactionBatchExecutedEvent::from_log(&input.to_log());
+});
@@ -0,0 +1,38 @@
+//! Shared input for the Ethereum event decoder fuzz targets. Run a target from
+//! `orchestrator/gravity_utils` with `cargo +nightly fuzz run send_to_cosmos`, any panic is
+//! an oracle halting bug
+//!
+//! The topics are not fixed to 32 bytes so that malformed node responses are covered too
+
+use gravity_utils::{deep_space::utils::bytes_to_hex_str, web30::types::Log};
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+
+#[derive(Arbitrary, Debug)]
+pub struct FuzzLog {
+    pub topics: Vec<Vec<u8>>,
+    pub data: Vec<u8>,
+    pub block_number: Option<u64>,
+}
+
+impl FuzzLog {
+    /// Builds the log the way the Ethereum node returns it
+    pub fn to_log(&self) -> Log {
+        let topics: Vec<String> = self
+            .topics
+            .iter()
+            .map(|t| format!("0x{}", bytes_to_hex_str(t)))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "address": "0x0000000000000000000000000000000000000000",
+            "topics": topics,
+            "data": format!("0x{}", bytes_to_hex_str(&self.data)),
+            "blockNumber": self.block_number.map(|n| format!("{n:#x}")),
+            "blockHash": null,
+            "transactionHash": null,
+            "transactionIndex": null,
+            "logIndex": null,
+            "removed": false,
+        }))
+        .unwrap()
+    }
+}
@@ -1,55 +1,197 @@
-use clarity::Uint256;
+use std::{fmt, str::FromStr};
 
-const ONE_ETH_FLOAT: f64 = 1000000000000000000.;
-const ONE_GWEI_FLOAT: f64 = 1000000000.;
-const ONE_NOM_FLOAT: f64 = 1000000000000000000.;
+use clarity::{u256, Uint256};
 
-/// TODO revisit this for higher precision while
-/// still representing the number to the user as a float
-/// this takes a number like 0.37 eth and turns it into wei
-/// or any erc20 with arbitrary decimals
-pub fn fraction_to_exponent(num: f64, exponent: u8) -> Uint256 {
-    let mut res = num;
-    // in order to avoid floating point rounding issues we
-    // multiply only by 10 each time. this reduces the rounding
-    // errors enough to be ignored
-    for _ in 0..exponent {
-        res *= 10f64
+use crate::error::GravityError;
+
+pub const ETH_DECIMALS: u8 = 18;
+pub const GWEI_DECIMALS: u8 = 9;
+pub const NOM_DECIMALS: u8 = 18;
+
+/// An exact, non negative decimal token amount such as `1.25` as entered by a user.
+/// The amount is kept in decimal form until the number of decimals of the token is
+/// known, no floating point math is done at any point so nothing is lost for 18 decimal
+/// tokens or very large amounts
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DecimalAmount {
+    /// the integer part without leading zeros, empty for zero
+    integer: String,
+    /// the fractional part without trailing zeros
+    fraction: String,
+}
+
+/// The error returned when a string is not a valid decimal amount
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid amount {0}, expected a decimal number such as 1.25")]
+pub struct ParseDecimalAmountError(String);
+
+impl DecimalAmount {
+    fn new(integer: &str, fraction: &str) -> DecimalAmount {
+        DecimalAmount {
+            integer: integer.trim_start_matches('0').to_string(),
+            fraction: fraction.trim_end_matches('0').to_string(),
+        }
+    }
+
+    /// Converts an amount in the smallest unit of a token, such as wei, into a decimal
+    /// amount for a token with the given number of decimals
+    pub fn from_base_units(amount: Uint256, decimals: u8) -> DecimalAmount {
+        let digits = amount.to_string();
+        let decimals = decimals as usize;
+        if digits.len() > decimals {
+            let (integer, fraction) = digits.split_at(digits.len() - decimals);
+            DecimalAmount::new(integer, fraction)
+        } else {
+            DecimalAmount::new("", &format!("{digits:0>decimals$}"))
+        }
+    }
+
+    /// Converts this amount into the smallest unit of a token with the given number of
+    /// decimals. Amounts with more decimal places than the token supports are an error
+    /// rather than being silently rounded
+    pub fn to_base_units(&self, decimals: u8) -> Result<Uint256, GravityError> {
+        let decimals = decimals as usize;
+        if self.fraction.len() > decimals {
+            return Err(GravityError::ValidationError(format!(
+                "Amount {self} has more than the {decimals} decimal places this token supports"
+            )));
+        }
+        let digits = format!("{}{:0<decimals$}", self.integer, self.fraction);
+        let digits = digits.trim_start_matches('0');
+        if digits.is_empty() {
+            return Ok(u256!(0));
+        }
+        Uint256::from_dec_or_hex_str_restricted(digits).map_err(|_| {
+            GravityError::ValidationError(format!("Amount {self} is too large for a 256 bit value"))
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.integer.is_empty() && self.fraction.is_empty()
+    }
+}
+
+impl FromStr for DecimalAmount {
+    type Err = ParseDecimalAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (integer, fraction) = match s.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (s, ""),
+        };
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty())
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            return Err(ParseDecimalAmountError(s.to_string()));
+        }
+        Ok(DecimalAmount::new(integer, fraction))
+    }
+}
+
+impl fmt::Display for DecimalAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let integer = if self.integer.is_empty() {
+            "0"
+        } else {
+            &self.integer
+        };
+        if self.fraction.is_empty() {
+            write!(f, "{integer}")
+        } else {
+            write!(f, "{integer}.{}", self.fraction)
+        }
     }
-    Uint256::from_u128(res as u128)
 }
 
 pub fn print_eth(input: Uint256) -> String {
-    let float: f64 = input.to_string().parse().unwrap();
-    let res = float / ONE_ETH_FLOAT;
-    format!("{res:.4}")
+    DecimalAmount::from_base_units(input, ETH_DECIMALS).to_string()
 }
 
 pub fn print_nom(input: Uint256) -> String {
-    let float: f64 = input.to_string().parse().unwrap();
-    let res = float / ONE_NOM_FLOAT;
-    format!("{res:.4}")
+    DecimalAmount::from_base_units(input, NOM_DECIMALS).to_string()
 }
 
 pub fn print_gwei(input: Uint256) -> String {
-    let float: f64 = input.to_string().parse().unwrap();
-    let res = float / ONE_GWEI_FLOAT;
-    format!("{res:}")
+    DecimalAmount::from_base_units(input, GWEI_DECIMALS).to_string()
 }
 
 #[test]
-fn even_f32_rounding() {
-    use clarity::u256;
+fn exact_decimal_conversion() {
     let one_eth = u256!(1000000000000000000);
     let one_point_five_eth = u256!(1500000000000000000);
     let one_point_one_five_eth = u256!(1150000000000000000);
     let a_high_precision_number = u256!(1150100000000000000);
-    let res = fraction_to_exponent(1f64, 18);
-    assert_eq!(one_eth, res);
-    let res = fraction_to_exponent(1.5f64, 18);
-    assert_eq!(one_point_five_eth, res);
-    let res = fraction_to_exponent(1.15f64, 18);
-    assert_eq!(one_point_one_five_eth, res);
-    let res = fraction_to_exponent(1.1501f64, 18);
-    assert_eq!(a_high_precision_number, res);
+    let the_smallest_number = u256!(1);
+    let parse = |s: &str| s.parse::<DecimalAmount>().unwrap();
+    assert_eq!(parse("1").to_base_units(18).unwrap(), one_eth);
+    assert_eq!(parse("1.5").to_base_units(18).unwrap(), one_point_five_eth);
+    assert_eq!(
+        parse("1.15").to_base_units(18).unwrap(),
+        one_point_one_five_eth
+    );
+    assert_eq!(
+        parse("1.1501").to_base_units(18).unwrap(),
+        a_high_precision_number
+    );
+    assert_eq!(
+        parse("0.000000000000000001").to_base_units(18).unwrap(),
+        the_smallest_number
+    );
+    assert_eq!(parse("007.50").to_string(), "7.5");
+    assert_eq!(parse(".5").to_string(), "0.5");
+    assert_eq!(parse("0.0").to_string(), "0");
+    assert_eq!(print_eth(one_point_one_five_eth), "1.15");
+    assert_eq!(print_gwei(u256!(1)), "0.000000001");
+
+    // more decimal places than the token has is an error, not a rounding
+    assert!(parse("1.0000001").to_base_units(6).is_err());
+    assert_eq!(parse("1.0000000").to_base_units(6).unwrap(), u256!(1000000));
+    // larger than 2^256
+    assert!(parse(&"9".repeat(80)).to_base_units(0).is_err());
+
+    for invalid in ["", ".", "-1", "1e18", "1.2.3", "1,5", "one", "+1", "0x10"] {
+        assert!(invalid.parse::<DecimalAmount>().is_err(), "{}", invalid);
+    }
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #![proptest_config(proptest::prelude::ProptestConfig::with_cases(10_000))]
+
+    #[test]
+    fn decimal_amount_base_units_round_trip(digits in "[0-9]{0,76}", decimals in 0u8..=36) {
+        // base units -> decimal -> string -> decimal -> base units
+        let digits = digits.trim_start_matches('0');
+        let amount = if digits.is_empty() {
+            u256!(0)
+        } else {
+            Uint256::from_dec_or_hex_str_restricted(digits).unwrap()
+        };
+        let decimal = DecimalAmount::from_base_units(amount, decimals);
+        let parsed: DecimalAmount = decimal.to_string().parse().unwrap();
+        assert_eq!(parsed, decimal);
+        assert_eq!(parsed.to_base_units(decimals).unwrap(), amount);
+    }
+
+    #[test]
+    fn decimal_amount_string_round_trip(
+        integer in "[0-9]{0,30}",
+        fraction in "[0-9]{0,30}",
+        extra_decimals in 0u8..=10,
+    ) {
+        // string -> decimal -> base units -> decimal
+        if !(integer.is_empty() && fraction.is_empty()) {
+            let input = format!("{}.{}", integer, fraction);
+            let decimal: DecimalAmount = input.parse().unwrap();
+            let decimals = fraction.len() as u8 + extra_decimals;
+            let base_units = decimal.to_base_units(decimals).unwrap();
+            assert_eq!(
+                DecimalAmount::from_base_units(base_units, decimals),
+                decimal
+            );
+        }
+    }
 }
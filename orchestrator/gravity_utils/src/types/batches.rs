@@ -47,6 +47,8 @@ pub struct TransactionBatch {
     /// the ERC20 token contract shared by all transactions
     /// and fees in this batch
     pub token_contract: EthAddress,
+    /// the Cosmos block height this batch was created at
+    pub block: u64,
 }
 
 impl TransactionBatch {
@@ -187,6 +189,7 @@ impl TryFrom<gravity_proto::gravity::OutgoingTxBatch> for TransactionBatch {
                 transactions,
                 token_contract: total_fee.token_contract_address,
                 total_fee,
+                block: input.block,
             })
         } else {
             Err(GravityError::ValidationError(
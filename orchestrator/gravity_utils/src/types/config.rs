@@ -1,37 +1,165 @@
 //! contains configuration structs that need to be accessed across crates.
 
-use clarity::{Address as EthAddress, Uint256};
+use std::{
+    convert::{TryFrom, TryInto},
+    path::PathBuf,
+};
+
+use clarity::{u256, Address as EthAddress, Uint256};
+use deep_space::Coin;
 use serde::{Deserialize, Serialize};
 
+use crate::{error::GravityError, DEFAULT_ADDRESS_PREFIX};
+
 /// Global configuration struct for Gravity bridge tools
 #[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone)]
 pub struct GravityBridgeToolsConfig {
+    pub connection: ConnectionConfig,
+    pub keys: KeysConfig,
     pub relayer: RelayerConfig,
     pub orchestrator: OrchestratorConfig,
     pub metrics: MetricsConfig,
+    pub watchdog: WatchdogConfig,
 }
 
 /// Toml serializable configuration struct for Gravity bridge tools
 #[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone)]
 pub struct TomlGravityBridgeToolsConfig {
+    #[serde(default = "TomlConnectionConfig::default")]
+    pub connection: TomlConnectionConfig,
+    #[serde(default = "KeysConfig::default")]
+    pub keys: KeysConfig,
     #[serde(default = "TomlRelayerConfig::default")]
     pub relayer: TomlRelayerConfig,
     #[serde(default = "OrchestratorConfig::default")]
     pub orchestrator: OrchestratorConfig,
     #[serde(default = "MetricsConfig::default")]
     pub metrics: MetricsConfig,
+    #[serde(default = "WatchdogConfig::default")]
+    pub watchdog: WatchdogConfig,
 }
 
-impl From<TomlGravityBridgeToolsConfig> for GravityBridgeToolsConfig {
-    fn from(input: TomlGravityBridgeToolsConfig) -> Self {
-        GravityBridgeToolsConfig {
-            relayer: input.relayer.into(),
+impl TryFrom<TomlGravityBridgeToolsConfig> for GravityBridgeToolsConfig {
+    type Error = GravityError;
+
+    fn try_from(input: TomlGravityBridgeToolsConfig) -> Result<Self, GravityError> {
+        Ok(GravityBridgeToolsConfig {
+            connection: input.connection.try_into()?,
+            keys: input.keys,
+            relayer: input.relayer.try_into()?,
             orchestrator: input.orchestrator,
             metrics: input.metrics,
+            watchdog: input.watchdog,
+        })
+    }
+}
+
+/// Endpoints and chain settings shared by every gbt command, any of these
+/// may be overridden on the command line
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ConnectionConfig {
+    /// The Cosmos gRPC server that will be used
+    pub cosmos_grpc: String,
+    /// The Ethereum RPC server that will be used
+    pub ethereum_rpc: String,
+    /// The address prefix for the Cosmos chain
+    pub address_prefix: String,
+    /// The address of the Gravity contract on Ethereum, if not set it
+    /// is looked up from the chain parameters where possible
+    pub gravity_contract_address: Option<EthAddress>,
+    /// The Cosmos Denom and amount to pay Cosmos chain fees
+    pub fees: Option<Coin>,
+}
+
+/// Connection configuration that's more easily parsable with toml
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct TomlConnectionConfig {
+    #[serde(default = "default_cosmos_grpc")]
+    pub cosmos_grpc: String,
+    #[serde(default = "default_ethereum_rpc")]
+    pub ethereum_rpc: String,
+    #[serde(default = "default_address_prefix")]
+    pub address_prefix: String,
+    #[serde(default)]
+    pub gravity_contract_address: Option<EthAddress>,
+    /// fees in the same format as the command line, eg: 1000000anom
+    #[serde(default)]
+    pub fees: Option<String>,
+}
+
+impl TryFrom<TomlConnectionConfig> for ConnectionConfig {
+    type Error = GravityError;
+
+    fn try_from(input: TomlConnectionConfig) -> Result<Self, GravityError> {
+        let fees = match input.fees {
+            Some(fees) => match fees.parse() {
+                Ok(fees) => Some(fees),
+                Err(e) => {
+                    return Err(GravityError::ValidationError(format!(
+                        "Invalid connection.fees \"{fees}\", expected an amount and denom like 1000000anom {e:?}"
+                    )))
+                }
+            },
+            None => None,
+        };
+        Ok(ConnectionConfig {
+            cosmos_grpc: input.cosmos_grpc,
+            ethereum_rpc: input.ethereum_rpc,
+            address_prefix: input.address_prefix,
+            gravity_contract_address: input.gravity_contract_address,
+            fees,
+        })
+    }
+}
+
+fn default_cosmos_grpc() -> String {
+    "http://localhost:9090".to_string()
+}
+
+fn default_ethereum_rpc() -> String {
+    "http://localhost:8545".to_string()
+}
+
+fn default_address_prefix() -> String {
+    DEFAULT_ADDRESS_PREFIX.to_string()
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        ConnectionConfig {
+            cosmos_grpc: default_cosmos_grpc(),
+            ethereum_rpc: default_ethereum_rpc(),
+            address_prefix: default_address_prefix(),
+            gravity_contract_address: None,
+            fees: None,
         }
     }
 }
 
+impl Default for TomlConnectionConfig {
+    fn default() -> Self {
+        TomlConnectionConfig {
+            cosmos_grpc: default_cosmos_grpc(),
+            ethereum_rpc: default_ethereum_rpc(),
+            address_prefix: default_address_prefix(),
+            gravity_contract_address: None,
+            fees: None,
+        }
+    }
+}
+
+/// References to the keys used by gbt, the keys themselves are never stored
+/// in the config, only the files they can be read from
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct KeysConfig {
+    /// A file containing the Cosmos mnemonic phrase
+    #[serde(default)]
+    pub cosmos_phrase_file: Option<PathBuf>,
+    /// A file containing the Ethereum private key
+    #[serde(default)]
+    pub ethereum_key_file: Option<PathBuf>,
+}
+
 /// Relayer configuration options
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct RelayerConfig {
@@ -59,15 +187,23 @@ pub struct TomlRelayerConfig {
     pub relayer_loop_speed: u64,
 }
 
-impl From<TomlRelayerConfig> for RelayerConfig {
-    fn from(input: TomlRelayerConfig) -> Self {
-        RelayerConfig {
-            valset_relaying_mode: input.valset_relaying_mode.into(),
-            batch_relaying_mode: input.batch_relaying_mode.into(),
+impl TryFrom<TomlRelayerConfig> for RelayerConfig {
+    type Error = GravityError;
+
+    fn try_from(input: TomlRelayerConfig) -> Result<Self, GravityError> {
+        if input.relayer_loop_speed == 0 {
+            return Err(GravityError::ValidationError(
+                "Invalid relayer.relayer_loop_speed 0, the loop speed must be at least 1 second"
+                    .into(),
+            ));
+        }
+        Ok(RelayerConfig {
+            valset_relaying_mode: input.valset_relaying_mode.try_into()?,
+            batch_relaying_mode: input.batch_relaying_mode.try_into()?,
             batch_request_mode: input.batch_request_mode,
             logic_call_market_enabled: input.logic_call_market_enabled,
             relayer_loop_speed: input.relayer_loop_speed,
-        }
+        })
     }
 }
 
@@ -93,17 +229,23 @@ pub struct TomlValsetRelayingMode {
     margin: Option<f64>,
 }
 
-impl From<TomlValsetRelayingMode> for ValsetRelayingMode {
-    fn from(input: TomlValsetRelayingMode) -> Self {
+/// The accepted values of `relayer.valset_relaying_mode.mode`
+pub const VALSET_RELAYING_MODES: &[&str] = &["ProfitableOnly", "Altruistic", "EveryValset"];
+
+impl TryFrom<TomlValsetRelayingMode> for ValsetRelayingMode {
+    type Error = GravityError;
+
+    fn try_from(input: TomlValsetRelayingMode) -> Result<Self, GravityError> {
+        const FIELD: &str = "relayer.valset_relaying_mode";
         match input.mode.as_str() {
             "ProfitableOnly" | "profitableonly" | "PROFITABLEONLY" => {
-                ValsetRelayingMode::ProfitableOnly {
-                    margin: input.margin.unwrap(),
-                }
+                Ok(ValsetRelayingMode::ProfitableOnly {
+                    margin: require_margin(FIELD, &input.mode, input.margin)?,
+                })
             }
-            "Altruistic" | "altruistic" | "ALTRUISTIC" => ValsetRelayingMode::Altruistic,
-            "EveryValset" | "everyvalset" | "EVERYVALSET" => ValsetRelayingMode::EveryValset,
-            _ => panic!("Invalid TomlValsetRelayingMode"),
+            "Altruistic" | "altruistic" | "ALTRUISTIC" => Ok(ValsetRelayingMode::Altruistic),
+            "EveryValset" | "everyvalset" | "EVERYVALSET" => Ok(ValsetRelayingMode::EveryValset),
+            _ => Err(invalid_mode(FIELD, &input.mode, VALSET_RELAYING_MODES)),
         }
     }
 }
@@ -159,26 +301,59 @@ pub struct TomlBatchRelayingMode {
     whitelist: Option<Vec<WhitelistToken>>,
 }
 
-impl From<TomlBatchRelayingMode> for BatchRelayingMode {
-    fn from(input: TomlBatchRelayingMode) -> Self {
+/// The accepted values of `relayer.batch_relaying_mode.mode`
+pub const BATCH_RELAYING_MODES: &[&str] =
+    &["EveryBatch", "ProfitableOnly", "ProfitableWithWhitelist"];
+
+impl TryFrom<TomlBatchRelayingMode> for BatchRelayingMode {
+    type Error = GravityError;
+
+    fn try_from(input: TomlBatchRelayingMode) -> Result<Self, GravityError> {
+        const FIELD: &str = "relayer.batch_relaying_mode";
         match input.mode.as_str() {
-            "EveryBatch" | "everybatch" | "EVERYBATCH" => BatchRelayingMode::EveryBatch,
+            "EveryBatch" | "everybatch" | "EVERYBATCH" => Ok(BatchRelayingMode::EveryBatch),
             "ProfitableOnly" | "profitableonly" | "PROFITABLEONLY" => {
-                BatchRelayingMode::ProfitableOnly {
-                    margin: input.margin.unwrap(),
-                }
+                Ok(BatchRelayingMode::ProfitableOnly {
+                    margin: require_margin(FIELD, &input.mode, input.margin)?,
+                })
             }
             "ProfitableWithWhitelist" | "profitablewithwhitelist" | "PROFITABLEWITHWHITELIST" => {
-                BatchRelayingMode::ProfitableWithWhitelist {
-                    margin: input.margin.unwrap(),
-                    whitelist: input.whitelist.unwrap(),
+                let margin = require_margin(FIELD, &input.mode, input.margin)?;
+                match input.whitelist {
+                    Some(whitelist) => {
+                        Ok(BatchRelayingMode::ProfitableWithWhitelist { margin, whitelist })
+                    }
+                    None => Err(GravityError::ValidationError(format!(
+                        "Missing {FIELD}.whitelist, it is required when mode is \"{}\"",
+                        input.mode
+                    ))),
                 }
             }
-            _ => panic!("Bad TomlBatchRelayingMode"),
+            _ => Err(invalid_mode(FIELD, &input.mode, BATCH_RELAYING_MODES)),
         }
     }
 }
 
+/// Checks that a relaying mode which needs a margin has one and that it is usable
+fn require_margin(field: &str, mode: &str, margin: Option<f64>) -> Result<f64, GravityError> {
+    match margin {
+        Some(margin) if margin.is_finite() && margin >= 0.0 => Ok(margin),
+        Some(margin) => Err(GravityError::ValidationError(format!(
+            "Invalid {field}.margin {margin}, it must be a non negative number"
+        ))),
+        None => Err(GravityError::ValidationError(format!(
+            "Missing {field}.margin, it is required when mode is \"{mode}\""
+        ))),
+    }
+}
+
+fn invalid_mode(field: &str, mode: &str, allowed: &[&str]) -> GravityError {
+    GravityError::ValidationError(format!(
+        "Invalid {field}.mode \"{mode}\", allowed values are {}",
+        allowed.join(", ")
+    ))
+}
+
 fn default_batch_relaying_mode() -> TomlBatchRelayingMode {
     TomlBatchRelayingMode {
         mode: "ProfitableOnly".to_string(),
@@ -209,9 +384,13 @@ fn default_relayer_loop_speed() -> u64 {
 impl Default for RelayerConfig {
     fn default() -> Self {
         RelayerConfig {
-            valset_relaying_mode: default_valset_relaying_mode().into(),
+            valset_relaying_mode: default_valset_relaying_mode()
+                .try_into()
+                .expect("Invalid default valset relaying mode"),
             batch_request_mode: default_batch_request_mode(),
-            batch_relaying_mode: default_batch_relaying_mode().into(),
+            batch_relaying_mode: default_batch_relaying_mode()
+                .try_into()
+                .expect("Invalid default batch relaying mode"),
             logic_call_market_enabled: default_logic_call_market_enabled(),
             relayer_loop_speed: default_relayer_loop_speed(),
         }
@@ -253,12 +432,28 @@ impl Default for OrchestratorConfig {
 /// Metrics server configuration options
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct MetricsConfig {
-    /// If this Orchestrator should run an integrated metrics server or not
-    #[serde(default = "default_metrics_enabled")]
-    pub metrics_enabled: bool,
-    /// Bind to specified ip:port
-    #[serde(default = "default_metrics_bind")]
-    pub metrics_bind: String,
+    /// If this Orchestrator should run an integrated metrics server or not, `metrics_enabled`
+    /// is still accepted as the old name
+    #[serde(default = "default_metrics_enabled", alias = "metrics_enabled")]
+    pub enabled: bool,
+    /// Bind to specified ip:port, `metrics_bind` is still accepted as the old name
+    #[serde(default = "default_metrics_bind", alias = "metrics_bind")]
+    pub bind: String,
+    /// /readyz fails when a loop has not completed an iteration within this many loop periods
+    #[serde(default = "default_readiness_loop_periods")]
+    pub readiness_loop_periods: u32,
+    /// /healthz fails when a loop has not completed an iteration within this many loop
+    /// periods, meaning it has stopped making progress
+    #[serde(default = "default_liveness_loop_periods")]
+    pub liveness_loop_periods: u32,
+    /// ETH balance in wei below which /healthz reports the balance as too low and the
+    /// watchdog alerts
+    #[serde(default = "default_min_balance")]
+    pub min_eth_balance: Uint256,
+    /// Fee balance in the smallest unit of the fee denom below which /healthz reports
+    /// the balance as too low and the watchdog alerts
+    #[serde(default = "default_min_balance")]
+    pub min_fee_balance: Uint256,
 }
 
 fn default_metrics_enabled() -> bool {
@@ -269,11 +464,135 @@ fn default_metrics_bind() -> String {
     "127.0.0.1:6631".to_string()
 }
 
+fn default_readiness_loop_periods() -> u32 {
+    3
+}
+
+fn default_liveness_loop_periods() -> u32 {
+    10
+}
+
+fn default_min_balance() -> Uint256 {
+    u256!(0)
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         MetricsConfig {
-            metrics_enabled: default_metrics_enabled(),
-            metrics_bind: default_metrics_bind(),
+            enabled: default_metrics_enabled(),
+            bind: default_metrics_bind(),
+            readiness_loop_periods: default_readiness_loop_periods(),
+            liveness_loop_periods: default_liveness_loop_periods(),
+            min_eth_balance: default_min_balance(),
+            min_fee_balance: default_min_balance(),
+        }
+    }
+}
+
+/// Orchestrator watchdog configuration, the watchdog periodically checks balances and
+/// the signing backlog and sends alerts before funds run out or slashing happens. The balance
+/// thresholds are shared with /healthz and set in `MetricsConfig`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct WatchdogConfig {
+    /// If the Orchestrator should run the watchdog or not
+    #[serde(default = "default_watchdog_enabled")]
+    pub enabled: bool,
+    /// Seconds between checks
+    #[serde(default = "default_watchdog_interval")]
+    pub interval: u64,
+    /// Alert when the oldest unsigned valset, batch or logic call is older than this
+    /// many Cosmos blocks
+    #[serde(default = "default_max_unsigned_age")]
+    pub max_unsigned_age: u64,
+    /// Alert when an unsigned valset, batch or logic call is less than this many Cosmos
+    /// blocks away from the end of its slashing window
+    #[serde(default = "default_slashing_margin")]
+    pub slashing_margin: u64,
+    /// Seconds before an alert that is still active is sent again
+    #[serde(default = "default_alert_repeat_interval")]
+    pub repeat_interval: u64,
+    /// URL that alerts are POSTed to as JSON
+    #[serde(default)]
+    pub webhook: Option<String>,
+    /// Command run with `sh -c` for every alert, the alert JSON is in `GBT_ALERT`
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+fn default_watchdog_enabled() -> bool {
+    false
+}
+
+fn default_watchdog_interval() -> u64 {
+    60
+}
+
+fn default_max_unsigned_age() -> u64 {
+    100
+}
+
+fn default_slashing_margin() -> u64 {
+    1000
+}
+
+fn default_alert_repeat_interval() -> u64 {
+    3600
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        WatchdogConfig {
+            enabled: default_watchdog_enabled(),
+            interval: default_watchdog_interval(),
+            max_unsigned_age: default_max_unsigned_age(),
+            slashing_margin: default_slashing_margin(),
+            repeat_interval: default_alert_repeat_interval(),
+            webhook: None,
+            command: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_relaying_modes() {
+        let res = ValsetRelayingMode::try_from(TomlValsetRelayingMode {
+            mode: "Profitable".to_string(),
+            margin: Some(1.5),
+        });
+        match res {
+            Err(GravityError::ValidationError(e)) => {
+                assert!(e.contains("relayer.valset_relaying_mode.mode"));
+                assert!(e.contains("ProfitableOnly, Altruistic, EveryValset"));
+            }
+            _ => panic!("Expected a validation error, got {:?}", res),
+        }
+
+        let res = BatchRelayingMode::try_from(TomlBatchRelayingMode {
+            mode: "ProfitableOnly".to_string(),
+            margin: None,
+            whitelist: None,
+        });
+        match res {
+            Err(GravityError::ValidationError(e)) => {
+                assert!(e.contains("relayer.batch_relaying_mode.margin"))
+            }
+            _ => panic!("Expected a validation error, got {:?}", res),
+        }
+
+        let res = BatchRelayingMode::try_from(TomlBatchRelayingMode {
+            mode: "ProfitableWithWhitelist".to_string(),
+            margin: Some(1.1),
+            whitelist: None,
+        });
+        match res {
+            Err(GravityError::ValidationError(e)) => {
+                assert!(e.contains("relayer.batch_relaying_mode.whitelist"))
+            }
+            _ => panic!("Expected a validation error, got {:?}", res),
         }
     }
 }
@@ -7,8 +7,6 @@
 // TODO this file needs static assertions that prevent it from compiling on 16 bit systems.
 // we assume a system bit width of at least 32
 
-use std::unimplemented;
-
 use clarity::{constants::ZERO_ADDRESS, Address as EthAddress, Uint256};
 use deep_space::{utils::bytes_to_hex_str, Address as CosmosAddress};
 use serde::{Deserialize, Serialize};
@@ -24,6 +22,18 @@ const U32_MAX: Uint256 = Uint256::from_u32(u32::MAX);
 const U64_MAX: Uint256 = Uint256::from_u64(u64::MAX);
 const USIZE_MAX: Uint256 = Uint256::from_u128(usize::MAX as u128);
 
+/// Checks that a log topic is a 32 byte word, topics come from the Ethereum node and a
+/// malformed one must not panic the oracle
+fn topic_word(topic: &[u8]) -> Result<&[u8], GravityError> {
+    if topic.len() != 32 {
+        return Err(GravityError::ValidationError(format!(
+            "Topic is {} bytes instead of 32",
+            topic.len()
+        )));
+    }
+    Ok(topic)
+}
+
 /// A parsed struct representing the Ethereum event fired by the Gravity contract
 /// when the validator set is updated.
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
@@ -111,6 +121,12 @@ impl ValsetUpdatedEvent {
             ));
         }
         let len_eth_addresses: usize = len_eth_addresses.to_string().parse().unwrap();
+        // each address takes a word, so a length that can not fit in the input is invalid
+        if len_eth_addresses > input.len() / 32 {
+            return Err(GravityError::ValidationError(
+                "too short for dynamic data".to_string(),
+            ));
+        }
         let index_start = (6 + len_eth_addresses) * 32;
         let index_end = index_start + 32;
         let powers_offset = index_end;
@@ -126,7 +142,7 @@ impl ValsetUpdatedEvent {
                 "Powers array len overflow, probably incorrect parsing".to_string(),
             ));
         }
-        let len_powers: usize = len_eth_addresses.to_string().parse().unwrap();
+        let len_powers: usize = len_powers.to_string().parse().unwrap();
         if len_powers != len_eth_addresses {
             return Err(GravityError::ValidationError(
                 "Array len mismatch, probably incorrect parsing".to_string(),
@@ -190,7 +206,7 @@ impl ValsetUpdatedEvent {
         if input.topics.get(1).is_none() {
             return Err(GravityError::ValidationError("Too few topics".to_string()));
         }
-        let valset_nonce_data = &input.topics[1];
+        let valset_nonce_data = topic_word(&input.topics[1])?;
         let valset_nonce = Uint256::from_bytes_be(valset_nonce_data).unwrap();
         if valset_nonce > U64_MAX {
             return Err(GravityError::ValidationError(
@@ -269,9 +285,14 @@ impl TransactionBatchExecutedEvent {
         if let (Some(batch_nonce_data), Some(erc20_data)) =
             (input.topics.get(1), input.topics.get(2))
         {
-            let batch_nonce = Uint256::from_bytes_be(batch_nonce_data).unwrap();
-            let erc20 = EthAddress::from_slice(&erc20_data[12..32])?;
-            let event_nonce = Uint256::from_bytes_be(&input.data).unwrap();
+            let batch_nonce = Uint256::from_bytes_be(topic_word(batch_nonce_data)?).unwrap();
+            let erc20 = EthAddress::from_slice(&topic_word(erc20_data)?[12..32])?;
+            if input.data.len() < 32 {
+                return Err(GravityError::ValidationError(
+                    "too short for TransactionBatchExecutedEvent".to_string(),
+                ));
+            }
+            let event_nonce = Uint256::from_bytes_be(&input.data[..32]).unwrap();
             let block_height = if let Some(bn) = input.block_number {
                 if bn > U64_MAX {
                     return Err(GravityError::ValidationError(
@@ -365,8 +386,8 @@ impl SendToCosmosEvent {
     pub fn from_log(input: &Log) -> Result<SendToCosmosEvent, GravityError> {
         let topics = (input.topics.get(1), input.topics.get(2));
         if let (Some(erc20_data), Some(sender_data)) = topics {
-            let erc20 = EthAddress::from_slice(&erc20_data[12..32])?;
-            let sender = EthAddress::from_slice(&sender_data[12..32])?;
+            let erc20 = EthAddress::from_slice(&topic_word(erc20_data)?[12..32])?;
+            let sender = EthAddress::from_slice(&topic_word(sender_data)?[12..32])?;
             let block_height = if let Some(bn) = input.block_number {
                 if bn > U64_MAX {
                     return Err(GravityError::ValidationError(
@@ -540,7 +561,7 @@ impl Erc20DeployedEvent {
     pub fn from_log(input: &Log) -> Result<Erc20DeployedEvent, GravityError> {
         let token_contract = input.topics.get(1);
         if let Some(new_token_contract_data) = token_contract {
-            let erc20 = EthAddress::from_slice(&new_token_contract_data[12..32])?;
+            let erc20 = EthAddress::from_slice(&topic_word(new_token_contract_data)?[12..32])?;
 
             let block_height = if let Some(bn) = input.block_number {
                 if bn > U64_MAX {
@@ -613,6 +634,11 @@ impl Erc20DeployedEvent {
         let denom_len: usize = denom_len.to_string().parse().unwrap();
         let index_start = 6 * 32;
         let index_end = index_start + denom_len;
+        if data.len() < index_end {
+            return Err(GravityError::ValidationError(
+                "Erc20DeployedEvent dynamic data too short".to_string(),
+            ));
+        }
         let denom = String::from_utf8(data[index_start..index_end].to_vec());
         trace!("Denom {:?}", denom);
         if denom.is_err() {
@@ -782,8 +808,8 @@ impl Erc20DeployedEvent {
         ret
     }
 }
-/// A parsed struct representing the Ethereum event fired when someone uses the Gravity
-/// contract to deploy a new ERC20 contract representing a Cosmos asset
+/// A parsed struct representing the Ethereum event fired by the Gravity contract when
+/// a logic call is executed
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct LogicCallExecutedEvent {
     pub invalidation_id: Vec<u8>,
@@ -793,9 +819,85 @@ pub struct LogicCallExecutedEvent {
     pub block_height: Uint256,
 }
 
+/// struct for holding the data encoded fields
+/// of a LogicCallExecutedEvent for unit testing
+#[derive(Eq, PartialEq, Debug)]
+struct LogicCallExecutedEventData {
+    pub invalidation_id: Vec<u8>,
+    pub invalidation_nonce: u64,
+    pub return_data: Vec<u8>,
+    pub event_nonce: u64,
+}
+
 impl LogicCallExecutedEvent {
-    pub fn from_log(_input: &Log) -> Result<LogicCallExecutedEvent, GravityError> {
-        unimplemented!()
+    /// The LogicCallEvent has no indexed fields so everything is parsed from the data bytes
+    pub fn from_log(input: &Log) -> Result<LogicCallExecutedEvent, GravityError> {
+        let block_height = if let Some(bn) = input.block_number {
+            if bn > U64_MAX {
+                return Err(GravityError::ValidationError(
+                    "Block height overflow! probably incorrect parsing".to_string(),
+                ));
+            } else {
+                bn
+            }
+        } else {
+            return Err(GravityError::ValidationError(
+                "Log does not have block number, we only search logs already in blocks?"
+                    .to_string(),
+            ));
+        };
+
+        let data = LogicCallExecutedEvent::decode_data_bytes(&input.data)?;
+        Ok(LogicCallExecutedEvent {
+            invalidation_id: data.invalidation_id,
+            invalidation_nonce: data.invalidation_nonce,
+            return_data: data.return_data,
+            event_nonce: data.event_nonce,
+            block_height,
+        })
+    }
+    fn decode_data_bytes(data: &[u8]) -> Result<LogicCallExecutedEventData, GravityError> {
+        // the invalidation id, invalidation nonce, offset of the return data, event nonce and
+        // the return data length
+        if data.len() < 5 * 32 {
+            return Err(GravityError::ValidationError(
+                "too short for LogicCallExecutedEventData".to_string(),
+            ));
+        }
+
+        let invalidation_id = data[0..32].to_vec();
+        let invalidation_nonce = Uint256::from_bytes_be(&data[32..64]).unwrap();
+        let event_nonce = Uint256::from_bytes_be(&data[96..128]).unwrap();
+        if invalidation_nonce > U64_MAX || event_nonce > U64_MAX {
+            return Err(GravityError::ValidationError(
+                "Nonce overflow, probably incorrect parsing".to_string(),
+            ));
+        }
+        let invalidation_nonce: u64 = invalidation_nonce.to_string().parse().unwrap();
+        let event_nonce: u64 = event_nonce.to_string().parse().unwrap();
+
+        let return_data_len = Uint256::from_bytes_be(&data[128..160]).unwrap();
+        // it's not probable that we have 4+ gigabytes of event data
+        if return_data_len > U32_MAX {
+            return Err(GravityError::ValidationError(
+                "Return data length overflow, probably incorrect parsing".to_string(),
+            ));
+        }
+        let return_data_len: usize = return_data_len.to_string().parse().unwrap();
+        let index_start = 5 * 32;
+        let index_end = index_start + return_data_len;
+        if data.len() < index_end {
+            return Err(GravityError::ValidationError(
+                "LogicCallExecutedEvent dynamic data too short".to_string(),
+            ));
+        }
+
+        Ok(LogicCallExecutedEventData {
+            invalidation_id,
+            invalidation_nonce,
+            return_data: data[index_start..index_end].to_vec(),
+            event_nonce,
+        })
     }
     pub fn from_logs(input: &[Log]) -> Result<Vec<LogicCallExecutedEvent>, GravityError> {
         let mut res = Vec::new();
@@ -831,7 +933,13 @@ fn _debug_print_data(input: &[u8]) {
 
 #[cfg(test)]
 mod tests {
-    use clarity::{u256, utils::hex_str_to_bytes};
+    use clarity::{
+        abi::{encode_tokens, Token},
+        u256,
+        utils::hex_str_to_bytes,
+    };
+    use ethabi::ParamType;
+    use proptest::{collection::vec, prelude::*};
     use rand::{
         distributions::{Distribution, Uniform},
         prelude::ThreadRng,
@@ -854,9 +962,8 @@ mod tests {
         event_bytes
     }
 
-    #[test]
-    fn test_valset_decode() {
-        let event = "0x0000000000000000000000000000000000000000000000000000000000000001\
+    /// a valset update with three members, the powers array length is the word at 288
+    const VALSET_EVENT: &str = "0x0000000000000000000000000000000000000000000000000000000000000001\
                           000000000000000000000000000000000000000000000000000000000000000000\
                           000000000000000000000000000000000000000000000000000000000000000000\
                           0000000000000000000000000000000000000000000000000000000000a0000000\
@@ -869,7 +976,10 @@ mod tests {
                           000000000000000000000000000000000000005555555500000000000000000000\
                           000000000000000000000000000000000000555555550000000000000000000000\
                           000000000000000000000000000000000055555555";
-        let event_bytes = hex_str_to_bytes(event).unwrap();
+
+    #[test]
+    fn test_valset_decode() {
+        let event_bytes = hex_str_to_bytes(VALSET_EVENT).unwrap();
 
         let correct = ValsetDataBytes {
             event_nonce: 1u8.into(),
@@ -920,6 +1030,39 @@ mod tests {
         assert_eq!(correct, res);
     }
 
+    #[test]
+    fn test_valset_len_mismatch() {
+        let mut event_bytes = hex_str_to_bytes(VALSET_EVENT).unwrap();
+        // claim two powers for three addresses
+        event_bytes[319] = 2;
+        assert!(ValsetUpdatedEvent::decode_data_bytes(&event_bytes).is_err());
+    }
+
+    #[test]
+    fn test_logic_call_decode() {
+        let event = "0x0303030303030303030303030303030303030303030303030303030303030303\
+        0000000000000000000000000000000000000000000000000000000000000008\
+        0000000000000000000000000000000000000000000000000000000000000080\
+        0000000000000000000000000000000000000000000000000000000000000009\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        0404000000000000000000000000000000000000000000000000000000000000";
+        let event_bytes = hex_str_to_bytes(event).unwrap();
+
+        let correct = LogicCallExecutedEventData {
+            invalidation_id: vec![3; 32],
+            invalidation_nonce: 8,
+            return_data: vec![4, 4],
+            event_nonce: 9,
+        };
+        let res = LogicCallExecutedEvent::decode_data_bytes(&event_bytes).unwrap();
+        assert_eq!(correct, res);
+
+        // a return data length that runs past the end of the event
+        let mut truncated = event_bytes;
+        truncated[159] = 33;
+        assert!(LogicCallExecutedEvent::decode_data_bytes(&truncated).is_err());
+    }
+
     #[test]
     fn fuzz_send_to_cosmos_decode() {
         let mut rng = thread_rng();
@@ -961,4 +1104,405 @@ mod tests {
             }
         }
     }
+
+    /// builds a log the way the Ethereum node returns it
+    fn log(topics: &[Vec<u8>], data: &[u8], block_number: Option<u64>) -> Log {
+        let topics: Vec<String> = topics
+            .iter()
+            .map(|t| format!("0x{}", bytes_to_hex_str(t)))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "address": "0x0000000000000000000000000000000000000000",
+            "topics": topics,
+            "data": format!("0x{}", bytes_to_hex_str(data)),
+            "blockNumber": block_number.map(|n| format!("{n:#x}")),
+            "blockHash": null,
+            "transactionHash": null,
+            "transactionIndex": null,
+            "logIndex": null,
+            "removed": false,
+        }))
+        .unwrap()
+    }
+
+    fn address_word(address: &[u8; 20]) -> Vec<u8> {
+        let mut word = vec![0u8; 12];
+        word.extend_from_slice(address);
+        word
+    }
+
+    fn u64_word(value: u64) -> Vec<u8> {
+        let mut word = vec![0u8; 24];
+        word.extend_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    fn address(bytes: &[u8; 20]) -> EthAddress {
+        EthAddress::from_slice(bytes).unwrap()
+    }
+
+    fn uint(bytes: &[u8; 32]) -> Uint256 {
+        Uint256::from_bytes_be(bytes).unwrap()
+    }
+
+    /// the event signature topic, the decoders don't look at it
+    fn signature() -> Vec<u8> {
+        vec![0u8; 32]
+    }
+
+    /// runs every decoder, the results don't matter as long as none of them panic
+    fn decode_all(log: &Log) {
+        let _ = ValsetUpdatedEvent::from_log(log);
+        let _ = TransactionBatchExecutedEvent::from_log(log);
+        let _ = SendToCosmosEvent::from_log(log);
+        let _ = Erc20DeployedEvent::from_log(log);
+        let _ = LogicCallExecutedEvent::from_log(log);
+    }
+
+    /// canonical topics and data for each event, used as a base for corruption
+    fn sample_events() -> Vec<(Vec<Vec<u8>>, Vec<u8>)> {
+        let member: EthAddress = "0x1bb537Aa56fFc7D608793BAFFC6c9C7De3c4F270"
+            .parse()
+            .unwrap();
+        vec![
+            (
+                vec![signature(), u64_word(2)],
+                encode_tokens(&[
+                    1u64.into(),
+                    u256!(0).into(),
+                    ZERO_ADDRESS.into(),
+                    vec![member, member].into(),
+                    vec![2u64, 1].into(),
+                ]),
+            ),
+            (
+                vec![signature(), u64_word(3), address_word(&[1; 20])],
+                encode_tokens(&[4u64.into()]),
+            ),
+            (
+                vec![signature(), address_word(&[1; 20]), address_word(&[2; 20])],
+                encode_tokens(&[
+                    Token::String("gravity1194za6yvg7djz3c6vqlcxzxwcjkaz9rdqs2eg9p".to_string()),
+                    u256!(100).into(),
+                    5u64.into(),
+                ]),
+            ),
+            (
+                vec![signature(), address_word(&[1; 20])],
+                encode_tokens(&[
+                    Token::String("footoken".to_string()),
+                    Token::String("Foo Token".to_string()),
+                    Token::String("FOO".to_string()),
+                    6u8.into(),
+                    7u64.into(),
+                ]),
+            ),
+            (
+                vec![signature()],
+                encode_tokens(&[
+                    Token::Bytes(vec![3; 32]),
+                    8u64.into(),
+                    Token::UnboundedBytes(vec![4; 40]),
+                    9u64.into(),
+                ]),
+            ),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+        #[test]
+        fn prop_from_log_never_panics(
+            topics in vec(vec(any::<u8>(), 0..40), 0..4),
+            data in vec(any::<u8>(), 0..2048),
+            block_number in any::<Option<u64>>(),
+        ) {
+            decode_all(&log(&topics, &data, block_number));
+        }
+
+        #[test]
+        fn prop_corrupted_events_never_panic(
+            event in 0usize..5,
+            index in 0usize..16,
+            word in any::<[u8; 32]>(),
+            len in 0usize..1024,
+        ) {
+            let (topics, mut data) = sample_events().swap_remove(event);
+            if let Some(target) = data.get_mut(index * 32..(index + 1) * 32) {
+                target.copy_from_slice(&word);
+            }
+            data.truncate(len);
+            decode_all(&log(&topics, &data, Some(1)));
+        }
+
+        #[test]
+        fn prop_valset_updated_roundtrip(
+            valset_nonce in any::<u64>(),
+            event_nonce in any::<u64>(),
+            reward_amount in any::<[u8; 32]>(),
+            reward_token in any::<Option<[u8; 20]>>(),
+            members in vec((any::<[u8; 20]>(), any::<u64>()), 0..10),
+            block_number in any::<u64>(),
+        ) {
+            let reward_amount = uint(&reward_amount);
+            let reward_token = reward_token.filter(|t| *t != [0; 20]).map(|t| address(&t));
+            let addresses: Vec<EthAddress> = members.iter().map(|(a, _)| address(a)).collect();
+            let powers: Vec<u64> = members.iter().map(|(_, p)| *p).collect();
+            let encode =
+                |event_nonce: u64, reward_amount: Uint256, reward_token: Option<EthAddress>| {
+                    encode_tokens(&[
+                        event_nonce.into(),
+                        reward_amount.into(),
+                        reward_token.unwrap_or(ZERO_ADDRESS).into(),
+                        addresses.clone().into(),
+                        powers.clone().into(),
+                    ])
+                };
+            let data = encode(event_nonce, reward_amount, reward_token);
+
+            let event = ValsetUpdatedEvent::from_log(&log(
+                &[signature(), u64_word(valset_nonce)],
+                &data,
+                Some(block_number),
+            ))
+            .unwrap();
+            prop_assert_eq!(event.valset_nonce, valset_nonce);
+            prop_assert_eq!(event.event_nonce, event_nonce);
+            prop_assert_eq!(event.reward_amount, reward_amount);
+            prop_assert_eq!(event.reward_token, reward_token);
+            prop_assert_eq!(event.block_height, Uint256::from_u64(block_number));
+            let decoded: Vec<(EthAddress, u64)> =
+                event.members.iter().map(|m| (m.eth_address, m.power)).collect();
+            let expected: Vec<(EthAddress, u64)> =
+                addresses.iter().copied().zip(powers.iter().copied()).collect();
+            prop_assert_eq!(decoded, expected);
+            prop_assert_eq!(
+                encode(event.event_nonce, event.reward_amount, event.reward_token),
+                data.clone()
+            );
+
+            let reference = ethabi::decode(
+                &[
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Address,
+                    ParamType::Array(Box::new(ParamType::Address)),
+                    ParamType::Array(Box::new(ParamType::Uint(256))),
+                ],
+                &data,
+            )
+            .unwrap();
+            prop_assert_eq!(reference[0].clone().into_uint().unwrap().as_u64(), event.event_nonce);
+            prop_assert_eq!(
+                reference[1].clone().into_uint().unwrap().to_string(),
+                event.reward_amount.to_string()
+            );
+            let reference_members: Vec<[u8; 20]> = reference[3]
+                .clone()
+                .into_array()
+                .unwrap()
+                .into_iter()
+                .map(|a| a.into_address().unwrap().to_fixed_bytes())
+                .collect();
+            let members_bytes: Vec<[u8; 20]> = members.iter().map(|(a, _)| *a).collect();
+            prop_assert_eq!(reference_members, members_bytes);
+            let reference_powers: Vec<u64> = reference[4]
+                .clone()
+                .into_array()
+                .unwrap()
+                .into_iter()
+                .map(|p| p.into_uint().unwrap().as_u64())
+                .collect();
+            prop_assert_eq!(reference_powers, powers.clone());
+        }
+
+        #[test]
+        fn prop_transaction_batch_executed_roundtrip(
+            batch_nonce in any::<u64>(),
+            erc20 in any::<[u8; 20]>(),
+            event_nonce in any::<u64>(),
+            block_number in any::<u64>(),
+        ) {
+            let data = encode_tokens(&[event_nonce.into()]);
+            let event = TransactionBatchExecutedEvent::from_log(&log(
+                &[signature(), u64_word(batch_nonce), address_word(&erc20)],
+                &data,
+                Some(block_number),
+            ))
+            .unwrap();
+            prop_assert_eq!(event.batch_nonce, batch_nonce);
+            prop_assert_eq!(event.erc20, address(&erc20));
+            prop_assert_eq!(event.event_nonce, event_nonce);
+            prop_assert_eq!(event.block_height, Uint256::from_u64(block_number));
+            prop_assert_eq!(encode_tokens(&[event.event_nonce.into()]), data.clone());
+
+            let reference = ethabi::decode(&[ParamType::Uint(256)], &data).unwrap();
+            prop_assert_eq!(reference[0].clone().into_uint().unwrap().as_u64(), event.event_nonce);
+        }
+
+        #[test]
+        fn prop_send_to_cosmos_roundtrip(
+            erc20 in any::<[u8; 20]>(),
+            sender in any::<[u8; 20]>(),
+            destination in "\\PC{0,64}",
+            amount in any::<[u8; 32]>(),
+            event_nonce in any::<u64>(),
+            block_number in any::<u64>(),
+        ) {
+            // the decoder trims whitespace since it can't be part of a bech32 address
+            prop_assume!(destination.trim() == destination);
+            let amount = uint(&amount);
+            let encode = |destination: &str, amount: Uint256, event_nonce: u64| {
+                encode_tokens(&[
+                    Token::String(destination.to_string()),
+                    amount.into(),
+                    event_nonce.into(),
+                ])
+            };
+            let data = encode(&destination, amount, event_nonce);
+
+            let event = SendToCosmosEvent::from_log(&log(
+                &[signature(), address_word(&erc20), address_word(&sender)],
+                &data,
+                Some(block_number),
+            ))
+            .unwrap();
+            prop_assert_eq!(event.erc20, address(&erc20));
+            prop_assert_eq!(event.sender, address(&sender));
+            prop_assert_eq!(&event.destination, &destination);
+            prop_assert_eq!(event.amount, amount);
+            prop_assert_eq!(event.event_nonce, event_nonce);
+            prop_assert_eq!(event.block_height, Uint256::from_u64(block_number));
+            prop_assert_eq!(
+                encode(&event.destination, event.amount, event.event_nonce),
+                data.clone()
+            );
+
+            let reference = ethabi::decode(
+                &[ParamType::String, ParamType::Uint(256), ParamType::Uint(256)],
+                &data,
+            )
+            .unwrap();
+            prop_assert_eq!(reference[0].clone().into_string().unwrap(), event.destination);
+            prop_assert_eq!(
+                reference[1].clone().into_uint().unwrap().to_string(),
+                event.amount.to_string()
+            );
+            prop_assert_eq!(reference[2].clone().into_uint().unwrap().as_u64(), event.event_nonce);
+        }
+
+        #[test]
+        fn prop_erc20_deployed_roundtrip(
+            erc20 in any::<[u8; 20]>(),
+            cosmos_denom in "\\PC{0,64}",
+            name in "\\PC{0,64}",
+            symbol in "\\PC{0,16}",
+            decimals in any::<u8>(),
+            event_nonce in any::<u64>(),
+            block_number in any::<u64>(),
+        ) {
+            let encode = |event: &Erc20DeployedEvent| {
+                encode_tokens(&[
+                    Token::String(event.cosmos_denom.clone()),
+                    Token::String(event.name.clone()),
+                    Token::String(event.symbol.clone()),
+                    event.decimals.into(),
+                    event.event_nonce.into(),
+                ])
+            };
+            let expected = Erc20DeployedEvent {
+                cosmos_denom,
+                erc20_address: address(&erc20),
+                name,
+                symbol,
+                decimals,
+                event_nonce,
+                block_height: Uint256::from_u64(block_number),
+            };
+            let data = encode(&expected);
+
+            let event = Erc20DeployedEvent::from_log(&log(
+                &[signature(), address_word(&erc20)],
+                &data,
+                Some(block_number),
+            ))
+            .unwrap();
+            prop_assert_eq!(&event, &expected);
+            prop_assert_eq!(encode(&event), data.clone());
+
+            let reference = ethabi::decode(
+                &[
+                    ParamType::String,
+                    ParamType::String,
+                    ParamType::String,
+                    ParamType::Uint(8),
+                    ParamType::Uint(256),
+                ],
+                &data,
+            )
+            .unwrap();
+            prop_assert_eq!(reference[0].clone().into_string().unwrap(), event.cosmos_denom);
+            prop_assert_eq!(reference[1].clone().into_string().unwrap(), event.name);
+            prop_assert_eq!(reference[2].clone().into_string().unwrap(), event.symbol);
+            prop_assert_eq!(
+                reference[3].clone().into_uint().unwrap().as_u64(),
+                u64::from(event.decimals)
+            );
+            prop_assert_eq!(reference[4].clone().into_uint().unwrap().as_u64(), event.event_nonce);
+        }
+
+        #[test]
+        fn prop_logic_call_executed_roundtrip(
+            invalidation_id in any::<[u8; 32]>(),
+            invalidation_nonce in any::<u64>(),
+            return_data in vec(any::<u8>(), 0..256),
+            event_nonce in any::<u64>(),
+            block_number in any::<u64>(),
+        ) {
+            let encode = |event: &LogicCallExecutedEvent| {
+                encode_tokens(&[
+                    Token::Bytes(event.invalidation_id.clone()),
+                    event.invalidation_nonce.into(),
+                    Token::UnboundedBytes(event.return_data.clone()),
+                    event.event_nonce.into(),
+                ])
+            };
+            let expected = LogicCallExecutedEvent {
+                invalidation_id: invalidation_id.to_vec(),
+                invalidation_nonce,
+                return_data,
+                event_nonce,
+                block_height: Uint256::from_u64(block_number),
+            };
+            let data = encode(&expected);
+
+            let event =
+                LogicCallExecutedEvent::from_log(&log(&[signature()], &data, Some(block_number)))
+                    .unwrap();
+            prop_assert_eq!(&event, &expected);
+            prop_assert_eq!(encode(&event), data.clone());
+
+            let reference = ethabi::decode(
+                &[
+                    ParamType::FixedBytes(32),
+                    ParamType::Uint(256),
+                    ParamType::Bytes,
+                    ParamType::Uint(256),
+                ],
+                &data,
+            )
+            .unwrap();
+            prop_assert_eq!(
+                reference[0].clone().into_fixed_bytes().unwrap(),
+                event.invalidation_id
+            );
+            prop_assert_eq!(
+                reference[1].clone().into_uint().unwrap().as_u64(),
+                event.invalidation_nonce
+            );
+            prop_assert_eq!(reference[2].clone().into_bytes().unwrap(), event.return_data);
+            prop_assert_eq!(reference[3].clone().into_uint().unwrap().as_u64(), event.event_nonce);
+        }
+    }
 }
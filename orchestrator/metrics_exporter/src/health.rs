@@ -0,0 +1,238 @@
+//! Liveness and readiness of the orchestrator and relayer loops, served as JSON on the
+//! `/healthz` and `/readyz` paths of the metrics server
+
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use gravity_utils::clarity::Uint256;
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::{Chain, LoopKind};
+
+lazy_static! {
+    static ref HEALTH: Mutex<HealthState> = Mutex::new(HealthState::default());
+}
+
+/// The state of a node as last seen by a loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeStatus {
+    /// the node answered and its chain is producing blocks
+    Moving,
+    /// the node is catching up with its chain
+    Syncing,
+    /// the node is waiting for its chain to start
+    WaitingToStart,
+    /// the node could not be reached
+    Unreachable,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HealthState {
+    loops: BTreeMap<&'static str, LoopState>,
+    nodes: BTreeMap<&'static str, NodeStatus>,
+    balance_thresholds: BTreeMap<String, Uint256>,
+    balances: BTreeMap<String, Uint256>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LoopState {
+    period: Duration,
+    started: SystemTime,
+    last_success: Option<SystemTime>,
+}
+
+/// The JSON body of `/healthz` and `/readyz`
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// false when any loop has not completed within the readiness number of loop periods
+    pub ready: bool,
+    /// false when any loop has not completed within the liveness number of loop periods,
+    /// the process is running but no longer making progress
+    pub live: bool,
+    pub loops: BTreeMap<&'static str, LoopReport>,
+    pub nodes: BTreeMap<&'static str, NodeStatus>,
+    pub balances: BTreeMap<String, BalanceReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoopReport {
+    /// unix time of the last successful iteration, None if there has not been one
+    pub last_success: Option<u64>,
+    /// seconds since the last successful iteration, or since the loop started
+    pub seconds_since_success: u64,
+    pub period_seconds: u64,
+    /// completed within the readiness number of loop periods
+    pub healthy: bool,
+    /// completed within the liveness number of loop periods
+    pub live: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceReport {
+    pub balance: Uint256,
+    pub threshold: Uint256,
+    /// the balance is at least the threshold, the watchdog alerts on the same condition
+    pub above_threshold: bool,
+}
+
+/// Registers a loop so it is reported before its first iteration completes, the period
+/// is updated on every call since the relayer loop speed may be reloaded
+pub fn health_loop_started(kind: LoopKind, period: Duration) {
+    let mut state = HEALTH.lock().unwrap();
+    state
+        .loops
+        .entry(kind.as_str())
+        .and_modify(|l| l.period = period)
+        .or_insert(LoopState {
+            period,
+            started: SystemTime::now(),
+            last_success: None,
+        });
+}
+
+/// Records a successful iteration of a loop
+pub fn health_loop_completed(kind: LoopKind) {
+    let mut state = HEALTH.lock().unwrap();
+    if let Some(l) = state.loops.get_mut(kind.as_str()) {
+        l.last_success = Some(SystemTime::now());
+    }
+}
+
+pub fn health_node_status(chain: Chain, status: NodeStatus) {
+    HEALTH.lock().unwrap().nodes.insert(chain.as_str(), status);
+}
+
+/// Sets the balance `asset` must stay above in its smallest unit, balances are recorded by
+/// `metrics_balance`
+pub fn health_balance_threshold(asset: &str, threshold: Uint256) {
+    HEALTH
+        .lock()
+        .unwrap()
+        .balance_thresholds
+        .insert(asset.to_string(), threshold);
+}
+
+pub(crate) fn health_balance(asset: &str, balance: Uint256) {
+    HEALTH
+        .lock()
+        .unwrap()
+        .balances
+        .insert(asset.to_string(), balance);
+}
+
+/// Builds the current report, a loop is unhealthy when it has not completed an iteration
+/// within `readiness_loop_periods` of its period and no longer live when it has not
+/// completed one within `liveness_loop_periods`
+pub fn health_report(readiness_loop_periods: u32, liveness_loop_periods: u32) -> HealthReport {
+    let state = HEALTH.lock().unwrap().clone();
+    build_report(
+        &state,
+        SystemTime::now(),
+        readiness_loop_periods,
+        liveness_loop_periods,
+    )
+}
+
+fn build_report(
+    state: &HealthState,
+    now: SystemTime,
+    readiness_loop_periods: u32,
+    liveness_loop_periods: u32,
+) -> HealthReport {
+    let unix = |t: SystemTime| t.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let loops: BTreeMap<&'static str, LoopReport> = state
+        .loops
+        .iter()
+        .map(|(name, l)| {
+            let since = now
+                .duration_since(l.last_success.unwrap_or(l.started))
+                .unwrap_or_default();
+            let report = LoopReport {
+                last_success: l.last_success.map(unix),
+                seconds_since_success: since.as_secs(),
+                period_seconds: l.period.as_secs(),
+                healthy: since <= l.period * readiness_loop_periods,
+                live: since <= l.period * liveness_loop_periods,
+            };
+            (*name, report)
+        })
+        .collect();
+    let balances = state
+        .balance_thresholds
+        .iter()
+        .filter_map(|(asset, threshold)| {
+            let balance = *state.balances.get(asset)?;
+            let report = BalanceReport {
+                balance,
+                threshold: *threshold,
+                above_threshold: balance >= *threshold,
+            };
+            Some((asset.clone(), report))
+        })
+        .collect();
+    HealthReport {
+        ready: loops.values().all(|l| l.healthy),
+        live: loops.values().all(|l| l.live),
+        loops,
+        nodes: state.nodes.clone(),
+        balances,
+    }
+}
+
+#[test]
+fn test_health_report() {
+    let now = SystemTime::now();
+    let mut state = HealthState::default();
+    state.loops.insert(
+        LoopKind::EthOracle.as_str(),
+        LoopState {
+            period: Duration::from_secs(10),
+            started: now - Duration::from_secs(100),
+            last_success: Some(now - Duration::from_secs(25)),
+        },
+    );
+    state
+        .balance_thresholds
+        .insert("eth".to_string(), Uint256::from_u64(10));
+    state
+        .balances
+        .insert("eth".to_string(), Uint256::from_u64(5));
+    let report = build_report(&state, now, 3, 10);
+    assert!(report.ready);
+    assert!(report.live);
+    assert_eq!(report.loops["eth_oracle"].seconds_since_success, 25);
+    assert!(!report.balances["eth"].above_threshold);
+
+    // a balance at the threshold is enough, as it is for the watchdog
+    state
+        .balances
+        .insert("eth".to_string(), Uint256::from_u64(10));
+    let report = build_report(&state, now, 3, 10);
+    assert!(report.balances["eth"].above_threshold);
+
+    // a loop that never completed is measured from when it started
+    state.loops.insert(
+        LoopKind::EthSigner.as_str(),
+        LoopState {
+            period: Duration::from_secs(10),
+            started: now - Duration::from_secs(31),
+            last_success: None,
+        },
+    );
+    let report = build_report(&state, now, 3, 10);
+    assert!(!report.ready);
+    assert!(report.live);
+    assert!(report.loops["eth_oracle"].healthy);
+    assert!(!report.loops["eth_signer"].healthy);
+
+    // a loop that stopped making progress fails liveness as well
+    let report = build_report(&state, now + Duration::from_secs(70), 3, 10);
+    assert!(!report.live);
+    assert!(report.loops["eth_oracle"].live);
+    assert!(!report.loops["eth_signer"].live);
+}
@@ -1,78 +1,501 @@
-use std::net::SocketAddr;
+//! Prometheus metrics for the orchestrator and relayer. Every label value comes from one of the
+//! enums below so the set of time series is small and does not change when log messages do.
 
-use gravity_utils::types::MetricsConfig;
+mod health;
+
+use std::{future::Future, net::SocketAddr, thread, time::Instant};
+
+use gravity_utils::{
+    clarity::{Address as EthAddress, Uint256},
+    types::MetricsConfig,
+};
 use lazy_static::lazy_static;
 use prometheus_exporter::prometheus::{
-    register_int_counter, register_int_counter_vec, register_int_gauge_vec, IntCounter,
-    IntCounterVec, IntGaugeVec,
+    self, register_gauge_vec, register_histogram_vec, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Encoder, GaugeVec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    TextEncoder,
 };
+use tiny_http::{Header, Response, Server};
+
+pub use health::{
+    health_balance_threshold, health_loop_completed, health_loop_started, health_node_status,
+    health_report, HealthReport, NodeStatus,
+};
+
+/// Buckets for RPC latency in seconds, from a fast local node to a timed out request
+const RPC_LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+/// Buckets for loop iteration durations in seconds, loops sleep between 10 and 60 seconds
+const LOOP_DURATION_BUCKETS: &[f64] = &[
+    0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Buckets for the cost of a relay in ETH
+const RELAY_COST_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
 
 lazy_static! {
+    pub static ref ERRORS: IntCounterVec = register_int_counter_vec!(
+        "gravity_errors_total",
+        "Errors since startup, check the orchestrator logs for details",
+        &["subsystem", "kind"]
+    )
+    .unwrap();
+    pub static ref WARNINGS: IntCounterVec = register_int_counter_vec!(
+        "gravity_warnings_total",
+        "Warnings since startup, check the orchestrator logs for details",
+        &["subsystem", "kind"]
+    )
+    .unwrap();
+    pub static ref RPC_LATENCY: HistogramVec = register_histogram_vec!(
+        "gravity_rpc_latency_seconds",
+        "Latency of requests to the Ethereum and Cosmos nodes",
+        &["method", "endpoint"],
+        RPC_LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap();
+    pub static ref LOOP_DURATION: HistogramVec = register_histogram_vec!(
+        "gravity_loop_duration_seconds",
+        "Time taken by one iteration of a main loop, not counting the sleep between iterations",
+        &["loop"],
+        LOOP_DURATION_BUCKETS.to_vec()
+    )
+    .unwrap();
+    pub static ref LATEST_BLOCK: IntGaugeVec = register_int_gauge_vec!(
+        "gravity_latest_block",
+        "Latest block height seen on each chain",
+        &["chain"]
+    )
+    .unwrap();
+    pub static ref LAST_CHECKED_EVENT_NONCE: IntGauge = register_int_gauge!(
+        "gravity_oracle_last_checked_event_nonce",
+        "The last Ethereum event nonce the oracle has checked"
+    )
+    .unwrap();
+    pub static ref ORACLE_LAG_BLOCKS: IntGauge = register_int_gauge!(
+        "gravity_oracle_lag_blocks",
+        "Ethereum blocks between the latest block and the last block the oracle has checked"
+    )
+    .unwrap();
+    pub static ref ORACLE_LAG_NONCES: IntGauge = register_int_gauge!(
+        "gravity_oracle_lag_nonces",
+        "Events on the Gravity contract this validator has not yet submitted claims for"
+    )
+    .unwrap();
+    pub static ref BLOCKS_UNTIL_SLASHING: IntGauge = register_int_gauge!(
+        "gravity_blocks_until_slashing",
+        "The shortest signing window, the Eth signer must recover within this many blocks"
+    )
+    .unwrap();
+    pub static ref BALANCE: GaugeVec = register_gauge_vec!(
+        "gravity_balance",
+        "Balances of the orchestrator and relayer keys, ETH in ether and Cosmos tokens in their base denom",
+        &["asset"]
+    )
+    .unwrap();
+    pub static ref CONFIRMS_SUBMITTED: IntCounterVec = register_int_counter_vec!(
+        "gravity_confirms_submitted_total",
+        "Confirms submitted by the Eth signer",
+        &["kind"]
+    )
+    .unwrap();
+    pub static ref CLAIMS_SUBMITTED: IntCounterVec = register_int_counter_vec!(
+        "gravity_claims_submitted_total",
+        "Ethereum event claims submitted by the oracle",
+        &["kind"]
+    )
+    .unwrap();
+    pub static ref RELAYS: IntCounterVec = register_int_counter_vec!(
+        "gravity_relays_total",
+        "Relays to Ethereum by outcome",
+        &["kind", "outcome"]
+    )
+    .unwrap();
+    pub static ref RELAYER_PENDING: IntGaugeVec = register_int_gauge_vec!(
+        "gravity_relayer_pending",
+        "Signed valsets, batches and logic calls that can be relayed",
+        &["kind"]
+    )
+    .unwrap();
+    pub static ref RELAYER_COST: HistogramVec = register_histogram_vec!(
+        "gravity_relayer_cost_eth",
+        "Cost of relays in ETH as estimated before sending and as realized from the change in balance",
+        &["kind", "cost"],
+        RELAY_COST_BUCKETS.to_vec()
+    )
+    .unwrap();
+    pub static ref RELAYER_DECISIONS: IntCounterVec = register_int_counter_vec!(
+        "gravity_relayer_decisions_total",
+        "Whether the relayer chose to relay according to its profitability settings, per token for batches",
+        &["kind", "token", "decision"]
+    )
+    .unwrap();
+    pub static ref RELAYER_OLDEST_BATCH_AGE: IntGauge = register_int_gauge!(
+        "gravity_relayer_oldest_unrelayed_batch_age_blocks",
+        "Cosmos blocks since the oldest signed batch that has not been relayed was created"
+    )
+    .unwrap();
+}
+
+/// The part of the bridge an error or warning comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subsystem {
+    Ethereum,
+    Cosmos,
+    EthereumAndCosmos,
+    Unclassified,
+}
+
+impl Subsystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Subsystem::Ethereum => "ethereum",
+            Subsystem::Cosmos => "cosmos",
+            Subsystem::EthereumAndCosmos => "ethereum_and_cosmos",
+            Subsystem::Unclassified => "unclassified",
+        }
+    }
+}
+
+/// What went wrong, alerts should be written against these rather than log messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// a node could not be reached or returned an error
+    RpcUnreachable,
+    /// the Cosmos node is catching up
+    NodeSyncing,
+    /// the Cosmos node is waiting for the chain to start
+    ChainNotStarted,
+    /// Ethereum events could not be fetched
+    EventQuery,
+    /// an Ethereum event could not be parsed
+    EventParse,
+    /// the Gravity module parameters could not be fetched
+    ParamsQuery,
+    /// confirms could not be submitted, this risks slashing
+    ConfirmSubmit,
+    /// claims could not be submitted or were not processed
+    ClaimSubmit,
+    /// a relay to Ethereum failed
+    Relay,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::RpcUnreachable => "rpc_unreachable",
+            ErrorKind::NodeSyncing => "node_syncing",
+            ErrorKind::ChainNotStarted => "chain_not_started",
+            ErrorKind::EventQuery => "event_query",
+            ErrorKind::EventParse => "event_parse",
+            ErrorKind::ParamsQuery => "params_query",
+            ErrorKind::ConfirmSubmit => "confirm_submit",
+            ErrorKind::ClaimSubmit => "claim_submit",
+            ErrorKind::Relay => "relay",
+        }
+    }
+}
+
+/// The chain a request or block height belongs to. Node URLs are not used as labels since
+/// they often contain API keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    Ethereum,
+    Cosmos,
+}
+
+impl Chain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chain::Ethereum => "ethereum",
+            Chain::Cosmos => "cosmos",
+        }
+    }
+}
+
+/// The long running loops of the orchestrator and relayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopKind {
+    EthOracle,
+    EthSigner,
+    Relayer,
+}
+
+impl LoopKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoopKind::EthOracle => "eth_oracle",
+            LoopKind::EthSigner => "eth_signer",
+            LoopKind::Relayer => "relayer",
+        }
+    }
+}
+
+/// Something the bridge signs and relays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeObject {
+    Valset,
+    Batch,
+    LogicCall,
+}
+
+impl BridgeObject {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BridgeObject::Valset => "valset",
+            BridgeObject::Batch => "batch",
+            BridgeObject::LogicCall => "logic_call",
+        }
+    }
+}
+
+/// An Ethereum event the oracle submits a claim for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimKind {
+    SendToCosmos,
+    BatchExecuted,
+    Erc20Deployed,
+    LogicCallExecuted,
+    ValsetUpdated,
+}
+
+impl ClaimKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaimKind::SendToCosmos => "send_to_cosmos",
+            ClaimKind::BatchExecuted => "batch_executed",
+            ClaimKind::Erc20Deployed => "erc20_deployed",
+            ClaimKind::LogicCallExecuted => "logic_call_executed",
+            ClaimKind::ValsetUpdated => "valset_updated",
+        }
+    }
+}
+
+/// What happened to a relay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayOutcome {
+    /// the transaction was sent, relays that found the contract already updated or the batch
+    /// timed out never get this far
+    Attempted,
+    /// the transaction was included and updated the contract
+    Succeeded,
+    /// another relayer updated the contract first
+    LostRace,
+    /// the transaction could not be sent or failed
+    Failed,
+}
 
-    //  Errors
-    pub static ref ERRORS_TOTAL: IntCounter =
-        register_int_counter!("orchestrator_errors_count_total", "Total errors since startup").unwrap();
-    pub static ref ERROR: IntCounterVec =
-        register_int_counter_vec!("orchestrator_errors_count_cosmos_eth", "Both ETH & Cosmos related errors", &["error_message"]).unwrap();
-    pub static ref ERROR_ETH: IntCounterVec =
-        register_int_counter_vec!("orchestrator_errors_count_eth", "ETH related errors", &["error_message"]).unwrap();
-    pub static ref ERROR_COSMOS: IntCounterVec =
-        register_int_counter_vec!("orchestrator_errors_count_cosmos", "Cosmos related errors", &["error_message"]).unwrap();
-    pub static ref ERROR_UNCLASSIFIED: IntCounterVec =
-        register_int_counter_vec!("orchestrator_errors_count_unclassified", "Chech orchestrator logs for more details", &["error_message"]).unwrap();
-
-    // Warnings
-    pub static ref WARNINGS_TOTAL: IntCounter =
-        register_int_counter!("orchestrator_warnings_count_total", "Total warnings since startup").unwrap();
-    pub static ref WARNING: IntCounterVec =
-        register_int_counter_vec!("orchestrator_warnings_count_cosmos_eth", "Both ETH & Cosmos related warnings", &["warn_message"]).unwrap();
-    pub static ref WARNING_ETH: IntCounterVec =
-        register_int_counter_vec!("orchestrator_warnings_count_eth", "ETH related warnings", &["warn_message"]).unwrap();
-    pub static ref WARNING_COSMOS: IntCounterVec =
-        register_int_counter_vec!("orchestrator_warnings_count_cosmos", "Cosmos related warnings", &["warn_message"]).unwrap();
-    pub static ref WARNING_UNCLASSIFIED: IntCounterVec =
-        register_int_counter_vec!("orchestrator_warnings_count_unclassified", "Chech orchestrator logs for more details", &["warn_message"]).unwrap();
-
-    // Information gauges
-    pub static ref LATEST_INFO: IntGaugeVec =
-        register_int_gauge_vec!("orchestrator_information", "Latest orchestrator information", &["gauge"]).unwrap();
-}
-
-pub fn metrics_errors_counter(s: i32, e: &str) {
-    match s {
-        0 => ERROR.with_label_values(&[e]).inc(),
-        1 => ERROR_ETH.with_label_values(&[e]).inc(),
-        2 => ERROR_COSMOS.with_label_values(&[e]).inc(),
-        _ => ERROR_UNCLASSIFIED.with_label_values(&[e]).inc(),
+impl RelayOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelayOutcome::Attempted => "attempted",
+            RelayOutcome::Succeeded => "succeeded",
+            RelayOutcome::LostRace => "lost_race",
+            RelayOutcome::Failed => "failed",
+        }
     }
-    ERRORS_TOTAL.inc()
 }
 
-pub fn metrics_warnings_counter(s: i32, e: &str) {
-    match s {
-        0 => WARNING.with_label_values(&[e]).inc(),
-        1 => WARNING_ETH.with_label_values(&[e]).inc(),
-        2 => WARNING_COSMOS.with_label_values(&[e]).inc(),
-        _ => WARNING_UNCLASSIFIED.with_label_values(&[e]).inc(),
+/// Which cost of a relay is recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostKind {
+    /// the cost estimated before sending
+    Estimated,
+    /// the cost actually paid
+    Realized,
+}
+
+impl CostKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostKind::Estimated => "estimated",
+            CostKind::Realized => "realized",
+        }
     }
-    WARNINGS_TOTAL.inc()
 }
 
-pub fn metrics_latest(u: u64, e: &str) {
-    match i64::try_from(u).is_ok() {
-        true => {
-            LATEST_INFO.with_label_values(&[e]).set(u as i64);
+/// The outcome of the profitability check before a relay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayDecision {
+    Relay,
+    Skip,
+}
+
+impl RelayDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelayDecision::Relay => "relay",
+            RelayDecision::Skip => "skip",
         }
-        false => {}
     }
 }
 
+pub fn metrics_error(subsystem: Subsystem, kind: ErrorKind) {
+    ERRORS
+        .with_label_values(&[subsystem.as_str(), kind.as_str()])
+        .inc()
+}
+
+pub fn metrics_warning(subsystem: Subsystem, kind: ErrorKind) {
+    WARNINGS
+        .with_label_values(&[subsystem.as_str(), kind.as_str()])
+        .inc()
+}
+
+/// Runs an RPC request and records its latency, `method` should be the name of the
+/// RPC method or query eg: "eth_getLogs"
+pub async fn metrics_rpc<T>(
+    method: &'static str,
+    chain: Chain,
+    request: impl Future<Output = T>,
+) -> T {
+    let start = Instant::now();
+    let res = request.await;
+    RPC_LATENCY
+        .with_label_values(&[method, chain.as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    res
+}
+
+/// Runs one iteration of a loop and records how long it took
+pub async fn metrics_loop<T>(kind: LoopKind, iteration: impl Future<Output = T>) -> T {
+    let start = Instant::now();
+    let res = iteration.await;
+    LOOP_DURATION
+        .with_label_values(&[kind.as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    res
+}
+
+pub fn metrics_latest_block(chain: Chain, height: u64) {
+    LATEST_BLOCK
+        .with_label_values(&[chain.as_str()])
+        .set(to_gauge(height));
+}
+
+pub fn metrics_last_checked_event_nonce(nonce: u64) {
+    LAST_CHECKED_EVENT_NONCE.set(to_gauge(nonce));
+}
+
+pub fn metrics_oracle_lag(blocks: u64, nonces: u64) {
+    ORACLE_LAG_BLOCKS.set(to_gauge(blocks));
+    ORACLE_LAG_NONCES.set(to_gauge(nonces));
+}
+
+pub fn metrics_blocks_until_slashing(blocks: u64) {
+    BLOCKS_UNTIL_SLASHING.set(to_gauge(blocks));
+}
+
+/// Records a balance given in the smallest unit of `asset`, eg: wei with 18 decimals
+/// for "eth" or the fee denom with 0 decimals
+pub fn metrics_balance(asset: &str, amount: Uint256, decimals: u8) {
+    BALANCE
+        .with_label_values(&[asset])
+        .set(to_float(amount, decimals));
+    health::health_balance(asset, amount);
+}
+
+pub fn metrics_confirms_submitted(kind: BridgeObject, count: usize) {
+    CONFIRMS_SUBMITTED
+        .with_label_values(&[kind.as_str()])
+        .inc_by(count as u64);
+}
+
+pub fn metrics_claims_submitted(kind: ClaimKind, count: usize) {
+    CLAIMS_SUBMITTED
+        .with_label_values(&[kind.as_str()])
+        .inc_by(count as u64);
+}
+
+pub fn metrics_relay(kind: BridgeObject, outcome: RelayOutcome) {
+    RELAYS
+        .with_label_values(&[kind.as_str(), outcome.as_str()])
+        .inc();
+}
+
+pub fn metrics_relayer_pending(kind: BridgeObject, count: usize) {
+    RELAYER_PENDING
+        .with_label_values(&[kind.as_str()])
+        .set(count as i64);
+}
+
+/// Records the cost of a relay given in wei
+pub fn metrics_relay_cost(kind: BridgeObject, cost: CostKind, wei: Uint256) {
+    RELAYER_COST
+        .with_label_values(&[kind.as_str(), cost.as_str()])
+        .observe(to_float(wei, 18));
+}
+
+/// Records a profitability decision, `token` is the ERC20 a batch pays its fees in
+pub fn metrics_relay_decision(
+    kind: BridgeObject,
+    token: Option<EthAddress>,
+    decision: RelayDecision,
+) {
+    let token = token.map(|t| t.to_string()).unwrap_or_default();
+    RELAYER_DECISIONS
+        .with_label_values(&[kind.as_str(), &token, decision.as_str()])
+        .inc();
+}
+
+pub fn metrics_oldest_unrelayed_batch_age(blocks: u64) {
+    RELAYER_OLDEST_BATCH_AGE.set(to_gauge(blocks));
+}
+
+/// Converts an amount in the smallest unit to whole tokens, f64 loses precision on
+/// large amounts which does not matter for alerting
+fn to_float(amount: Uint256, decimals: u8) -> f64 {
+    let amount: f64 = amount.to_string().parse().unwrap_or(f64::MAX);
+    amount / 10f64.powi(decimals as i32)
+}
+
+/// Gauges are i64, values too large for one are clamped rather than wrapping
+fn to_gauge(v: u64) -> i64 {
+    i64::try_from(v).unwrap_or(i64::MAX)
+}
+
+/// Serves `/healthz` and `/readyz` as JSON, `/readyz` answers 503 when a loop has stalled and
+/// `/healthz` when a loop has stopped making progress, every other path serves the metrics
 pub fn metrics_server(config: &MetricsConfig) {
     // Parse address used to bind exporter to.
-    let addr_raw = &config.metrics_bind;
+    let addr_raw = &config.bind;
     let addr: SocketAddr = addr_raw.parse().expect("can not parse listen addr");
     // Start exporter
-    prometheus_exporter::start(addr).expect("can not start exporter");
+    let server = Server::http(addr).expect("can not start exporter");
+    let readiness_loop_periods = config.readiness_loop_periods;
+    let liveness_loop_periods = config.liveness_loop_periods;
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let path = request.url().split('?').next().unwrap_or_default();
+            let (status, content_type, body) = match path {
+                "/healthz" | "/readyz" => {
+                    let report = health_report(readiness_loop_periods, liveness_loop_periods);
+                    let healthy = match path {
+                        "/readyz" => report.ready,
+                        _ => report.live,
+                    };
+                    let status = if healthy { 200 } else { 503 };
+                    let body = serde_json::to_string(&report).unwrap();
+                    (status, "application/json".to_string(), body.into_bytes())
+                }
+                _ => {
+                    let encoder = TextEncoder::new();
+                    let mut body = Vec::new();
+                    encoder
+                        .encode(&prometheus::gather(), &mut body)
+                        .expect("can not encode metrics");
+                    (200, encoder.format_type().to_string(), body)
+                }
+            };
+            let header = Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
+                .expect("invalid content type");
+            let response = Response::from_data(body)
+                .with_status_code(status)
+                .with_header(header);
+            // the client hung up, there is nothing to do
+            let _ = request.respond(response);
+        }
+    });
 }
 
 /// Test overflowing bigint
@@ -80,4 +503,19 @@ pub fn metrics_server(config: &MetricsConfig) {
 fn test_overflow_big_integer() {
     let res = i64::try_from(18446744073709551615u64).is_err();
     assert!(res);
+    assert_eq!(to_gauge(18446744073709551615u64), i64::MAX);
+}
+
+#[test]
+fn test_labels_are_stable() {
+    metrics_error(Subsystem::Cosmos, ErrorKind::RpcUnreachable);
+    metrics_error(Subsystem::Cosmos, ErrorKind::RpcUnreachable);
+    assert_eq!(
+        ERRORS
+            .with_label_values(&["cosmos", "rpc_unreachable"])
+            .get(),
+        2
+    );
+    metrics_balance("eth", Uint256::from_u128(1_500_000_000_000_000_000), 18);
+    assert_eq!(BALANCE.with_label_values(&["eth"]).get(), 1.5);
 }
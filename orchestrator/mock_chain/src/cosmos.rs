@@ -0,0 +1,207 @@
+//! The Cosmos SDK services the orchestrator uses through deep_space: node status from the
+//! tendermint service, accounts from auth and broadcasting from the tx service. Transactions
+//! are not signature checked, their messages are applied to the chain state as soon as the
+//! fee is accepted
+
+use std::sync::{Arc, Mutex};
+
+use gravity_proto::cosmos_sdk_proto::{
+    cosmos::{
+        auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+        base::{
+            abci::v1beta1::{GasInfo, Result as AbciResult, TxResponse},
+            tendermint::v1beta1::{
+                GetLatestBlockRequest, GetLatestBlockResponse, GetNodeInfoRequest,
+                GetNodeInfoResponse, GetSyncingRequest, GetSyncingResponse,
+            },
+        },
+        tx::v1beta1::{
+            AuthInfo, BroadcastTxRequest, BroadcastTxResponse, GetTxRequest, GetTxResponse,
+            SimulateRequest, SimulateResponse, TxBody, TxRaw,
+        },
+    },
+    tendermint::{
+        p2p::DefaultNodeInfo,
+        types::{Block, Header},
+    },
+};
+use prost::Message;
+use prost_types::Any;
+use tonic::{Request, Response, Status};
+
+use crate::{
+    cosmos_services::{
+        auth::query_server::Query as AuthQuery,
+        tendermint::service_server::Service as TendermintService,
+        tx::service_server::Service as TxService,
+    },
+    state::{ChainState, INSUFFICIENT_FEE_CODE},
+};
+
+/// Gas reported by `Simulate` and used by every delivered transaction
+const MOCK_GAS_USED: i64 = 100_000;
+
+pub(crate) struct Tendermint(pub(crate) Arc<Mutex<ChainState>>);
+
+#[tonic::async_trait]
+impl TendermintService for Tendermint {
+    async fn get_node_info(
+        &self,
+        _request: Request<GetNodeInfoRequest>,
+    ) -> Result<Response<GetNodeInfoResponse>, Status> {
+        let chain_id = self.0.lock().unwrap().chain_id.clone();
+        Ok(Response::new(GetNodeInfoResponse {
+            default_node_info: Some(DefaultNodeInfo {
+                network: chain_id,
+                ..Default::default()
+            }),
+            application_version: None,
+        }))
+    }
+
+    async fn get_syncing(
+        &self,
+        _request: Request<GetSyncingRequest>,
+    ) -> Result<Response<GetSyncingResponse>, Status> {
+        Ok(Response::new(GetSyncingResponse {
+            syncing: self.0.lock().unwrap().syncing,
+        }))
+    }
+
+    async fn get_latest_block(
+        &self,
+        _request: Request<GetLatestBlockRequest>,
+    ) -> Result<Response<GetLatestBlockResponse>, Status> {
+        let state = self.0.lock().unwrap();
+        Ok(Response::new(GetLatestBlockResponse {
+            block_id: None,
+            block: Some(Block {
+                header: Some(Header {
+                    chain_id: state.chain_id.clone(),
+                    height: state.block_height as i64,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        }))
+    }
+}
+
+pub(crate) struct Auth(pub(crate) Arc<Mutex<ChainState>>);
+
+#[tonic::async_trait]
+impl AuthQuery for Auth {
+    /// Every address exists, the sequence is the number of accepted transactions since
+    /// signatures are not checked
+    async fn account(
+        &self,
+        request: Request<QueryAccountRequest>,
+    ) -> Result<Response<QueryAccountResponse>, Status> {
+        let sequence = self
+            .0
+            .lock()
+            .unwrap()
+            .txs
+            .iter()
+            .filter(|t| t.code == 0)
+            .count();
+        let account = BaseAccount {
+            address: request.into_inner().address,
+            pub_key: None,
+            account_number: 1,
+            sequence: sequence as u64,
+        };
+        Ok(Response::new(QueryAccountResponse {
+            account: Some(Any {
+                type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
+                value: account.encode_to_vec(),
+            }),
+        }))
+    }
+}
+
+pub(crate) struct Tx(pub(crate) Arc<Mutex<ChainState>>);
+
+#[tonic::async_trait]
+impl TxService for Tx {
+    async fn simulate(
+        &self,
+        _request: Request<SimulateRequest>,
+    ) -> Result<Response<SimulateResponse>, Status> {
+        Ok(Response::new(SimulateResponse {
+            gas_info: Some(GasInfo {
+                gas_wanted: MOCK_GAS_USED as u64,
+                gas_used: MOCK_GAS_USED as u64,
+            }),
+            result: Some(AbciResult::default()),
+        }))
+    }
+
+    async fn get_tx(
+        &self,
+        request: Request<GetTxRequest>,
+    ) -> Result<Response<GetTxResponse>, Status> {
+        let hash = request.into_inner().hash;
+        let state = self.0.lock().unwrap();
+        match state
+            .txs
+            .iter()
+            .find(|t| t.txhash.eq_ignore_ascii_case(&hash))
+        {
+            Some(tx) => Ok(Response::new(GetTxResponse {
+                tx: None,
+                tx_response: Some(tx.clone()),
+            })),
+            None => Err(Status::not_found(format!("tx {hash} not found"))),
+        }
+    }
+
+    /// Decodes the transaction, rejects it with the sdk insufficient fee error when the fee is
+    /// below `ChainState::min_fee` and otherwise delivers its messages in the current block
+    async fn broadcast_tx(
+        &self,
+        request: Request<BroadcastTxRequest>,
+    ) -> Result<Response<BroadcastTxResponse>, Status> {
+        let tx_bytes = request.into_inner().tx_bytes;
+        let raw = TxRaw::decode(&tx_bytes[..])
+            .map_err(|e| Status::invalid_argument(format!("Bad TxRaw {e:?}")))?;
+        let body = TxBody::decode(&raw.body_bytes[..])
+            .map_err(|e| Status::invalid_argument(format!("Bad TxBody {e:?}")))?;
+        let auth_info = AuthInfo::decode(&raw.auth_info_bytes[..])
+            .map_err(|e| Status::invalid_argument(format!("Bad AuthInfo {e:?}")))?;
+        let fee = auth_info.fee.map(|f| f.amount).unwrap_or_default();
+
+        let mut state = self.0.lock().unwrap();
+        let mut response = TxResponse {
+            height: state.block_height as i64,
+            // deterministic so that scenarios can refer to transactions by index
+            txhash: format!("{:064X}", state.txs.len() + 1),
+            ..Default::default()
+        };
+        if let Some(required) = state.check_fee(&fee) {
+            let got: Vec<String> = fee
+                .iter()
+                .map(|c| format!("{}{}", c.amount, c.denom))
+                .collect();
+            response.codespace = "sdk".to_string();
+            response.code = INSUFFICIENT_FEE_CODE;
+            response.raw_log = format!(
+                "insufficient fees; got: {} required: {}{}: insufficient fee",
+                got.join(","),
+                required.amount,
+                required.denom
+            );
+        } else if let Err(e) = state.deliver(body.messages) {
+            response.codespace = "sdk".to_string();
+            response.code = 2;
+            response.raw_log = e;
+        } else {
+            response.gas_wanted = MOCK_GAS_USED;
+            response.gas_used = MOCK_GAS_USED;
+        }
+        state.txs.push(response.clone());
+        Ok(Response::new(BroadcastTxResponse {
+            tx_response: Some(response),
+        }))
+    }
+}
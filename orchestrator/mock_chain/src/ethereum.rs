@@ -0,0 +1,259 @@
+//! A scripted Ethereum JSON-RPC node. Logs and `eth_call` results are set by the test,
+//! raw transactions are recorded instead of executed and every request is kept so that a
+//! scenario can assert on what the orchestrator asked for
+
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    io::{self, Read},
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+};
+
+use gravity_utils::{
+    clarity::utils::{bytes_to_hex_str, hex_str_to_bytes},
+    TEST_ETH_CHAIN_ID,
+};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use tiny_http::{Header, Response, Server};
+
+/// Returned by `eth_estimateGas` for every transaction
+const MOCK_GAS_ESTIMATE: u64 = 200_000;
+
+#[derive(Debug, Clone)]
+pub struct EthState {
+    pub block_number: u64,
+    pub gas_price: u64,
+    pub balance: u128,
+    /// returned by `eth_getLogs` when their first topic and block number match the filter
+    pub logs: Vec<Value>,
+    /// `eth_call` results by 4 byte method selector, unknown methods return a zero word
+    pub calls: HashMap<[u8; 4], Vec<u8>>,
+    /// the hex data of every `eth_sendRawTransaction`
+    pub sent_transactions: Vec<String>,
+    /// the method of every request in the order they were received
+    pub requests: Vec<String>,
+}
+
+impl Default for EthState {
+    fn default() -> Self {
+        EthState {
+            block_number: 1_000,
+            gas_price: 1_000_000_000,
+            balance: u64::MAX as u128,
+            logs: Vec::new(),
+            calls: HashMap::new(),
+            sent_transactions: Vec::new(),
+            requests: Vec::new(),
+        }
+    }
+}
+
+impl EthState {
+    /// Sets the result of `eth_call` for a method returning a single number, such as
+    /// `lastBatchNonce(address)`
+    pub fn set_call(&mut self, signature: &str, result: u64) {
+        let mut word = vec![0; 32];
+        word[24..].copy_from_slice(&result.to_be_bytes());
+        self.calls.insert(method_selector(signature), word);
+    }
+
+    /// Adds a log emitted by `address` in `block_number`
+    pub fn push_log(&mut self, address: &str, topics: Vec<String>, data: &[u8], block_number: u64) {
+        let log_index = self.logs.len();
+        self.logs.push(json!({
+            "removed": false,
+            "logIndex": hex_u64(log_index as u64),
+            "transactionIndex": "0x0",
+            "transactionHash": hex_word(log_index as u64),
+            "blockHash": hex_word(block_number),
+            "blockNumber": hex_u64(block_number),
+            "address": address,
+            "data": format!("0x{}", bytes_to_hex_str(data)),
+            "topics": topics,
+        }));
+    }
+
+    /// The number of requests made for `method`
+    pub fn request_count(&self, method: &str) -> usize {
+        self.requests.iter().filter(|m| *m == method).count()
+    }
+
+    fn handle(&mut self, method: &str, params: &Value) -> Result<Value, String> {
+        self.requests.push(method.to_string());
+        Ok(match method {
+            // the test chain id sets the block delay to zero
+            "net_version" => json!(TEST_ETH_CHAIN_ID.to_string()),
+            "eth_chainId" => json!(hex_u64(TEST_ETH_CHAIN_ID)),
+            "eth_syncing" => json!(false),
+            "eth_blockNumber" => json!(hex_u64(self.block_number)),
+            "eth_gasPrice" => json!(hex_u64(self.gas_price)),
+            "eth_estimateGas" => json!(hex_u64(MOCK_GAS_ESTIMATE)),
+            "eth_getBalance" => json!(format!("{:#x}", self.balance)),
+            "eth_getTransactionCount" => json!(hex_u64(self.sent_transactions.len() as u64)),
+            "eth_getBlockByNumber" => self.block(),
+            "eth_getLogs" => json!(self.logs(&params[0])),
+            "eth_call" => {
+                let data = params[0]["data"].as_str().unwrap_or_default();
+                let selector = hex_str_to_bytes(data)
+                    .unwrap_or_default()
+                    .get(..4)
+                    .and_then(|s| s.try_into().ok())
+                    .ok_or_else(|| format!("eth_call without a method selector {data}"))?;
+                let result = self.calls.get(&selector).cloned().unwrap_or(vec![0; 32]);
+                json!(format!("0x{}", bytes_to_hex_str(&result)))
+            }
+            "eth_sendRawTransaction" => {
+                let tx = params[0].as_str().unwrap_or_default().to_string();
+                self.sent_transactions.push(tx);
+                json!(hex_word(self.sent_transactions.len() as u64))
+            }
+            // sent transactions are never mined
+            "eth_getTransactionByHash" | "eth_getTransactionReceipt" => Value::Null,
+            _ => return Err(format!("Method {method} is not mocked")),
+        })
+    }
+
+    fn logs(&self, filter: &Value) -> Vec<Value> {
+        let block = |v: &Value| match v.as_str() {
+            Some("latest") | None => self.block_number,
+            Some("earliest") => 0,
+            Some(n) => u64::from_str_radix(n.trim_start_matches("0x"), 16).unwrap_or_default(),
+        };
+        let from = block(&filter["fromBlock"]);
+        let to = block(&filter["toBlock"]);
+        // the first topic is either a single event signature or a list of them
+        let topics: Vec<&str> = match &filter["topics"][0] {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(t) => t.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        self.logs
+            .iter()
+            .filter(|log| {
+                let number = block(&log["blockNumber"]);
+                let topic = log["topics"][0].as_str().unwrap_or_default();
+                from <= number
+                    && number <= to
+                    && (topics.is_empty() || topics.iter().any(|t| t.eq_ignore_ascii_case(topic)))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn block(&self) -> Value {
+        json!({
+            "number": hex_u64(self.block_number),
+            "hash": hex_word(self.block_number),
+            "parentHash": hex_word(self.block_number.saturating_sub(1)),
+            "nonce": "0x0000000000000000",
+            "sha3Uncles": hex_word(0),
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "transactionsRoot": hex_word(0),
+            "stateRoot": hex_word(0),
+            "receiptsRoot": hex_word(0),
+            "miner": format!("0x{}", "0".repeat(40)),
+            "difficulty": "0x0",
+            "totalDifficulty": "0x0",
+            "extraData": "0x",
+            "size": "0x0",
+            "gasLimit": hex_u64(30_000_000),
+            "gasUsed": "0x0",
+            "timestamp": hex_u64(self.block_number * 15),
+            "baseFeePerGas": hex_u64(self.gas_price),
+            "mixHash": hex_word(0),
+            "transactions": [],
+            "uncles": [],
+        })
+    }
+}
+
+/// Serves `EthState` over JSON-RPC on a local port until dropped
+pub struct MockEthereum {
+    state: Arc<Mutex<EthState>>,
+    server: Arc<Server>,
+    url: String,
+}
+
+impl MockEthereum {
+    pub fn start(state: EthState) -> Result<MockEthereum, io::Error> {
+        let server = Server::http("127.0.0.1:0")
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{e:?}")))?;
+        let url = format!("http://{}", server.server_addr());
+        let server = Arc::new(server);
+        let state = Arc::new(Mutex::new(state));
+
+        let (thread_server, thread_state) = (server.clone(), state.clone());
+        thread::spawn(move || {
+            for mut request in thread_server.incoming_requests() {
+                let mut body = String::new();
+                let response = match request.as_reader().read_to_string(&mut body) {
+                    Ok(_) => respond(&thread_state, &body),
+                    Err(e) => {
+                        json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": format!("{e:?}")}})
+                    }
+                };
+                let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+                let response = Response::from_string(response.to_string()).with_header(header);
+                if let Err(e) = request.respond(response) {
+                    warn!("Mock Ethereum failed to respond {:?}", e);
+                }
+            }
+        });
+
+        Ok(MockEthereum { state, server, url })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> MutexGuard<EthState> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockEthereum {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn respond(state: &Mutex<EthState>, body: &str) -> Value {
+    let request: Value = match serde_json::from_str(body) {
+        Ok(r) => r,
+        Err(e) => {
+            return json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": format!("{e:?}")}})
+        }
+    };
+    let method = request["method"].as_str().unwrap_or_default();
+    match state.lock().unwrap().handle(method, &request["params"]) {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": request["id"], "result": result}),
+        Err(e) => {
+            json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -32601, "message": e}})
+        }
+    }
+}
+
+/// The first 4 bytes of the keccak hash of a method signature
+pub fn method_selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// The first topic of the logs of an event such as `SENT_TO_COSMOS_EVENT_SIG`
+pub fn event_topic(signature: &str) -> String {
+    format!(
+        "0x{}",
+        bytes_to_hex_str(&Keccak256::digest(signature.as_bytes()))
+    )
+}
+
+fn hex_u64(n: u64) -> String {
+    format!("{n:#x}")
+}
+
+fn hex_word(n: u64) -> String {
+    format!("0x{n:064x}")
+}
@@ -0,0 +1,272 @@
+//! The Gravity module query service of the mock chain, only the queries the orchestrator and
+//! relayer make are answered, everything else is `unimplemented`
+
+use std::sync::{Arc, Mutex};
+
+use gravity_proto::gravity::{query_server::Query, *};
+use gravity_utils::clarity::utils::bytes_to_hex_str;
+use tonic::{Request, Response, Status};
+
+use crate::state::ChainState;
+
+/// The number of valsets returned by `LastValsetRequests` on a real chain
+const LAST_VALSET_REQUESTS: usize = 5;
+
+pub(crate) struct GravityQuery(pub(crate) Arc<Mutex<ChainState>>);
+
+impl GravityQuery {
+    fn state(&self) -> std::sync::MutexGuard<ChainState> {
+        self.0.lock().unwrap()
+    }
+}
+
+#[tonic::async_trait]
+impl Query for GravityQuery {
+    async fn params(
+        &self,
+        _request: Request<QueryParamsRequest>,
+    ) -> Result<Response<QueryParamsResponse>, Status> {
+        Ok(Response::new(QueryParamsResponse {
+            params: Some(self.state().params.clone()),
+        }))
+    }
+
+    async fn current_valset(
+        &self,
+        _request: Request<QueryCurrentValsetRequest>,
+    ) -> Result<Response<QueryCurrentValsetResponse>, Status> {
+        Ok(Response::new(QueryCurrentValsetResponse {
+            valset: self.state().valsets.last().cloned(),
+        }))
+    }
+
+    async fn valset_request(
+        &self,
+        request: Request<QueryValsetRequestRequest>,
+    ) -> Result<Response<QueryValsetRequestResponse>, Status> {
+        let nonce = request.into_inner().nonce;
+        let valset = self
+            .state()
+            .valsets
+            .iter()
+            .find(|v| v.nonce == nonce)
+            .cloned();
+        Ok(Response::new(QueryValsetRequestResponse { valset }))
+    }
+
+    async fn valset_confirm(
+        &self,
+        request: Request<QueryValsetConfirmRequest>,
+    ) -> Result<Response<QueryValsetConfirmResponse>, Status> {
+        let request = request.into_inner();
+        let confirm = self
+            .state()
+            .valset_confirms
+            .iter()
+            .find(|c| c.nonce == request.nonce && c.orchestrator == request.address)
+            .cloned();
+        Ok(Response::new(QueryValsetConfirmResponse { confirm }))
+    }
+
+    async fn valset_confirms_by_nonce(
+        &self,
+        request: Request<QueryValsetConfirmsByNonceRequest>,
+    ) -> Result<Response<QueryValsetConfirmsByNonceResponse>, Status> {
+        let nonce = request.into_inner().nonce;
+        let confirms = self
+            .state()
+            .valset_confirms
+            .iter()
+            .filter(|c| c.nonce == nonce)
+            .cloned()
+            .collect();
+        Ok(Response::new(QueryValsetConfirmsByNonceResponse {
+            confirms,
+        }))
+    }
+
+    async fn last_valset_requests(
+        &self,
+        _request: Request<QueryLastValsetRequestsRequest>,
+    ) -> Result<Response<QueryLastValsetRequestsResponse>, Status> {
+        // newest first
+        let valsets = self
+            .state()
+            .valsets
+            .iter()
+            .rev()
+            .take(LAST_VALSET_REQUESTS)
+            .cloned()
+            .collect();
+        Ok(Response::new(QueryLastValsetRequestsResponse { valsets }))
+    }
+
+    async fn last_pending_valset_request_by_addr(
+        &self,
+        request: Request<QueryLastPendingValsetRequestByAddrRequest>,
+    ) -> Result<Response<QueryLastPendingValsetRequestByAddrResponse>, Status> {
+        let address = request.into_inner().address;
+        Ok(Response::new(QueryLastPendingValsetRequestByAddrResponse {
+            valsets: self.state().unsigned_valsets(&address),
+        }))
+    }
+
+    async fn last_pending_batch_request_by_addr(
+        &self,
+        request: Request<QueryLastPendingBatchRequestByAddrRequest>,
+    ) -> Result<Response<QueryLastPendingBatchRequestByAddrResponse>, Status> {
+        let address = request.into_inner().address;
+        Ok(Response::new(QueryLastPendingBatchRequestByAddrResponse {
+            batch: self.state().unsigned_batches(&address),
+        }))
+    }
+
+    async fn last_pending_logic_call_by_addr(
+        &self,
+        request: Request<QueryLastPendingLogicCallByAddrRequest>,
+    ) -> Result<Response<QueryLastPendingLogicCallByAddrResponse>, Status> {
+        let address = request.into_inner().address;
+        Ok(Response::new(QueryLastPendingLogicCallByAddrResponse {
+            call: self.state().unsigned_logic_calls(&address),
+        }))
+    }
+
+    async fn last_event_nonce_by_addr(
+        &self,
+        request: Request<QueryLastEventNonceByAddrRequest>,
+    ) -> Result<Response<QueryLastEventNonceByAddrResponse>, Status> {
+        let address = request.into_inner().address;
+        let event_nonce = self
+            .state()
+            .event_nonces
+            .get(&address)
+            .copied()
+            .unwrap_or_default();
+        Ok(Response::new(QueryLastEventNonceByAddrResponse {
+            event_nonce,
+        }))
+    }
+
+    async fn batch_fees(
+        &self,
+        _request: Request<QueryBatchFeeRequest>,
+    ) -> Result<Response<QueryBatchFeeResponse>, Status> {
+        Err(Status::unimplemented("batch_fees"))
+    }
+
+    async fn outgoing_tx_batches(
+        &self,
+        _request: Request<QueryOutgoingTxBatchesRequest>,
+    ) -> Result<Response<QueryOutgoingTxBatchesResponse>, Status> {
+        // newest first
+        let batches = self.state().batches.iter().rev().cloned().collect();
+        Ok(Response::new(QueryOutgoingTxBatchesResponse { batches }))
+    }
+
+    async fn outgoing_logic_calls(
+        &self,
+        _request: Request<QueryOutgoingLogicCallsRequest>,
+    ) -> Result<Response<QueryOutgoingLogicCallsResponse>, Status> {
+        let calls = self.state().logic_calls.iter().rev().cloned().collect();
+        Ok(Response::new(QueryOutgoingLogicCallsResponse { calls }))
+    }
+
+    async fn batch_request_by_nonce(
+        &self,
+        request: Request<QueryBatchRequestByNonceRequest>,
+    ) -> Result<Response<QueryBatchRequestByNonceResponse>, Status> {
+        let request = request.into_inner();
+        let batch = self
+            .state()
+            .batches
+            .iter()
+            .find(|b| {
+                b.batch_nonce == request.nonce && b.token_contract == request.contract_address
+            })
+            .cloned();
+        Ok(Response::new(QueryBatchRequestByNonceResponse { batch }))
+    }
+
+    async fn batch_confirms(
+        &self,
+        request: Request<QueryBatchConfirmsRequest>,
+    ) -> Result<Response<QueryBatchConfirmsResponse>, Status> {
+        let request = request.into_inner();
+        let confirms = self
+            .state()
+            .batch_confirms
+            .iter()
+            .filter(|c| c.nonce == request.nonce && c.token_contract == request.contract_address)
+            .cloned()
+            .collect();
+        Ok(Response::new(QueryBatchConfirmsResponse { confirms }))
+    }
+
+    async fn logic_confirms(
+        &self,
+        request: Request<QueryLogicConfirmsRequest>,
+    ) -> Result<Response<QueryLogicConfirmsResponse>, Status> {
+        let request = request.into_inner();
+        let invalidation_id = bytes_to_hex_str(&request.invalidation_id);
+        let confirms = self
+            .state()
+            .logic_call_confirms
+            .iter()
+            .filter(|c| {
+                c.invalidation_nonce == request.invalidation_nonce
+                    && c.invalidation_id.eq_ignore_ascii_case(&invalidation_id)
+            })
+            .cloned()
+            .collect();
+        Ok(Response::new(QueryLogicConfirmsResponse { confirms }))
+    }
+
+    async fn erc20_to_denom(
+        &self,
+        _request: Request<QueryErc20ToDenomRequest>,
+    ) -> Result<Response<QueryErc20ToDenomResponse>, Status> {
+        Err(Status::unimplemented("erc20_to_denom"))
+    }
+
+    async fn denom_to_erc20(
+        &self,
+        _request: Request<QueryDenomToErc20Request>,
+    ) -> Result<Response<QueryDenomToErc20Response>, Status> {
+        Err(Status::unimplemented("denom_to_erc20"))
+    }
+
+    async fn get_attestations(
+        &self,
+        _request: Request<QueryAttestationsRequest>,
+    ) -> Result<Response<QueryAttestationsResponse>, Status> {
+        Err(Status::unimplemented("get_attestations"))
+    }
+
+    async fn get_delegate_key_by_validator(
+        &self,
+        _request: Request<QueryDelegateKeysByValidatorAddress>,
+    ) -> Result<Response<QueryDelegateKeysByValidatorAddressResponse>, Status> {
+        Err(Status::unimplemented("get_delegate_key_by_validator"))
+    }
+
+    async fn get_delegate_key_by_eth(
+        &self,
+        _request: Request<QueryDelegateKeysByEthAddress>,
+    ) -> Result<Response<QueryDelegateKeysByEthAddressResponse>, Status> {
+        Err(Status::unimplemented("get_delegate_key_by_eth"))
+    }
+
+    async fn get_delegate_key_by_orchestrator(
+        &self,
+        _request: Request<QueryDelegateKeysByOrchestratorAddress>,
+    ) -> Result<Response<QueryDelegateKeysByOrchestratorAddressResponse>, Status> {
+        Err(Status::unimplemented("get_delegate_key_by_orchestrator"))
+    }
+
+    async fn get_pending_send_to_eth(
+        &self,
+        _request: Request<QueryPendingSendToEth>,
+    ) -> Result<Response<QueryPendingSendToEthResponse>, Status> {
+        Err(Status::unimplemented("get_pending_send_to_eth"))
+    }
+}
@@ -0,0 +1,94 @@
+//! An in-process stand in for a Gravity chain and its Ethereum node so that the orchestrator
+//! and relayer loops can be tested with `cargo test` instead of the docker test environment.
+//! `MockChain` serves the Gravity query service and the Cosmos services deep_space uses over
+//! gRPC, `MockEthereum` serves a scripted JSON-RPC. Both are driven by plain state structs
+//! that tests set up before a scenario and inspect afterwards
+
+#[macro_use]
+extern crate log;
+
+mod cosmos;
+mod ethereum;
+mod gravity;
+#[cfg(test)]
+mod scenarios;
+mod state;
+
+/// Servers for the Cosmos SDK services the mock chain serves, generated by proto_build from
+/// `mock_chain/proto` since cosmos-sdk-proto is built without them. Only the methods deep_space
+/// calls are declared, tonic answers any other method with `Unimplemented`
+mod cosmos_services {
+    pub mod auth {
+        include!("prost/cosmos.auth.v1beta1.rs");
+    }
+    pub mod tendermint {
+        include!("prost/cosmos.base.tendermint.v1beta1.rs");
+    }
+    pub mod tx {
+        include!("prost/cosmos.tx.v1beta1.rs");
+    }
+}
+
+use std::{
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use cosmos_services::{
+    auth::query_server::QueryServer as AuthQueryServer,
+    tendermint::service_server::ServiceServer as TendermintServer,
+    tx::service_server::ServiceServer as TxServer,
+};
+use gravity_proto::gravity::query_server::QueryServer;
+use tokio::{net::TcpListener, task::JoinHandle};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+pub use ethereum::{event_topic, method_selector, EthState, MockEthereum};
+pub use state::{ChainState, INSUFFICIENT_FEE_CODE, MOCK_CHAIN_ID, MOCK_GRAVITY_ID};
+
+/// Serves `ChainState` over gRPC on a local port until dropped
+pub struct MockChain {
+    state: Arc<Mutex<ChainState>>,
+    server: JoinHandle<()>,
+    url: String,
+}
+
+impl MockChain {
+    pub async fn start(state: ChainState) -> Result<MockChain, io::Error> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(state));
+
+        let router = Server::builder()
+            .add_service(QueryServer::new(gravity::GravityQuery(state.clone())))
+            .add_service(TendermintServer::new(cosmos::Tendermint(state.clone())))
+            .add_service(AuthQueryServer::new(cosmos::Auth(state.clone())))
+            .add_service(TxServer::new(cosmos::Tx(state.clone())));
+        let server = tokio::spawn(async move {
+            if let Err(e) = router
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+            {
+                error!("Mock chain gRPC server failed with {:?}", e);
+            }
+        });
+
+        Ok(MockChain { state, server, url })
+    }
+
+    /// The gRPC url for `Contact::new` and `GravityQueryClient::connect`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> MutexGuard<ChainState> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockChain {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
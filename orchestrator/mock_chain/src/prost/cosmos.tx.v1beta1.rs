@@ -0,0 +1,252 @@
+/// Generated server implementations.
+pub mod service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with ServiceServer.
+    #[async_trait]
+    pub trait Service: Send + Sync + 'static {
+        async fn simulate(
+            &self,
+            request: tonic::Request<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateRequest,
+            >,
+        ) -> Result<
+            tonic::Response<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse,
+            >,
+            tonic::Status,
+        >;
+        async fn get_tx(
+            &self,
+            request: tonic::Request<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest,
+            >,
+        ) -> Result<
+            tonic::Response<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse,
+            >,
+            tonic::Status,
+        >;
+        async fn broadcast_tx(
+            &self,
+            request: tonic::Request<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxRequest,
+            >,
+        ) -> Result<
+            tonic::Response<
+                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxResponse,
+            >,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct ServiceServer<T: Service> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Service> ServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ServiceServer<T>
+    where
+        T: Service,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/cosmos.tx.v1beta1.Service/Simulate" => {
+                    #[allow(non_camel_case_types)]
+                    struct SimulateSvc<T: Service>(pub Arc<T>);
+                    impl<
+                        T: Service,
+                    > tonic::server::UnaryService<
+                        ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateRequest,
+                    > for SimulateSvc<T> {
+                        type Response = ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).simulate(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SimulateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/cosmos.tx.v1beta1.Service/GetTx" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTxSvc<T: Service>(pub Arc<T>);
+                    impl<
+                        T: Service,
+                    > tonic::server::UnaryService<
+                        ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest,
+                    > for GetTxSvc<T> {
+                        type Response = ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_tx(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetTxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                    #[allow(non_camel_case_types)]
+                    struct BroadcastTxSvc<T: Service>(pub Arc<T>);
+                    impl<
+                        T: Service,
+                    > tonic::server::UnaryService<
+                        ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxRequest,
+                    > for BroadcastTxSvc<T> {
+                        type Response = ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                ::gravity_proto::cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).broadcast_tx(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BroadcastTxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Service> Clone for ServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Service> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Service> tonic::transport::NamedService for ServiceServer<T> {
+        const NAME: &'static str = "cosmos.tx.v1beta1.Service";
+    }
+}
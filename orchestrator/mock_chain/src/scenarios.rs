@@ -0,0 +1,319 @@
+//! Orchestrator and relayer loop scenarios run against the mock chain
+
+use std::{convert::TryFrom, time::Duration};
+
+use ethereum_gravity::message_signatures::{encode_tx_batch_confirm, encode_valset_confirm_hashed};
+use gravity_proto::{
+    cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin,
+    gravity::{
+        query_client::QueryClient as GravityQueryClient, BridgeValidator, Erc20Token,
+        MsgConfirmBatch, MsgValsetConfirm, OutgoingLogicCall, OutgoingTransferTx, OutgoingTxBatch,
+        Valset,
+    },
+};
+use gravity_utils::{
+    clarity::{
+        constants::ZERO_ADDRESS, u256, utils::bytes_to_hex_str, Address as EthAddress,
+        PrivateKey as EthPrivateKey, Uint256,
+    },
+    deep_space::{coin::Coin, private_key::PrivateKey as CosmosPrivateKey, Contact},
+    error::GravityError,
+    types::{
+        event_signatures::SENT_TO_COSMOS_EVENT_SIG, RelayerConfig, TransactionBatch,
+        ValsetConfirmResponse, TOTAL_GRAVITY_POWER,
+    },
+    web30::client::Web3,
+    DEFAULT_ADDRESS_PREFIX,
+};
+use orchestrator::{ethereum_event_watcher::check_for_events, main_loop::eth_signer_main_loop};
+use relayer::batch_relaying::relay_batches;
+use tokio::time::timeout;
+use tonic::transport::Channel;
+
+use crate::{event_topic, ChainState, EthState, MockChain, MockEthereum, INSUFFICIENT_FEE_CODE};
+
+const FEE_DENOM: &str = "anom";
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(5);
+/// long enough for the first iteration of a loop, loops sleep for longer between iterations
+const SCENARIO_TIMEOUT: Duration = Duration::from_secs(5);
+const GRAVITY_CONTRACT: &str = "0x7580bFE88Dd3d07947908FAE12d95872a260F2D8";
+const TOKEN_CONTRACT: &str = "0xD7600ae27C99988A6CD360234062b540F88ECA43";
+
+fn keys() -> (CosmosPrivateKey, EthPrivateKey) {
+    let secret = [7u8; 32];
+    (
+        CosmosPrivateKey::from_secret(&secret),
+        EthPrivateKey::from_slice(&secret).unwrap(),
+    )
+}
+
+fn orchestrator_address() -> String {
+    let (cosmos_key, _) = keys();
+    cosmos_key
+        .to_address(DEFAULT_ADDRESS_PREFIX)
+        .unwrap()
+        .to_string()
+}
+
+fn fee(amount: u64) -> Coin {
+    Coin {
+        denom: FEE_DENOM.to_string(),
+        amount: Uint256::from_u64(amount),
+    }
+}
+
+/// A valset where our Ethereum key holds all of the power
+fn valset(nonce: u64) -> Valset {
+    let (_, eth_key) = keys();
+    Valset {
+        nonce,
+        members: vec![BridgeValidator {
+            power: TOTAL_GRAVITY_POWER,
+            ethereum_address: eth_key.to_address().to_string(),
+        }],
+        height: nonce * 10,
+        reward_amount: "0".to_string(),
+        reward_token: ZERO_ADDRESS.to_string(),
+    }
+}
+
+fn batch(nonce: u64, batch_timeout: u64) -> OutgoingTxBatch {
+    let (_, eth_key) = keys();
+    let token = |amount: &str| {
+        Some(Erc20Token {
+            contract: TOKEN_CONTRACT.to_string(),
+            amount: amount.to_string(),
+        })
+    };
+    OutgoingTxBatch {
+        batch_nonce: nonce,
+        batch_timeout,
+        transactions: vec![OutgoingTransferTx {
+            id: nonce,
+            sender: orchestrator_address(),
+            dest_address: eth_key.to_address().to_string(),
+            erc20_token: token("100"),
+            erc20_fee: token("10"),
+        }],
+        token_contract: TOKEN_CONTRACT.to_string(),
+        block: 90,
+    }
+}
+
+async fn connect(chain: &MockChain) -> (Contact, GravityQueryClient<Channel>) {
+    let contact = Contact::new(chain.url(), OPERATION_TIMEOUT, DEFAULT_ADDRESS_PREFIX).unwrap();
+    let grpc_client = GravityQueryClient::connect(chain.url().to_string())
+        .await
+        .unwrap();
+    (contact, grpc_client)
+}
+
+/// Runs the first iteration of the signer loop, None if the loop is still running
+async fn run_signer(chain: &MockChain, fee: Coin) -> Option<Result<(), GravityError>> {
+    let (contact, grpc_client) = connect(chain).await;
+    let (cosmos_key, eth_key) = keys();
+    let signer = eth_signer_main_loop(cosmos_key, eth_key, contact, grpc_client, fee);
+    timeout(SCENARIO_TIMEOUT, signer).await.ok()
+}
+
+#[tokio::test]
+async fn test_missed_valset() {
+    let (_, eth_key) = keys();
+    let mut state = ChainState::default();
+    state.valsets = vec![valset(1), valset(2)];
+    // the orchestrator signed the newest valset but was offline for the first
+    state.valset_confirms = vec![MsgValsetConfirm {
+        nonce: 2,
+        orchestrator: orchestrator_address(),
+        ..Default::default()
+    }];
+    let chain = MockChain::start(state).await.unwrap();
+
+    assert!(run_signer(&chain, fee(1)).await.is_none());
+
+    let state = chain.state();
+    let confirm = state
+        .valset_confirms
+        .iter()
+        .find(|c| c.nonce == 1)
+        .expect("missed valset was not signed")
+        .clone();
+    assert_eq!(confirm.orchestrator, orchestrator_address());
+    assert_eq!(confirm.eth_address, eth_key.to_address().to_string());
+    // the signature has to be usable on Ethereum
+    let valset: gravity_utils::types::Valset = valset(1).into();
+    let hash = encode_valset_confirm_hashed(state.params.gravity_id.clone(), &valset);
+    let confirm = ValsetConfirmResponse::try_from(confirm).unwrap();
+    assert!(valset.order_sigs(&hash, &[confirm]).is_ok());
+}
+
+#[tokio::test]
+async fn test_logic_call_confirm() {
+    let mut state = ChainState::default();
+    state.logic_calls = vec![OutgoingLogicCall {
+        transfers: vec![Erc20Token {
+            contract: TOKEN_CONTRACT.to_string(),
+            amount: "100".to_string(),
+        }],
+        fees: vec![Erc20Token {
+            contract: TOKEN_CONTRACT.to_string(),
+            amount: "10".to_string(),
+        }],
+        logic_contract_address: TOKEN_CONTRACT.to_string(),
+        payload: vec![1, 2, 3],
+        timeout: 10_000,
+        invalidation_id: vec![9; 32],
+        invalidation_nonce: 1,
+        block: 90,
+    }];
+    let chain = MockChain::start(state).await.unwrap();
+
+    assert!(run_signer(&chain, fee(1)).await.is_none());
+
+    let state = chain.state();
+    assert_eq!(state.logic_call_confirms.len(), 1);
+    assert_eq!(state.logic_call_confirms[0].invalidation_nonce, 1);
+    assert_eq!(
+        state.logic_call_confirms[0].invalidation_id,
+        bytes_to_hex_str(&[9; 32])
+    );
+    assert!(state
+        .unsigned_logic_calls(&orchestrator_address())
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_node_syncing() {
+    let mut state = ChainState::default();
+    state.syncing = true;
+    state.valsets = vec![valset(1)];
+    let chain = MockChain::start(state).await.unwrap();
+
+    assert!(run_signer(&chain, fee(1)).await.is_none());
+
+    // nothing is signed until the node has caught up
+    assert!(chain.state().txs.is_empty());
+    assert!(chain.state().valset_confirms.is_empty());
+}
+
+#[tokio::test]
+async fn test_insufficient_fees() {
+    let mut state = ChainState::default();
+    state.valsets = vec![valset(1)];
+    state.min_fee = Some(ProtoCoin {
+        denom: FEE_DENOM.to_string(),
+        amount: "100".to_string(),
+    });
+    let chain = MockChain::start(state).await.unwrap();
+
+    // a fee that is too small stops the orchestrator instead of being retried forever
+    let res = run_signer(&chain, fee(1)).await;
+    assert!(matches!(
+        res,
+        Some(Err(GravityError::UnrecoverableError(_)))
+    ));
+    let txs = chain.state().txs.clone();
+    assert!(!txs.is_empty());
+    assert!(txs.iter().all(|t| t.code == INSUFFICIENT_FEE_CODE));
+    assert!(chain.state().valset_confirms.is_empty());
+}
+
+#[tokio::test]
+async fn test_batch_timeout() {
+    let (_, eth_key) = keys();
+    let mut state = ChainState::default();
+    state.valsets = vec![valset(1)];
+    // the batch timed out at Ethereum block 500
+    state.batches = vec![batch(1, 500)];
+    let gravity_id = state.params.gravity_id.clone();
+    let signed = TransactionBatch::try_from(batch(1, 500)).unwrap();
+    let signature =
+        eth_key.sign_ethereum_msg(&encode_tx_batch_confirm(gravity_id.clone(), &signed));
+    state.batch_confirms = vec![MsgConfirmBatch {
+        nonce: 1,
+        token_contract: TOKEN_CONTRACT.to_string(),
+        eth_signer: eth_key.to_address().to_string(),
+        orchestrator: orchestrator_address(),
+        signature: bytes_to_hex_str(&signature.to_bytes()),
+    }];
+    let chain = MockChain::start(state).await.unwrap();
+    let eth = MockEthereum::start(EthState {
+        block_number: 1_000,
+        ..Default::default()
+    })
+    .unwrap();
+    let web3 = Web3::new(eth.url(), OPERATION_TIMEOUT);
+    let (_, mut grpc_client) = connect(&chain).await;
+
+    relay_batches(
+        &valset(1).into(),
+        eth_key,
+        &web3,
+        &mut grpc_client,
+        GRAVITY_CONTRACT.parse::<EthAddress>().unwrap(),
+        gravity_id,
+        OPERATION_TIMEOUT,
+        &RelayerConfig::default(),
+        Some(100),
+    )
+    .await;
+
+    // the batch was considered, its nonce on Ethereum was checked, but never submitted
+    assert_eq!(eth.state().request_count("eth_call"), 1);
+    assert!(eth.state().sent_transactions.is_empty());
+}
+
+#[tokio::test]
+async fn test_oracle_deposit() {
+    let (cosmos_key, eth_key) = keys();
+    let chain = MockChain::start(ChainState::default()).await.unwrap();
+    let eth = MockEthereum::start(EthState::default()).unwrap();
+
+    // SendToCosmosEvent(address indexed, address indexed, string, uint256 amount, uint256 nonce)
+    let destination = orchestrator_address();
+    let mut data = vec![0u8; 4 * 32];
+    data[31] = 0x60;
+    data[63] = 100;
+    data[95] = 1;
+    data[127] = destination.len() as u8;
+    let mut padded = destination.as_bytes().to_vec();
+    padded.resize((destination.len() + 31) / 32 * 32, 0);
+    data.extend(padded);
+    let address_topic = |a: EthAddress| {
+        let address = a.to_string().trim_start_matches("0x").to_lowercase();
+        format!("0x{address:0>64}")
+    };
+    eth.state().push_log(
+        GRAVITY_CONTRACT,
+        vec![
+            event_topic(SENT_TO_COSMOS_EVENT_SIG),
+            address_topic(TOKEN_CONTRACT.parse().unwrap()),
+            address_topic(eth_key.to_address()),
+        ],
+        &data,
+        900,
+    );
+
+    let web3 = Web3::new(eth.url(), OPERATION_TIMEOUT);
+    let (contact, mut grpc_client) = connect(&chain).await;
+    check_for_events(
+        &web3,
+        &contact,
+        &mut grpc_client,
+        GRAVITY_CONTRACT.parse().unwrap(),
+        cosmos_key,
+        fee(1),
+        u256!(0),
+    )
+    .await
+    .unwrap();
+
+    let state = chain.state();
+    assert_eq!(state.event_nonces[&orchestrator_address()], 1);
+    assert_eq!(state.messages.len(), 1);
+    assert_eq!(
+        state.messages[0].type_url,
+        "/gravity.v1.MsgSendToCosmosClaim"
+    );
+    assert_eq!(eth.state().request_count("eth_getLogs"), 5);
+}
@@ -0,0 +1,184 @@
+//! The state of the mock Gravity chain, tests script it directly and the gRPC services read
+//! and update it as the orchestrator talks to them
+
+use std::collections::HashMap;
+
+use gravity_proto::{
+    cosmos_sdk_proto::cosmos::base::{abci::v1beta1::TxResponse, v1beta1::Coin},
+    gravity::{
+        MsgBatchSendToEthClaim, MsgConfirmBatch, MsgConfirmLogicCall, MsgErc20DeployedClaim,
+        MsgLogicCallExecutedClaim, MsgSendToCosmosClaim, MsgValsetConfirm, MsgValsetUpdatedClaim,
+        OutgoingLogicCall, OutgoingTxBatch, Params, Valset,
+    },
+};
+use gravity_utils::{clarity::utils::bytes_to_hex_str, TEST_ETH_CHAIN_ID};
+use prost::Message;
+use prost_types::Any;
+
+pub const MOCK_CHAIN_ID: &str = "gravity-test";
+pub const MOCK_GRAVITY_ID: &str = "gravity-test";
+
+/// The sdk error code for a fee below the minimum gas price of the validator
+pub const INSUFFICIENT_FEE_CODE: u32 = 13;
+
+#[derive(Debug, Clone)]
+pub struct ChainState {
+    pub chain_id: String,
+    pub block_height: u64,
+    /// reported by the tendermint `GetSyncing` query
+    pub syncing: bool,
+    pub params: Params,
+    /// every valset the chain has requested, oldest first
+    pub valsets: Vec<Valset>,
+    /// batches waiting to be relayed, oldest first
+    pub batches: Vec<OutgoingTxBatch>,
+    /// logic calls waiting to be relayed, oldest first
+    pub logic_calls: Vec<OutgoingLogicCall>,
+    pub valset_confirms: Vec<MsgValsetConfirm>,
+    pub batch_confirms: Vec<MsgConfirmBatch>,
+    pub logic_call_confirms: Vec<MsgConfirmLogicCall>,
+    /// the last event nonce claimed by each orchestrator address
+    pub event_nonces: HashMap<String, u64>,
+    /// transactions paying less than this are rejected, None accepts any fee
+    pub min_fee: Option<Coin>,
+    /// every broadcast transaction, including rejected ones
+    pub txs: Vec<TxResponse>,
+    /// the messages of every accepted transaction in the order they were delivered
+    pub messages: Vec<Any>,
+}
+
+impl Default for ChainState {
+    fn default() -> Self {
+        ChainState {
+            chain_id: MOCK_CHAIN_ID.to_string(),
+            block_height: 100,
+            syncing: false,
+            params: Params {
+                gravity_id: MOCK_GRAVITY_ID.to_string(),
+                bridge_chain_id: TEST_ETH_CHAIN_ID,
+                signed_valsets_window: 10_000,
+                signed_batches_window: 10_000,
+                signed_logic_calls_window: 10_000,
+                target_batch_timeout: 43_200_000,
+                average_block_time: 5_000,
+                average_ethereum_block_time: 15_000,
+                bridge_active: true,
+                ..Default::default()
+            },
+            valsets: Vec::new(),
+            batches: Vec::new(),
+            logic_calls: Vec::new(),
+            valset_confirms: Vec::new(),
+            batch_confirms: Vec::new(),
+            logic_call_confirms: Vec::new(),
+            event_nonces: HashMap::new(),
+            min_fee: None,
+            txs: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+}
+
+impl ChainState {
+    /// Valsets `orchestrator` has not confirmed, oldest first
+    pub fn unsigned_valsets(&self, orchestrator: &str) -> Vec<Valset> {
+        self.valsets
+            .iter()
+            .filter(|v| {
+                !self
+                    .valset_confirms
+                    .iter()
+                    .any(|c| c.orchestrator == orchestrator && c.nonce == v.nonce)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Batches `orchestrator` has not confirmed, oldest first
+    pub fn unsigned_batches(&self, orchestrator: &str) -> Vec<OutgoingTxBatch> {
+        self.batches
+            .iter()
+            .filter(|b| {
+                !self.batch_confirms.iter().any(|c| {
+                    c.orchestrator == orchestrator
+                        && c.nonce == b.batch_nonce
+                        && c.token_contract == b.token_contract
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Logic calls `orchestrator` has not confirmed, oldest first
+    pub fn unsigned_logic_calls(&self, orchestrator: &str) -> Vec<OutgoingLogicCall> {
+        self.logic_calls
+            .iter()
+            .filter(|l| {
+                !self.logic_call_confirms.iter().any(|c| {
+                    c.orchestrator == orchestrator
+                        && c.invalidation_nonce == l.invalidation_nonce
+                        && c.invalidation_id
+                            .eq_ignore_ascii_case(&bytes_to_hex_str(&l.invalidation_id))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Applies the messages of an accepted transaction, confirms are stored so the queries
+    /// return them and claims advance the event nonce of their orchestrator
+    pub(crate) fn deliver(&mut self, messages: Vec<Any>) -> Result<(), String> {
+        for msg in messages {
+            let value = &msg.value[..];
+            let decoded = match msg.type_url.as_str() {
+                "/gravity.v1.MsgValsetConfirm" => {
+                    MsgValsetConfirm::decode(value).map(|m| self.valset_confirms.push(m))
+                }
+                "/gravity.v1.MsgConfirmBatch" => {
+                    MsgConfirmBatch::decode(value).map(|m| self.batch_confirms.push(m))
+                }
+                "/gravity.v1.MsgConfirmLogicCall" => {
+                    MsgConfirmLogicCall::decode(value).map(|m| self.logic_call_confirms.push(m))
+                }
+                "/gravity.v1.MsgSendToCosmosClaim" => MsgSendToCosmosClaim::decode(value)
+                    .map(|m| self.claim(m.orchestrator, m.event_nonce)),
+                "/gravity.v1.MsgBatchSendToEthClaim" => MsgBatchSendToEthClaim::decode(value)
+                    .map(|m| self.claim(m.orchestrator, m.event_nonce)),
+                "/gravity.v1.MsgErc20DeployedClaim" => MsgErc20DeployedClaim::decode(value)
+                    .map(|m| self.claim(m.orchestrator, m.event_nonce)),
+                "/gravity.v1.MsgLogicCallExecutedClaim" => MsgLogicCallExecutedClaim::decode(value)
+                    .map(|m| self.claim(m.orchestrator, m.event_nonce)),
+                "/gravity.v1.MsgValsetUpdatedClaim" => MsgValsetUpdatedClaim::decode(value)
+                    .map(|m| self.claim(m.orchestrator, m.event_nonce)),
+                // anything else is recorded without changing the state
+                _ => Ok(()),
+            };
+            decoded.map_err(|e| format!("Failed to decode {} {e:?}", msg.type_url))?;
+            self.messages.push(msg);
+        }
+        Ok(())
+    }
+
+    /// Returns the minimum fee if `fee` does not cover it
+    pub(crate) fn check_fee(&self, fee: &[Coin]) -> Option<Coin> {
+        let min_fee = self.min_fee.as_ref()?;
+        let paid: u128 = fee
+            .iter()
+            .filter(|c| c.denom == min_fee.denom)
+            .filter_map(|c| c.amount.parse::<u128>().ok())
+            .sum();
+        let required: u128 = min_fee.amount.parse().unwrap_or_default();
+        if paid < required {
+            Some(min_fee.clone())
+        } else {
+            None
+        }
+    }
+
+    fn claim(&mut self, orchestrator: String, event_nonce: u64) {
+        let last = self.event_nonces.entry(orchestrator).or_default();
+        if event_nonce > *last {
+            *last = event_nonce;
+        }
+    }
+}
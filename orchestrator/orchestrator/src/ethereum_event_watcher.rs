@@ -16,7 +16,9 @@ use gravity_utils::{
     web30::{client::Web3, jsonrpc::error::Web3Error},
     USE_FINALIZATION,
 };
-use metrics_exporter::metrics_errors_counter;
+use metrics_exporter::{
+    metrics_claims_submitted, metrics_error, metrics_rpc, Chain, ClaimKind, ErrorKind, Subsystem,
+};
 use tonic::transport::Channel;
 
 #[derive(Clone, Copy)]
@@ -70,54 +72,69 @@ pub async fn check_for_events(
             })?
     };
 
-    let deposits = web3
-        .check_for_events(
+    let deposits = metrics_rpc(
+        "eth_getLogs",
+        Chain::Ethereum,
+        web3.check_for_events(
             starting_block,
             Some(ending_block),
             vec![gravity_contract_address],
             vec![SENT_TO_COSMOS_EVENT_SIG],
-        )
-        .await;
+        ),
+    )
+    .await;
     trace!("Deposits {:?}", deposits);
 
-    let batches = web3
-        .check_for_events(
+    let batches = metrics_rpc(
+        "eth_getLogs",
+        Chain::Ethereum,
+        web3.check_for_events(
             starting_block,
             Some(ending_block),
             vec![gravity_contract_address],
             vec![TRANSACTION_BATCH_EXECUTED_EVENT_SIG],
-        )
-        .await;
+        ),
+    )
+    .await;
     trace!("Batches {:?}", batches);
 
-    let valsets = web3
-        .check_for_events(
+    let valsets = metrics_rpc(
+        "eth_getLogs",
+        Chain::Ethereum,
+        web3.check_for_events(
             starting_block,
             Some(ending_block),
             vec![gravity_contract_address],
             vec![VALSET_UPDATED_EVENT_SIG],
-        )
-        .await;
+        ),
+    )
+    .await;
     trace!("Valsets {:?}", valsets);
 
-    let erc20_deployed = web3
-        .check_for_events(
+    let erc20_deployed = metrics_rpc(
+        "eth_getLogs",
+        Chain::Ethereum,
+        web3.check_for_events(
             starting_block,
             Some(ending_block),
             vec![gravity_contract_address],
             vec![ERC20_DEPLOYED_EVENT_SIG],
-        )
-        .await;
+        ),
+    )
+    .await;
     trace!("ERC20 Deployments {:?}", erc20_deployed);
 
-    let logic_call_executed = web3
-        .check_for_events(
+    let logic_call_executed = metrics_rpc(
+        "eth_getLogs",
+        Chain::Ethereum,
+        web3.check_for_events(
             starting_block,
             Some(ending_block),
             vec![gravity_contract_address],
             vec![LOGIC_CALL_EVENT_SIG],
-        )
-        .await;
+        ),
+    )
+    .await;
     trace!("Logic call executions {:?}", logic_call_executed);
 
     if let (Ok(valsets), Ok(batches), Ok(deposits), Ok(deploys), Ok(logic_calls)) = (
@@ -143,10 +160,14 @@ pub async fn check_for_events(
         // block, so we also need this routine so make sure we don't send in the first event in this hypothetical
         // multi event block again. In theory we only send all events for every block and that will pass of fail
         // atomicly but lets not take that risk.
-        let last_event_nonce = get_last_event_nonce_for_validator(
-            grpc_client,
-            our_cosmos_address,
-            contact.get_prefix(),
+        let last_event_nonce = metrics_rpc(
+            "last_event_nonce_by_addr",
+            Chain::Cosmos,
+            get_last_event_nonce_for_validator(
+                grpc_client,
+                our_cosmos_address,
+                contact.get_prefix(),
+            ),
         )
         .await?;
         let valsets = ValsetUpdatedEvent::filter_by_event_nonce(last_event_nonce, &valsets);
@@ -160,18 +181,28 @@ pub async fn check_for_events(
 
         if !valsets.is_empty() {
             info!(
+                subsystem = "eth_oracle",
+                nonce = valsets[0].valset_nonce,
+                event_nonce = valsets[0].event_nonce;
                 "Oracle observed Valset update with nonce {} and event nonce {}",
                 valsets[0].valset_nonce, valsets[0].event_nonce
             )
         }
         if !deposits.is_empty() {
             info!(
+                subsystem = "eth_oracle",
+                token_contract = deposits[0].erc20.to_string(),
+                event_nonce = deposits[0].event_nonce;
                 "Oracle observed deposit with sender {}, destination {:?}, amount {}, and event nonce {}",
                 deposits[0].sender, deposits[0].validated_destination, deposits[0].amount, deposits[0].event_nonce
             )
         }
         if !withdraws.is_empty() {
             info!(
+                subsystem = "eth_oracle",
+                nonce = withdraws[0].batch_nonce,
+                token_contract = withdraws[0].erc20.to_string(),
+                event_nonce = withdraws[0].event_nonce;
                 "Oracle observed batch with nonce {}, contract {}, and event nonce {}",
                 withdraws[0].batch_nonce, withdraws[0].erc20, withdraws[0].event_nonce
             )
@@ -180,11 +211,15 @@ pub async fn check_for_events(
             let v = erc20_deploys[0].clone();
             if v.cosmos_denom.len() < 1000 && v.name.len() < 1000 && v.symbol.len() < 1000 {
                 info!(
+                subsystem = "eth_oracle",
+                token_contract = v.erc20_address.to_string(),
+                event_nonce = v.event_nonce;
                 "Oracle observed ERC20 deployment with denom {} erc20 name {} and symbol {} and event nonce {}",
                 erc20_deploys[0].cosmos_denom, erc20_deploys[0].name, erc20_deploys[0].symbol, erc20_deploys[0].event_nonce,
                 );
             } else {
                 info!(
+                    subsystem = "eth_oracle", event_nonce = v.event_nonce;
                     "Oracle observed ERC20 deployment with  event nonce {}",
                     erc20_deploys[0].event_nonce,
                 );
@@ -192,6 +227,9 @@ pub async fn check_for_events(
         }
         if !logic_calls.is_empty() {
             info!(
+                subsystem = "eth_oracle",
+                nonce = logic_calls[0].invalidation_nonce,
+                event_nonce = logic_calls[0].event_nonce;
                 "Oracle observed logic call execution with ID {} Nonce {} and event nonce {}",
                 bytes_to_hex_str(&logic_calls[0].invalidation_id),
                 logic_calls[0].invalidation_nonce,
@@ -206,7 +244,14 @@ pub async fn check_for_events(
             || !logic_calls.is_empty()
             || !valsets.is_empty()
         {
-            let res = send_ethereum_claims(
+            let claim_counts = [
+                (ClaimKind::SendToCosmos, deposits.len()),
+                (ClaimKind::BatchExecuted, withdraws.len()),
+                (ClaimKind::Erc20Deployed, erc20_deploys.len()),
+                (ClaimKind::LogicCallExecuted, logic_calls.len()),
+                (ClaimKind::ValsetUpdated, valsets.len()),
+            ];
+            let res = match send_ethereum_claims(
                 contact,
                 our_private_key,
                 deposits,
@@ -216,7 +261,14 @@ pub async fn check_for_events(
                 valsets,
                 fee,
             )
-            .await?;
+            .await
+            {
+                Ok(res) => res,
+                Err(e) => {
+                    metrics_error(Subsystem::Cosmos, ErrorKind::ClaimSubmit);
+                    return Err(e.into());
+                }
+            };
 
             let new_event_nonce = get_last_event_nonce_for_validator(
                 grpc_client,
@@ -230,11 +282,21 @@ pub async fn check_for_events(
             // since we can't actually trust that the above txresponse is correct we have to check here
             // we may be able to trust the tx response post grpc
             if new_event_nonce == last_event_nonce {
+                metrics_error(Subsystem::Cosmos, ErrorKind::ClaimSubmit);
                 return Err(GravityError::ValidationError(
                     format!("Claims did not process, trying to update but still on {}, trying again in a moment, check txhash {} for errors", last_event_nonce, res.txhash),
                 ));
             } else {
-                info!("Claims processed, new nonce {}", new_event_nonce);
+                info!(
+                    subsystem = "eth_oracle",
+                    event_nonce = new_event_nonce,
+                    txid = res.txhash.clone(),
+                    outcome = "processed";
+                    "Claims processed, new nonce {}", new_event_nonce
+                );
+                for (kind, count) in claim_counts {
+                    metrics_claims_submitted(kind, count);
+                }
             }
         }
         Ok(CheckedNonces {
@@ -243,7 +305,7 @@ pub async fn check_for_events(
         })
     } else {
         error!("Failed to get events");
-        metrics_errors_counter(1, "Failed to get events");
+        metrics_error(Subsystem::Ethereum, ErrorKind::EventQuery);
         Err(GravityError::RpcError(Box::new(Web3Error::BadResponse(
             "Failed to get logs!".into(),
         ))))
@@ -7,3 +7,4 @@ extern crate log;
 pub mod ethereum_event_watcher;
 pub mod main_loop;
 pub mod oracle_resync;
+pub mod watchdog;
@@ -11,7 +11,8 @@ use cosmos_gravity::{
     },
     send::{send_batch_confirm, send_logic_call_confirm, send_valset_confirms},
 };
-use futures::future::{try_join, try_join3};
+use ethereum_gravity::utils::get_event_nonce;
+use futures::future::{try_join4, try_join5};
 use gravity_proto::{
     cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse,
     gravity::query_client::QueryClient as GravityQueryClient,
@@ -27,24 +28,36 @@ use gravity_utils::{
     u64_array_bigints,
     web30::client::Web3,
 };
-use metrics_exporter::{metrics_errors_counter, metrics_latest, metrics_warnings_counter};
+use metrics_exporter::{
+    health_loop_completed, health_loop_started, health_node_status, metrics_balance,
+    metrics_blocks_until_slashing, metrics_confirms_submitted, metrics_error,
+    metrics_last_checked_event_nonce, metrics_latest_block, metrics_loop, metrics_oracle_lag,
+    metrics_rpc, metrics_warning, BridgeObject, Chain, ErrorKind, LoopKind, NodeStatus, Subsystem,
+};
 use relayer::main_loop::relayer_main_loop;
-use tokio::time::sleep;
+use tokio::{sync::watch, time::sleep};
 use tonic::transport::Channel;
 
-use crate::{ethereum_event_watcher::check_for_events, oracle_resync::get_last_checked_block};
+use crate::{
+    ethereum_event_watcher::check_for_events, oracle_resync::get_last_checked_block,
+    watchdog::watchdog_loop,
+};
 
 /// The execution speed governing all loops in this file
 /// which is to say all loops started by Orchestrator main
 /// loop except the relayer loop
 pub const ETH_SIGNER_LOOP_SPEED: Duration = Duration::from_secs(11);
 pub const ETH_ORACLE_LOOP_SPEED: Duration = Duration::from_secs(13);
+/// How often the balance metrics are refreshed
+pub const BALANCE_METRICS_LOOP_SPEED: Duration = Duration::from_secs(60);
 
 /// This loop combines the three major roles required to make
 /// up the 'Orchestrator', all three of these are async loops
 /// meaning they will occupy the same thread, but since they do
 /// very little actual cpu bound work and spend the vast majority
 /// of all execution time sleeping this shouldn't be an issue at all.
+/// `config` is shared with the relayer, which applies changes to its section while
+/// running, see `relayer_main_loop`. The other sections are read once at the start
 #[allow(clippy::too_many_arguments)]
 pub async fn orchestrator_main_loop(
     cosmos_key: CosmosPrivateKey,
@@ -55,9 +68,10 @@ pub async fn orchestrator_main_loop(
     gravity_contract_address: EthAddress,
     gravity_id: String,
     user_fee_amount: Coin,
-    config: GravityBridgeToolsConfig,
+    config: watch::Receiver<GravityBridgeToolsConfig>,
 ) -> Result<(), GravityError> {
     let fee = user_fee_amount;
+    let startup_config = config.borrow().clone();
 
     let a = eth_oracle_main_loop(
         cosmos_key,
@@ -76,6 +90,26 @@ pub async fn orchestrator_main_loop(
         fee.clone(),
     );
 
+    let (balances, balances_rx) = watch::channel(Balances::default());
+    let d = balance_metrics_loop(
+        cosmos_key,
+        ethereum_key.to_address(),
+        web3.clone(),
+        contact.clone(),
+        fee.denom.clone(),
+        balances,
+    );
+
+    let e = watchdog_loop(
+        cosmos_key,
+        contact.clone(),
+        grpc_client.clone(),
+        fee.denom.clone(),
+        startup_config.watchdog,
+        startup_config.metrics,
+        balances_rx,
+    );
+
     let c = relayer_main_loop(
         ethereum_key,
         Some(cosmos_key),
@@ -85,15 +119,15 @@ pub async fn orchestrator_main_loop(
         grpc_client.clone(),
         gravity_contract_address,
         gravity_id,
-        &config.relayer,
+        config,
     );
 
     // if the relayer is not enabled we just don't start the future
-    if config.orchestrator.relayer_enabled {
-        if let Err(e) = try_join3(a, b, c).await {
+    if startup_config.orchestrator.relayer_enabled {
+        if let Err(e) = try_join5(a, b, c, d, e).await {
             return Err(e);
         }
-    } else if let Err(e) = try_join(a, b).await {
+    } else if let Err(e) = try_join4(a, b, d, e).await {
         return Err(e);
     }
 
@@ -129,14 +163,17 @@ pub async fn eth_oracle_main_loop(
     let mut last_checked_event = u256!(0);
     info!("Oracle resync complete, Oracle now operational");
     let mut grpc_client = grpc_client;
+    health_loop_started(LoopKind::EthOracle, ETH_SIGNER_LOOP_SPEED);
 
     loop {
         let _ = tokio::join!(
-            async {
-                let latest_eth_block = web3.eth_block_number().await;
-                let latest_cosmos_block = contact.get_chain_status().await;
+            metrics_loop(LoopKind::EthOracle, async {
+                let latest_eth_block =
+                    metrics_rpc("eth_blockNumber", Chain::Ethereum, web3.eth_block_number()).await;
+                let latest_cosmos_block =
+                    metrics_rpc("chain_status", Chain::Cosmos, contact.get_chain_status()).await;
 
-                match (latest_eth_block, latest_cosmos_block) {
+                let latest_eth_block = match (latest_eth_block, latest_cosmos_block) {
                     (Ok(latest_eth_block), Ok(ChainStatus::Moving { block_height })) => {
                         trace!(
                             "Latest Eth block {} Latest Cosmos block {}",
@@ -144,41 +181,52 @@ pub async fn eth_oracle_main_loop(
                             block_height,
                         );
 
-                        metrics_latest(block_height, "latest_cosmos_block");
-                        // Converting into u64
-                        metrics_latest(latest_eth_block.resize_to_u64(), "latest_eth_block");
+                        metrics_latest_block(Chain::Cosmos, block_height);
+                        metrics_latest_block(Chain::Ethereum, latest_eth_block.resize_to_u64());
+                        health_node_status(Chain::Ethereum, NodeStatus::Moving);
+                        health_node_status(Chain::Cosmos, NodeStatus::Moving);
+                        latest_eth_block
                     }
                     (Ok(_latest_eth_block), Ok(ChainStatus::Syncing)) => {
                         warn!("Cosmos node syncing, Eth oracle paused");
-                        metrics_warnings_counter(2, "Cosmos node syncing");
+                        metrics_warning(Subsystem::Cosmos, ErrorKind::NodeSyncing);
+                        health_node_status(Chain::Ethereum, NodeStatus::Moving);
+                        health_node_status(Chain::Cosmos, NodeStatus::Syncing);
                         sleep(DELAY).await;
                         return None;
                     }
                     (Ok(_latest_eth_block), Ok(ChainStatus::WaitingToStart)) => {
                         warn!("Cosmos node syncing waiting for chain start, Eth oracle paused");
-                        metrics_warnings_counter(2, "Cosmos node syncing waiting for chain start");
+                        metrics_warning(Subsystem::Cosmos, ErrorKind::ChainNotStarted);
+                        health_node_status(Chain::Ethereum, NodeStatus::Moving);
+                        health_node_status(Chain::Cosmos, NodeStatus::WaitingToStart);
                         sleep(DELAY).await;
                         return None;
                     }
                     (Ok(_), Err(_)) => {
                         warn!("Could not contact Cosmos grpc, trying again");
-                        metrics_warnings_counter(2, "Could not contact Cosmos grpc");
+                        metrics_warning(Subsystem::Cosmos, ErrorKind::RpcUnreachable);
+                        health_node_status(Chain::Ethereum, NodeStatus::Moving);
+                        health_node_status(Chain::Cosmos, NodeStatus::Unreachable);
                         sleep(DELAY).await;
                         return None;
                     }
                     (Err(e), Ok(_)) => {
                         warn!("Could not contact Eth node ({:?}), trying again", e);
-                        metrics_warnings_counter(1, "Could not contact Eth node");
+                        metrics_warning(Subsystem::Ethereum, ErrorKind::RpcUnreachable);
+                        health_node_status(Chain::Ethereum, NodeStatus::Unreachable);
                         sleep(DELAY).await;
                         return None;
                     }
                     (Err(_), Err(_)) => {
                         error!("Could not reach Ethereum or Cosmos rpc!");
-                        metrics_errors_counter(0, "Could not reach Ethereum or Cosmos rpc");
+                        metrics_error(Subsystem::EthereumAndCosmos, ErrorKind::RpcUnreachable);
+                        health_node_status(Chain::Ethereum, NodeStatus::Unreachable);
+                        health_node_status(Chain::Cosmos, NodeStatus::Unreachable);
                         sleep(DELAY).await;
                         return None;
                     }
-                }
+                };
 
                 // Relays events from Ethereum -> Cosmos
                 match check_for_events(
@@ -210,16 +258,36 @@ pub async fn eth_oracle_main_loop(
                             .await;
                         }
                         last_checked_event = nonces.event_nonce;
-                        metrics_latest(last_checked_event.resize_to_u64(), "last_checked_event");
+                        health_loop_completed(LoopKind::EthOracle);
+                        metrics_last_checked_event_nonce(last_checked_event.resize_to_u64());
+                        let lag_blocks = latest_eth_block
+                            .checked_sub(last_checked_block)
+                            .unwrap_or(u256!(0));
+                        let eth_event_nonce = metrics_rpc(
+                            "state_lastEventNonce",
+                            Chain::Ethereum,
+                            get_event_nonce(
+                                gravity_contract_address,
+                                gravity_contract_address,
+                                &web3,
+                            ),
+                        )
+                        .await;
+                        if let Ok(eth_event_nonce) = eth_event_nonce {
+                            metrics_oracle_lag(
+                                lag_blocks.resize_to_u64(),
+                                eth_event_nonce.saturating_sub(last_checked_event.resize_to_u64()),
+                            );
+                        }
                     }
                     Err(e) => {
                         error!("Failed to get events for block range, Check your Eth node and Cosmos gRPC {:?}", e);
-                        metrics_errors_counter(0, "Failed to get events for block range");
+                        metrics_error(Subsystem::EthereumAndCosmos, ErrorKind::EventQuery);
                     }
                 }
 
                 Some(())
-            },
+            }),
             tokio::time::sleep(ETH_SIGNER_LOOP_SPEED)
         );
     }
@@ -238,18 +306,25 @@ pub async fn eth_signer_main_loop(
 ) -> Result<(), GravityError> {
     let our_cosmos_address = cosmos_key.to_address(&contact.get_prefix()).unwrap();
     let mut grpc_client = grpc_client;
+    health_loop_started(LoopKind::EthSigner, ETH_SIGNER_LOOP_SPEED);
 
     loop {
         let (async_result, _) = tokio::join!(
-            async {
+            metrics_loop(LoopKind::EthSigner, async {
                 // repeatedly refreshing the parameters here maintains loop correctness
                 // if the gravity_id is changed or slashing windows are changed. Neither of these
                 // is very probable
-                let params = match get_gravity_params(&mut grpc_client).await {
+                let params = match metrics_rpc(
+                    "gravity_params",
+                    Chain::Cosmos,
+                    get_gravity_params(&mut grpc_client),
+                )
+                .await
+                {
                     Ok(p) => p,
                     Err(e) => {
                         error!("Failed to get Gravity parameters with {} correct your Cosmos gRPC connection immediately, you are risking slashing",e);
-                        metrics_errors_counter(2, "Failed to get Gravity parameters correct your Cosmos gRPC connection immediately, you are risking slashing");
+                        metrics_error(Subsystem::Cosmos, ErrorKind::ParamsQuery);
                         return Ok(());
                     }
                 };
@@ -258,35 +333,32 @@ pub async fn eth_signer_main_loop(
                     params.signed_logic_calls_window,
                 );
                 let gravity_id = params.gravity_id;
+                metrics_blocks_until_slashing(blocks_until_slashing);
 
-                let latest_cosmos_block = contact.get_chain_status().await;
+                let latest_cosmos_block =
+                    metrics_rpc("chain_status", Chain::Cosmos, contact.get_chain_status()).await;
                 match latest_cosmos_block {
                     Ok(ChainStatus::Moving { block_height }) => {
                         trace!("Latest Cosmos block {}", block_height,);
+                        // failures from here on are logged and counted as errors but the
+                        // signer is still able to do its work
+                        health_loop_completed(LoopKind::EthSigner);
                     }
                     Ok(ChainStatus::Syncing) => {
                         warn!("Cosmos node syncing, Eth signer paused");
                         warn!("If this operation will take more than {} blocks of time you must find another node to submit signatures or risk slashing", blocks_until_slashing);
-                        metrics_warnings_counter(2, "Cosmos node syncing, Eth signer paused");
-                        metrics_latest(blocks_until_slashing, "blocks_until_slashing");
+                        metrics_warning(Subsystem::Cosmos, ErrorKind::NodeSyncing);
                         sleep(DELAY).await;
                         return Ok(());
                     }
                     Ok(ChainStatus::WaitingToStart) => {
                         warn!("Cosmos node syncing waiting for chain start, Eth signer paused");
-                        metrics_warnings_counter(
-                            2,
-                            "Cosmos node syncing waiting for chain start, Eth signer paused",
-                        );
+                        metrics_warning(Subsystem::Cosmos, ErrorKind::ChainNotStarted);
                         sleep(DELAY).await;
                         return Ok(());
                     }
                     Err(_) => {
-                        metrics_latest(blocks_until_slashing, "blocks_until_slashing");
-                        metrics_errors_counter(
-                            2,
-                            "Could not reach Cosmos rpc! You must correct this or you risk being slashed",
-                        );
+                        metrics_error(Subsystem::Cosmos, ErrorKind::RpcUnreachable);
                         return Ok(());
                     }
                 }
@@ -304,10 +376,12 @@ pub async fn eth_signer_main_loop(
                             trace!("No validator sets to sign, node is caught up!")
                         } else {
                             info!(
+                                subsystem = "eth_signer", nonce = valsets[0].nonce;
                                 "Sending {} valset confirms starting with {}",
                                 valsets.len(),
                                 valsets[0].nonce
                             );
+                            let count = valsets.len();
                             let res = send_valset_confirms(
                                 &contact,
                                 ethereum_key,
@@ -318,6 +392,9 @@ pub async fn eth_signer_main_loop(
                             )
                             .await;
                             trace!("Valset confirm result is {:?}", res);
+                            if res.is_ok() {
+                                metrics_confirms_submitted(BridgeObject::Valset, count);
+                            }
                             return check_for_fee_error(res, &fee);
                         }
                     }
@@ -340,11 +417,15 @@ pub async fn eth_signer_main_loop(
                             trace!("No unsigned batch sets to sign, node is caught up!")
                         } else {
                             info!(
-                                "Sending {} valset confirms starting with {}",
+                                subsystem = "eth_signer",
+                                nonce = last_unsigned_batches[0].nonce,
+                                token_contract = last_unsigned_batches[0].token_contract.to_string();
+                                "Sending {} batch confirms starting with {}",
                                 last_unsigned_batches.len(),
                                 last_unsigned_batches[0].nonce
                             );
 
+                            let count = last_unsigned_batches.len();
                             let res = send_batch_confirm(
                                 &contact,
                                 ethereum_key,
@@ -355,6 +436,9 @@ pub async fn eth_signer_main_loop(
                             )
                             .await;
                             trace!("Batch confirm result is {:?}", res);
+                            if res.is_ok() {
+                                metrics_confirms_submitted(BridgeObject::Batch, count);
+                            }
                             return check_for_fee_error(res, &fee);
                         }
                     }
@@ -376,10 +460,13 @@ pub async fn eth_signer_main_loop(
                             trace!("No unsigned call sets to sign, node is caught up!")
                         } else {
                             info!(
-                                "Sending {} valset confirms starting with {}",
+                                subsystem = "eth_signer",
+                                nonce = last_unsigned_calls[0].invalidation_nonce;
+                                "Sending {} logic call confirms starting with {}",
                                 last_unsigned_calls.len(),
                                 last_unsigned_calls[0].invalidation_nonce
                             );
+                            let count = last_unsigned_calls.len();
                             let res = send_logic_call_confirm(
                                 &contact,
                                 ethereum_key,
@@ -390,6 +477,9 @@ pub async fn eth_signer_main_loop(
                             )
                             .await;
                             trace!("call confirm result is {:?}", res);
+                            if res.is_ok() {
+                                metrics_confirms_submitted(BridgeObject::LogicCall, count);
+                            }
                             return check_for_fee_error(res, &fee);
                         }
                     }
@@ -400,7 +490,7 @@ pub async fn eth_signer_main_loop(
                 }
 
                 Ok(())
-            },
+            }),
             sleep(ETH_SIGNER_LOOP_SPEED)
         );
 
@@ -410,6 +500,65 @@ pub async fn eth_signer_main_loop(
     }
 }
 
+/// The latest balances of the orchestrator keys read by `balance_metrics_loop`, a balance is
+/// None until it has been read once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Balances {
+    /// in wei
+    pub eth: Option<Uint256>,
+    /// in the smallest unit of the fee denom
+    pub fee: Option<Uint256>,
+}
+
+/// Keeps the balance metrics of the orchestrator keys up to date, the Ethereum key pays
+/// for relaying and the Cosmos key pays the fees on confirms and claims. Every reading is
+/// also published to `balances` for the watchdog
+pub async fn balance_metrics_loop(
+    cosmos_key: CosmosPrivateKey,
+    our_ethereum_address: EthAddress,
+    web3: Web3,
+    contact: Contact,
+    fee_denom: String,
+    balances: watch::Sender<Balances>,
+) -> Result<(), GravityError> {
+    let our_cosmos_address = cosmos_key.to_address(&contact.get_prefix()).unwrap();
+    loop {
+        let (eth_balance, cosmos_balances) = tokio::join!(
+            metrics_rpc(
+                "eth_getBalance",
+                Chain::Ethereum,
+                web3.eth_get_balance(our_ethereum_address)
+            ),
+            metrics_rpc(
+                "bank_balances",
+                Chain::Cosmos,
+                contact.get_balances(our_cosmos_address)
+            ),
+        );
+        match eth_balance {
+            Ok(balance) => {
+                metrics_balance("eth", balance, 18);
+                balances.send_modify(|b| b.eth = Some(balance));
+            }
+            Err(e) => trace!("Failed to get our Ethereum balance {:?}", e),
+        }
+        match cosmos_balances {
+            Ok(coins) => {
+                let balance = coins
+                    .into_iter()
+                    .find(|c| c.denom == fee_denom)
+                    .map(|c| c.amount)
+                    .unwrap_or(u256!(0));
+                // Cosmos balances are recorded in the base denom
+                metrics_balance(&fee_denom, balance, 0);
+                balances.send_modify(|b| b.fee = Some(balance));
+            }
+            Err(e) => trace!("Failed to get our Cosmos balances {:?}", e),
+        }
+        sleep(BALANCE_METRICS_LOOP_SPEED).await;
+    }
+}
+
 /// Checks for fee errors on our confirm submission transactions, a failure here
 /// can be fatal and cause slashing so we want to warn the user and exit. There is
 /// no point in running if we can't perform our most important function
@@ -434,6 +583,7 @@ fn check_for_fee_error(
     } else if res.is_err() {
         let error = res.err();
         error!("{:?}", error);
+        metrics_error(Subsystem::Cosmos, ErrorKind::ConfirmSubmit);
     }
 
     Ok(())
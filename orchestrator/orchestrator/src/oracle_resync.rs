@@ -11,7 +11,7 @@ use gravity_utils::{
     u64_array_bigints,
     web30::client::Web3,
 };
-use metrics_exporter::metrics_errors_counter;
+use metrics_exporter::{metrics_error, ErrorKind, Subsystem};
 use tokio::time::sleep as delay_for;
 use tonic::transport::Channel;
 
@@ -103,7 +103,7 @@ pub async fn get_last_checked_block(
         {
             error!("Failed to get blockchain events while resyncing, is your Eth node working? If you see only one of these it's fine",);
             delay_for(RETRY_TIME).await;
-            metrics_errors_counter(1, "Failed to get blockchain events while resyncing");
+            metrics_error(Subsystem::Ethereum, ErrorKind::EventQuery);
             continue;
         }
         let batch_events = batch_events.unwrap();
@@ -132,7 +132,7 @@ pub async fn get_last_checked_block(
                 }
                 Err(e) => {
                     error!("Got batch event that we can't parse {}", e);
-                    metrics_errors_counter(1, "Got batch event that we can't parse");
+                    metrics_error(Subsystem::Ethereum, ErrorKind::EventParse);
                 }
             }
         }
@@ -152,7 +152,7 @@ pub async fn get_last_checked_block(
                 }
                 Err(e) => {
                     error!("Got SendToCosmos event that we can't parse {}", e);
-                    metrics_errors_counter(3, "Got SendToCosmos event that we can't parse");
+                    metrics_error(Subsystem::Ethereum, ErrorKind::EventParse);
                 }
             }
         }
@@ -172,7 +172,7 @@ pub async fn get_last_checked_block(
                 }
                 Err(e) => {
                     error!("Got ERC20Deployed event that we can't parse {}", e);
-                    metrics_errors_counter(3, "Got ERC20Deployed event that we can't parse");
+                    metrics_error(Subsystem::Ethereum, ErrorKind::EventParse);
                 }
             }
         }
@@ -192,7 +192,7 @@ pub async fn get_last_checked_block(
                 }
                 Err(e) => {
                     error!("Got ERC20Deployed event that we can't parse {}", e);
-                    metrics_errors_counter(3, "Got ERC20Deployed event that we can't parse");
+                    metrics_error(Subsystem::Ethereum, ErrorKind::EventParse);
                 }
             }
         }
@@ -229,7 +229,7 @@ pub async fn get_last_checked_block(
                 }
                 Err(e) => {
                     error!("Got valset event that we can't parse {}", e);
-                    metrics_errors_counter(3, "Got valset event that we can't parse");
+                    metrics_error(Subsystem::Ethereum, ErrorKind::EventParse);
                 }
             }
         }
@@ -0,0 +1,397 @@
+//! The watchdog periodically checks the balances of the orchestrator keys and how far behind the
+//! orchestrator is on signing, `check_for_fee` and `check_for_eth` only run at startup and the
+//! signer only warns about slashing while the node is syncing. Balances are the ones last read
+//! by `balance_metrics_loop`, checked against the same `[metrics]` thresholds as /healthz.
+//! Alerts are logged and sent to every configured `Notifier`
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use cosmos_gravity::query::{get_gravity_params, get_oldest_unsigned_heights, UnsignedHeights};
+use gravity_proto::gravity::{query_client::QueryClient as GravityQueryClient, Params};
+use gravity_utils::{
+    clarity::Uint256,
+    deep_space::{client::ChainStatus, private_key::PrivateKey as CosmosPrivateKey, Contact},
+    error::GravityError,
+    types::{MetricsConfig, WatchdogConfig},
+};
+use hyper::{Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use serde::Serialize;
+use tokio::{process::Command, sync::watch, time::sleep};
+use tonic::transport::Channel;
+
+use crate::main_loop::Balances;
+
+/// How long a notifier may take to deliver an alert
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    LowFeeBalance,
+    LowEthBalance,
+    UnsignedValset,
+    UnsignedBatch,
+    UnsignedLogicCall,
+    ValsetSlashingRisk,
+    BatchSlashingRisk,
+    LogicCallSlashingRisk,
+}
+
+/// The JSON payload sent to notifiers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Alert {
+    pub kind: AlertKind,
+    /// the Cosmos address of the orchestrator
+    pub orchestrator: String,
+    pub message: String,
+    pub value: String,
+    pub threshold: String,
+}
+
+/// Where alerts are sent, every alert is also logged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notifier {
+    /// POSTs the alert as JSON to this URL
+    Webhook(String),
+    /// Runs this command with `sh -c`, with the alert as JSON in `GBT_ALERT`
+    Command(String),
+}
+
+impl Notifier {
+    pub fn from_config(config: &WatchdogConfig) -> Vec<Notifier> {
+        let mut notifiers = Vec::new();
+        if let Some(url) = &config.webhook {
+            notifiers.push(Notifier::Webhook(url.clone()));
+        }
+        if let Some(command) = &config.command {
+            notifiers.push(Notifier::Command(command.clone()));
+        }
+        notifiers
+    }
+
+    pub async fn notify(&self, alert: &Alert) -> Result<(), GravityError> {
+        let payload = serde_json::to_string(alert).unwrap();
+        match tokio::time::timeout(NOTIFY_TIMEOUT, self.send(payload)).await {
+            Ok(res) => res,
+            Err(_) => Err(GravityError::RecoverableError(format!(
+                "Timed out sending alert to {self:?}"
+            ))),
+        }
+    }
+
+    async fn send(&self, payload: String) -> Result<(), GravityError> {
+        match self {
+            Notifier::Webhook(url) => {
+                let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+                let request = Request::post(url)
+                    .header("content-type", "application/json")
+                    .body(Body::from(payload))
+                    .map_err(|e| {
+                        GravityError::ValidationError(format!("Invalid webhook {url} {e:?}"))
+                    })?;
+                let response = client.request(request).await.map_err(|e| {
+                    GravityError::RecoverableError(format!("Failed to POST to {url} {e:?}"))
+                })?;
+                if !response.status().is_success() {
+                    return Err(GravityError::RecoverableError(format!(
+                        "Webhook {url} answered {}",
+                        response.status()
+                    )));
+                }
+                Ok(())
+            }
+            Notifier::Command(command) => {
+                let status = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("GBT_ALERT", payload)
+                    .status()
+                    .await
+                    .map_err(|e| {
+                        GravityError::RecoverableError(format!("Failed to run {command} {e:?}"))
+                    })?;
+                if !status.success() {
+                    return Err(GravityError::RecoverableError(format!(
+                        "Alert command {command} exited with {status}"
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Runs the watchdog until the orchestrator exits, returns immediately if it is disabled.
+/// An alert that stays active is sent again every `repeat_interval` seconds, an alert that
+/// clears and comes back is sent right away. The balance thresholds are `min_eth_balance` and
+/// `min_fee_balance` in `metrics_config`
+pub async fn watchdog_loop(
+    cosmos_key: CosmosPrivateKey,
+    contact: Contact,
+    grpc_client: GravityQueryClient<Channel>,
+    fee_denom: String,
+    config: WatchdogConfig,
+    metrics_config: MetricsConfig,
+    balances: watch::Receiver<Balances>,
+) -> Result<(), GravityError> {
+    if !config.enabled {
+        return Ok(());
+    }
+    let our_cosmos_address = cosmos_key.to_address(&contact.get_prefix()).unwrap();
+    let notifiers = Notifier::from_config(&config);
+    let repeat_interval = Duration::from_secs(config.repeat_interval);
+    let mut grpc_client = grpc_client;
+    let mut params_client = grpc_client.clone();
+    let mut last_sent = HashMap::new();
+    info!("Watchdog started, sending alerts to {:?}", notifiers);
+
+    loop {
+        let (chain_status, params, unsigned) = tokio::join!(
+            contact.get_chain_status(),
+            get_gravity_params(&mut params_client),
+            get_oldest_unsigned_heights(&mut grpc_client, our_cosmos_address, contact.get_prefix()),
+        );
+
+        let mut checks = Vec::new();
+        let Balances { eth, fee } = *balances.borrow();
+        if let Some(balance) = fee {
+            checks.extend(check_balance(
+                AlertKind::LowFeeBalance,
+                balance,
+                metrics_config.min_fee_balance,
+                &fee_denom,
+            ));
+        }
+        if let Some(balance) = eth {
+            checks.extend(check_balance(
+                AlertKind::LowEthBalance,
+                balance,
+                metrics_config.min_eth_balance,
+                "wei",
+            ));
+        }
+        match (chain_status, params, unsigned) {
+            (Ok(ChainStatus::Moving { block_height }), Ok(params), Ok(unsigned)) => {
+                checks.extend(check_unsigned(&config, &params, unsigned, block_height))
+            }
+            (Ok(_), Ok(_), Ok(_)) => trace!("Watchdog skipping signing checks, node not synced"),
+            (status, params, unsigned) => warn!(
+                "Watchdog failed to query signing status {:?} {:?} {:?}",
+                status.err(),
+                params.err(),
+                unsigned.err()
+            ),
+        }
+
+        // forget alerts that cleared so they are sent as soon as they come back
+        last_sent.retain(|kind, _| checks.iter().any(|(k, _, _, _)| k == kind));
+        for (kind, message, value, threshold) in checks {
+            if let Some(sent) = last_sent.get(&kind) {
+                if sent.elapsed() < repeat_interval {
+                    continue;
+                }
+            }
+            warn!("Watchdog: {}", message);
+            let alert = Alert {
+                kind,
+                orchestrator: our_cosmos_address.to_string(),
+                message,
+                value,
+                threshold,
+            };
+            for notifier in notifiers.iter() {
+                if let Err(e) = notifier.notify(&alert).await {
+                    error!("Failed to send watchdog alert {:?}", e);
+                }
+            }
+            last_sent.insert(kind, Instant::now());
+        }
+
+        sleep(Duration::from_secs(config.interval)).await;
+    }
+}
+
+/// An alert before it is addressed to an orchestrator: kind, message, value and threshold
+type Check = (AlertKind, String, String, String);
+
+fn check_balance(kind: AlertKind, balance: Uint256, min: Uint256, unit: &str) -> Option<Check> {
+    if balance >= min {
+        return None;
+    }
+    let message = match kind {
+        AlertKind::LowFeeBalance => format!(
+            "Fee balance {balance}{unit} is below {min}{unit}, confirms and claims will fail and you will be slashed"
+        ),
+        _ => format!(
+            "Ethereum balance {balance} {unit} is below {min} {unit}, relaying will fail"
+        ),
+    };
+    Some((kind, message, balance.to_string(), min.to_string()))
+}
+
+/// Checks the age of the oldest unsigned valset, batch and logic call against
+/// `max_unsigned_age` and the blocks left in their slashing windows against `slashing_margin`
+fn check_unsigned(
+    config: &WatchdogConfig,
+    params: &Params,
+    unsigned: UnsignedHeights,
+    block_height: u64,
+) -> Vec<Check> {
+    let objects = [
+        (
+            "valset",
+            unsigned.valset,
+            params.signed_valsets_window,
+            AlertKind::UnsignedValset,
+            AlertKind::ValsetSlashingRisk,
+        ),
+        (
+            "batch",
+            unsigned.batch,
+            params.signed_batches_window,
+            AlertKind::UnsignedBatch,
+            AlertKind::BatchSlashingRisk,
+        ),
+        (
+            "logic call",
+            unsigned.logic_call,
+            params.signed_logic_calls_window,
+            AlertKind::UnsignedLogicCall,
+            AlertKind::LogicCallSlashingRisk,
+        ),
+    ];
+    let mut checks = Vec::new();
+    for (name, created, window, unsigned_kind, slashing_kind) in objects {
+        let created = match created {
+            Some(created) => created,
+            None => continue,
+        };
+        let age = block_height.saturating_sub(created);
+        let blocks_left = window.saturating_sub(age);
+        if blocks_left < config.slashing_margin {
+            checks.push((
+                slashing_kind,
+                format!("The oldest unsigned {name} can be slashed in {blocks_left} blocks, check your orchestrator immediately"),
+                blocks_left.to_string(),
+                config.slashing_margin.to_string(),
+            ));
+        } else if age > config.max_unsigned_age {
+            checks.push((
+                unsigned_kind,
+                format!("The oldest unsigned {name} is {age} blocks old, your orchestrator is not signing"),
+                age.to_string(),
+                config.max_unsigned_age.to_string(),
+            ));
+        }
+    }
+    checks
+}
+
+#[test]
+fn test_watchdog_checks() {
+    let config = WatchdogConfig {
+        max_unsigned_age: 10,
+        slashing_margin: 100,
+        ..Default::default()
+    };
+    let params = Params {
+        signed_valsets_window: 1000,
+        signed_batches_window: 1000,
+        signed_logic_calls_window: 1000,
+        ..Default::default()
+    };
+
+    assert!(check_balance(
+        AlertKind::LowFeeBalance,
+        Uint256::from_u64(100),
+        Uint256::from_u64(100),
+        "anom"
+    )
+    .is_none());
+    let (kind, _, value, threshold) = check_balance(
+        AlertKind::LowFeeBalance,
+        Uint256::from_u64(99),
+        Uint256::from_u64(100),
+        "anom",
+    )
+    .unwrap();
+    assert_eq!(kind, AlertKind::LowFeeBalance);
+    assert_eq!((value.as_str(), threshold.as_str()), ("99", "100"));
+
+    assert!(check_unsigned(&config, &params, UnsignedHeights::default(), 5000).is_empty());
+    let unsigned = UnsignedHeights {
+        valset: Some(4995),
+        batch: Some(4980),
+        logic_call: Some(4050),
+    };
+    let kinds: Vec<AlertKind> = check_unsigned(&config, &params, unsigned, 5000)
+        .into_iter()
+        .map(|(kind, _, _, _)| kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![AlertKind::UnsignedBatch, AlertKind::LogicCallSlashingRisk]
+    );
+}
+
+#[tokio::test]
+async fn test_webhook_notifier() {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    // a stub that answers one request and hands back everything it received
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+    let stub = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length: usize = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    socket
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await
+                        .unwrap();
+                    return text;
+                }
+            }
+        }
+    });
+
+    let alert = Alert {
+        kind: AlertKind::LowEthBalance,
+        orchestrator: "onomy1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc59rcghe".to_string(),
+        message: "low".to_string(),
+        value: "1".to_string(),
+        threshold: "2".to_string(),
+    };
+    Notifier::Webhook(url).notify(&alert).await.unwrap();
+    let request = stub.await.unwrap();
+    assert!(request.starts_with("POST /alerts HTTP/1.1"));
+    assert!(request.ends_with(&serde_json::to_string(&alert).unwrap()));
+    assert!(request.contains("\"kind\":\"low_eth_balance\""));
+
+    // nothing listens on port 1
+    assert!(Notifier::Webhook("http://127.0.0.1:1/alerts".to_string())
+        .notify(&alert)
+        .await
+        .is_err());
+}
@@ -1,32 +1,45 @@
 //! Protobuf files in the gravity repo, copying the result to the gravity_proto crate for import
 //! and use. While this builder generates about a dozen files only one contains all the gravity
 //! proto info and the rest are discarded in favor of upstream cosmos-sdk-proto
-
-// Building new Gravity rust proto definitions
-// run 'cargo run'
-// go to gravity_proto/prost
-// delete all files except gravity.v1.rs
-// re-write calls to super::super::cosmos as cosmos-sdk-proto::cosmos
+//!
+//! The servers for the Cosmos SDK services the mock chain serves are generated as well, from the
+//! service definitions in `mock_chain/proto`, and copied to the mock_chain crate
+//!
+//! Regenerating the Rust bindings after editing a proto file is a single command run from the
+//! orchestrator folder
+//!
+//! ```text
+//! cargo run -p proto_build
+//! ```
+//!
+//! and `cargo run -p proto_build -- --check` regenerates into a temporary directory and fails if
+//! the result differs from the committed files, which is how CI catches bindings that have
+//! drifted from `module/proto` or `mock_chain/proto`
 
 use std::{
-    ffi::OsStr,
-    fs::{self, create_dir_all, remove_dir_all},
+    env,
+    fs::{self, create_dir_all, remove_file},
     io,
     path::{Path, PathBuf},
+    process,
 };
 
 use regex::Regex;
 use walkdir::WalkDir;
 
-/// Protos belonging to these Protobuf packages will be excluded
-/// (i.e. because they are sourced from `tendermint-proto` or `cosmos-sdk-proto`)
-const EXCLUDED_PROTO_PACKAGES: &[&str] = &[
-    "gogoproto",
-    "google",
-    "tendermint",
-    "cosmos.base",
-    "cosmos_proto",
+/// The generated files gravity_proto includes, everything else prost produces is sourced from
+/// `cosmos-sdk-proto` or `tendermint-proto` and discarded
+const GENERATED_FILES: &[&str] = &["gravity.v1.rs"];
+/// The generated Cosmos SDK service servers mock_chain includes
+const MOCK_CHAIN_GENERATED_FILES: &[&str] = &[
+    "cosmos.auth.v1beta1.rs",
+    "cosmos.base.tendermint.v1beta1.rs",
+    "cosmos.tx.v1beta1.rs",
 ];
+/// Packages whose messages the mock chain services take from `cosmos-sdk-proto` instead of
+/// generating them
+const MOCK_CHAIN_EXTERN_PATHS: &[(&str, &str)] =
+    &[(".cosmos", "::gravity_proto::cosmos_sdk_proto::cosmos")];
 /// Attribute preceeding a Tonic client definition
 const TONIC_CLIENT_ATTRIBUTE: &str = "#[doc = r\" Generated client implementations.\"]";
 /// Attributes to add to gRPC clients
@@ -38,24 +51,103 @@ const GRPC_CLIENT_ATTRIBUTES: &[&str] = &[
 /// Regex for locating instances of `cosmos-sdk-proto` in prost/tonic build output
 const COSMOS_SDK_PROTO_REGEX: &str = "(super::)+cosmos";
 
-/// A temporary directory for proto building
-const TMP_PATH: &str = "/tmp/gravity/";
-/// the output directory
+/// The output directory, relative to this crate so the builder works from any directory
 const OUT_PATH: &str = "../gravity_proto/src/prost/";
+/// The output directory of the mock chain services, relative to this crate
+const MOCK_CHAIN_OUT_PATH: &str = "../mock_chain/src/prost/";
+
+/// Bindings generated into one crate, the output directory only ever holds `files`
+struct Output {
+    /// names the build directory of this output
+    name: &'static str,
+    files: &'static [&'static str],
+    out_path: &'static str,
+    /// compiles the protos into the given directory
+    compile: fn(&Path),
+}
+
+const OUTPUTS: &[Output] = &[
+    Output {
+        name: "gravity",
+        files: GENERATED_FILES,
+        out_path: OUT_PATH,
+        compile: compile_protos,
+    },
+    Output {
+        name: "mock_chain",
+        files: MOCK_CHAIN_GENERATED_FILES,
+        out_path: MOCK_CHAIN_OUT_PATH,
+        compile: compile_mock_chain_protos,
+    },
+];
 
-// All paths must end with a / and either be absolute or include a ./ to reference the current
-// working directory.
+const USAGE: &str = "Usage: proto_build [--check]
+
+Regenerates the gravity_proto Rust bindings from module/proto and the mock_chain service
+servers from mock_chain/proto
+
+Options:
+  --check  Regenerate into a temporary directory and exit with an error if the result differs
+           from the committed bindings, nothing is written
+  --help   Print this message";
 
 fn main() {
-    let out_path = Path::new(&OUT_PATH);
-    let tmp_path = Path::new(&TMP_PATH);
-    compile_protos(out_path, tmp_path);
+    let mut check = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => {
+                eprintln!("[error] Unknown argument '{arg}'\n\n{USAGE}");
+                process::exit(2);
+            }
+        }
+    }
+
+    let root: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+    // a fresh directory every run, so files left over from an earlier build can never end up in
+    // the output
+    let tmp_dir = tempfile::tempdir().expect("Failed to create a temporary directory");
+    let mut drifted = Vec::new();
+    for output in OUTPUTS {
+        let build_dir = tmp_dir.path().join(output.name).join("build");
+        let generated_dir = tmp_dir.path().join(output.name).join("generated");
+        let out_dir = root.join(output.out_path);
+
+        (output.compile)(&build_dir);
+        copy_generated_files(output.files, &build_dir, &generated_dir);
+
+        if check {
+            drifted.extend(check_generated_files(
+                output.files,
+                &generated_dir,
+                &out_dir,
+            ));
+        } else {
+            install_generated_files(output.files, &generated_dir, &out_dir);
+        }
+    }
+
+    if check {
+        if !drifted.is_empty() {
+            eprintln!(
+                "[error] {} out of date, run `cargo run -p proto_build` and commit the result",
+                drifted.join(", ")
+            );
+            process::exit(1);
+        }
+        println!("[info ] Generated files are up to date");
+    }
+    println!("[info ] => Done!");
 }
 
-fn compile_protos(out_dir: &Path, tmp_dir: &Path) {
+fn compile_protos(tmp_dir: &Path) {
     println!(
-        "[info] Compiling .proto files to Rust into '{}'...",
-        out_dir.display()
+        "[info ] Compiling .proto files to Rust into '{}'...",
+        tmp_dir.display()
     );
 
     let root = env!("CARGO_MANIFEST_DIR");
@@ -76,11 +168,12 @@ fn compile_protos(out_dir: &Path, tmp_dir: &Path) {
     // which insists that any passed file be included in a directory passed as an include
     let proto_include_paths = [gravity_proto_include_dir, third_party_proto_include_dir];
 
-    // List available proto files
+    // List available proto files, sorted so protoc sees them in the same order on every machine
     let mut protos: Vec<PathBuf> = vec![];
     for proto_path in &proto_paths {
         protos.append(
             &mut WalkDir::new(proto_path)
+                .sort_by_file_name()
                 .into_iter()
                 .filter_map(|e| e.ok())
                 .filter(|e| {
@@ -104,39 +197,59 @@ fn compile_protos(out_dir: &Path, tmp_dir: &Path) {
         .compile_protos(&protos, &proto_include_paths)
         .unwrap();
 
-    // Compile all proto client for GRPC services
-    println!("[info ] Compiling proto clients for GRPC services!");
+    // Compile all proto clients and servers for GRPC services, the servers are used by the
+    // mock chain in tests
+    println!("[info ] Compiling proto clients and servers for GRPC services!");
     tonic_build::configure()
         .build_client(true)
-        .build_server(false)
+        .build_server(true)
         .out_dir(tmp_dir)
         .compile(&protos, &proto_include_paths)
         .unwrap();
+}
 
-    copy_generated_files(tmp_dir, out_dir);
+/// Compiles the servers for the Cosmos SDK services in `mock_chain/proto`, their messages are
+/// taken from cosmos-sdk-proto so only the services are generated
+fn compile_mock_chain_protos(tmp_dir: &Path) {
+    println!(
+        "[info ] Compiling mock chain service servers to Rust into '{}'...",
+        tmp_dir.display()
+    );
 
-    println!("[info ] => Done!");
-}
+    let root: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+    let proto_dir = root.parent().unwrap().join("mock_chain/proto");
+    let protos: Vec<PathBuf> = WalkDir::new(&proto_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension() == Some("proto".as_ref()))
+        .map(|e| e.into_path())
+        .collect();
 
-fn copy_generated_files(from_dir: &Path, to_dir: &Path) {
-    println!("Copying generated files into '{}'...", to_dir.display());
+    fs::create_dir_all(tmp_dir)
+        .unwrap_or_else(|_| panic!("Failed to create {:?}", tmp_dir.to_str()));
 
-    // Remove old compiled files
-    remove_dir_all(to_dir).unwrap_or_default();
-    create_dir_all(to_dir).unwrap();
+    let mut builder = tonic_build::configure()
+        .build_client(false)
+        .build_server(true)
+        .out_dir(tmp_dir);
+    for (proto_path, rust_path) in MOCK_CHAIN_EXTERN_PATHS {
+        builder = builder.extern_path(*proto_path, *rust_path);
+    }
+    builder.compile(&protos, &[proto_dir]).unwrap();
+}
 
-    let mut filenames = Vec::new();
+/// Copies `files` out of the build directory, patching them for use with cosmos-sdk-proto
+fn copy_generated_files(files: &[&str], from_dir: &Path, to_dir: &Path) {
+    println!(
+        "[info ] Patching generated files into '{}'...",
+        to_dir.display()
+    );
+    create_dir_all(to_dir).unwrap();
 
-    // Copy new compiled files (prost does not use folder structures)
-    let errors = WalkDir::new(from_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .map(|e| {
-            let filename = e.file_name().to_os_string().to_str().unwrap().to_string();
-            filenames.push(filename.clone());
-            copy_and_patch(e.path(), format!("{}/{}", to_dir.display(), &filename))
-        })
+    let errors = files
+        .iter()
+        .map(|filename| copy_and_patch(from_dir.join(filename), to_dir.join(filename)))
         .filter_map(|e| e.err())
         .collect::<Vec<_>>();
 
@@ -150,19 +263,10 @@ fn copy_generated_files(from_dir: &Path, to_dir: &Path) {
 }
 
 fn copy_and_patch(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> io::Result<()> {
-    // Skip proto files belonging to `EXCLUDED_PROTO_PACKAGES`
-    for package in EXCLUDED_PROTO_PACKAGES {
-        if let Some(filename) = src.as_ref().file_name().and_then(OsStr::to_str) {
-            if filename.starts_with(&format!("{package}.")) {
-                return Ok(());
-            }
-        }
-    }
-
     let contents = fs::read_to_string(src)?;
 
-    // `prost-build` output references types from `tendermint-proto` crate
-    // relative paths, which we need to munge into `tendermint_proto` in
+    // `prost-build` output references types from `cosmos-sdk-proto` through relative paths
+    // like `super::super::cosmos`, which we need to munge into `cosmos_sdk_proto::cosmos` in
     // order to leverage types from the upstream crate.
     let contents = Regex::new(COSMOS_SDK_PROTO_REGEX)
         .unwrap()
@@ -174,3 +278,135 @@ fn copy_and_patch(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> io::Result<(
 
     fs::write(dest, patched_contents)
 }
+
+/// Replaces the committed bindings with the freshly generated ones and prunes any other file
+/// from the output directory
+fn install_generated_files(files: &[&str], from_dir: &Path, to_dir: &Path) {
+    println!(
+        "[info ] Copying generated files into '{}'...",
+        to_dir.display()
+    );
+    create_dir_all(to_dir).unwrap();
+
+    for entry in fs::read_dir(to_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let keep = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .map(|f| files.contains(&f))
+            .unwrap_or(false);
+        if path.is_file() && !keep {
+            println!("[info ] Removing stale file '{}'", path.display());
+            remove_file(&path).unwrap();
+        }
+    }
+    for filename in files {
+        fs::copy(from_dir.join(filename), to_dir.join(filename)).unwrap_or_else(|e| {
+            panic!("[error] Failed to copy {}: {}", filename, e);
+        });
+    }
+}
+
+/// Compares the freshly generated bindings with the committed ones, printing the first
+/// difference in each file, and returns the names of the files that differ. Files in the
+/// output directory that the builder would prune count as drift as well
+fn check_generated_files(
+    files: &[&str],
+    generated_dir: &Path,
+    committed_dir: &Path,
+) -> Vec<String> {
+    println!(
+        "[info ] Checking generated files against '{}'...",
+        committed_dir.display()
+    );
+    let mut drifted = Vec::new();
+
+    for filename in files {
+        let generated = fs::read_to_string(generated_dir.join(filename)).unwrap();
+        let committed = match fs::read_to_string(committed_dir.join(filename)) {
+            Ok(committed) => committed,
+            Err(e) => {
+                eprintln!("[error] {filename} is missing from the committed bindings: {e}");
+                drifted.push(filename.to_string());
+                continue;
+            }
+        };
+        if let Some(difference) = first_difference(&committed, &generated) {
+            eprintln!("[error] {filename} differs from the generated bindings\n{difference}");
+            drifted.push(filename.to_string());
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(committed_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_file() && !files.contains(&filename.as_str()) {
+                eprintln!("[error] {filename} is not generated and would be removed");
+                drifted.push(filename);
+            }
+        }
+    }
+
+    drifted
+}
+
+/// Describes the first line where `committed` and `generated` disagree, or None if they match
+fn first_difference(committed: &str, generated: &str) -> Option<String> {
+    if committed == generated {
+        return None;
+    }
+    let mut committed_lines = committed.lines();
+    let mut generated_lines = generated.lines();
+    let mut line = 1;
+    loop {
+        match (committed_lines.next(), generated_lines.next()) {
+            (Some(c), Some(g)) if c == g => line += 1,
+            (None, None) => return Some("  files differ only in line endings".to_string()),
+            (c, g) => {
+                return Some(format!(
+                    "  line {line}\n  - committed: {}\n  + generated: {}",
+                    c.unwrap_or("<end of file>"),
+                    g.unwrap_or("<end of file>")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_difference() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            first_difference("a\nb\nc\n", "a\nx\nc\n"),
+            Some("  line 2\n  - committed: b\n  + generated: x".to_string())
+        );
+        assert_eq!(
+            first_difference("a\n", "a\nb\n"),
+            Some("  line 2\n  - committed: <end of file>\n  + generated: b".to_string())
+        );
+        assert!(first_difference("a\n", "a\r\n").is_some());
+    }
+
+    #[test]
+    fn test_cosmos_paths_are_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.rs");
+        let dest = dir.path().join("dest.rs");
+        fs::write(
+            &src,
+            "pub amount: Option<super::super::cosmos::base::v1beta1::Coin>,\n\
+             pub meta: Option<super::cosmos::bank::v1beta1::Metadata>,\n",
+        )
+        .unwrap();
+        copy_and_patch(&src, &dest).unwrap();
+        assert_eq!(
+            fs::read_to_string(dest).unwrap(),
+            "pub amount: Option<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,\n\
+             pub meta: Option<cosmos_sdk_proto::cosmos::bank::v1beta1::Metadata>,\n"
+        );
+    }
+}
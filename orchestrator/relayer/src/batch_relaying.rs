@@ -17,9 +17,12 @@ use gravity_utils::{
     },
     web30::client::Web3,
 };
+use metrics_exporter::{metrics_oldest_unrelayed_batch_age, metrics_relayer_pending, BridgeObject};
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use tonic::transport::Channel;
 
+use crate::metrics::{record_decision, relay_with_metrics};
+
 #[derive(Debug, Clone)]
 struct SubmittableBatch {
     batch: TransactionBatch,
@@ -33,7 +36,8 @@ struct SubmittableBatch {
 /// far as signatures and then make requests to Ethereum to determine which are
 /// valid to submit given the current chain state. From there we simulate a submission
 /// and if that succeeds and we like the gas cost we complete the relaying process and
-/// actually submit the data to Ethereum
+/// actually submit the data to Ethereum. `cosmos_block_height` is used to report the
+/// age of the oldest batch waiting to be relayed
 pub async fn relay_batches(
     // the validator set currently in the contract on Ethereum
     current_valset: &Valset,
@@ -44,12 +48,22 @@ pub async fn relay_batches(
     gravity_id: String,
     timeout: Duration,
     config: &RelayerConfig,
+    cosmos_block_height: Option<u64>,
 ) {
     let possible_batches =
         get_batches_and_signatures(current_valset, grpc_client, gravity_id.clone()).await;
 
     trace!("possible batches {:?}", possible_batches);
 
+    // batches are removed from Cosmos once their execution is observed, so every signed
+    // batch here is still waiting to be relayed
+    let pending = possible_batches.values().flatten();
+    metrics_relayer_pending(BridgeObject::Batch, pending.clone().count());
+    if let Some(height) = cosmos_block_height {
+        let oldest = pending.map(|b| b.batch.block).min().unwrap_or(height);
+        metrics_oldest_unrelayed_batch_age(height.saturating_sub(oldest));
+    }
+
     submit_batches(
         current_valset,
         ethereum_key,
@@ -293,6 +307,11 @@ async fn submit_batches(
                 let cost = cost.unwrap();
 
                 info!(
+                    subsystem = "relayer",
+                    nonce = oldest_signed_batch.nonce,
+                    token_contract = oldest_signed_batch.token_contract.to_string(),
+                    gas = cost.gas.to_string(),
+                    cost_wei = cost.get_total().to_string();
                     "We have detected a batch to relay. This batch is estimated to cost {} Gas @ {} gwei / {:.4} ETH to submit",
                     cost.gas,
                     print_gwei(cost.gas_price),
@@ -310,17 +329,30 @@ async fn submit_batches(
                     &config.batch_relaying_mode,
                 )
                 .await;
+                record_decision(
+                    BridgeObject::Batch,
+                    Some(oldest_signed_batch.token_contract),
+                    should_relay,
+                );
 
                 if should_relay {
-                    let res = send_eth_transaction_batch(
-                        current_valset,
-                        oldest_signed_batch,
-                        &oldest_signatures,
+                    let res = relay_with_metrics(
+                        BridgeObject::Batch,
+                        oldest_signed_batch.nonce,
+                        Some(oldest_signed_batch.token_contract),
+                        cost,
+                        our_ethereum_address,
                         web3,
-                        timeout,
-                        gravity_contract_address,
-                        gravity_id.clone(),
-                        ethereum_key,
+                        send_eth_transaction_batch(
+                            current_valset,
+                            oldest_signed_batch,
+                            &oldest_signatures,
+                            web3,
+                            timeout,
+                            gravity_contract_address,
+                            gravity_id.clone(),
+                            ethereum_key,
+                        ),
                     )
                     .await;
                     if res.is_err() {
@@ -328,6 +360,10 @@ async fn submit_batches(
                     }
                 } else {
                     info!(
+                        subsystem = "relayer",
+                        nonce = oldest_signed_batch.nonce,
+                        token_contract = oldest_signed_batch.token_contract.to_string(),
+                        outcome = "skipped";
                         "Not relaying batch {}/{} due to it not being profitable",
                         oldest_signed_batch.token_contract, oldest_signed_batch.nonce
                                             );
@@ -5,6 +5,7 @@ pub mod batch_relaying;
 pub mod find_latest_valset;
 pub mod logic_call_relaying;
 pub mod main_loop;
+pub mod metrics;
 pub mod request_batches;
 pub mod valset_relaying;
 
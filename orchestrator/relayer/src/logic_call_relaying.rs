@@ -18,8 +18,11 @@ use gravity_utils::{
     types::{LogicCall, LogicCallConfirmResponse, RelayerConfig, Valset},
     web30::{amm::WETH_CONTRACT_ADDRESS, client::Web3},
 };
+use metrics_exporter::{metrics_relayer_pending, BridgeObject};
 use tonic::transport::Channel;
 
+use crate::metrics::{record_decision, relay_with_metrics};
+
 // Determines whether or not submitting `logic_call` will be profitable given the estimated `cost`
 // and the current exchange rate available on uniswap
 async fn should_relay_logic_call(
@@ -104,6 +107,7 @@ pub async fn relay_logic_calls(
     let latest_calls = latest_calls.unwrap();
     let mut oldest_signed_call: Option<LogicCall> = None;
     let mut oldest_signatures: Option<Vec<LogicCallConfirmResponse>> = None;
+    let mut pending = 0;
     for call in latest_calls {
         let sigs = get_logic_call_signatures(
             grpc_client,
@@ -116,6 +120,7 @@ pub async fn relay_logic_calls(
             let hash = encode_logic_call_confirm_hashed(gravity_id.clone(), call.clone());
             // this checks that the signatures for the batch are actually possible to submit to the chain
             if current_valset.order_sigs(&hash, &sigs).is_ok() {
+                pending += 1;
                 oldest_signed_call = Some(call);
                 oldest_signatures = Some(sigs);
             } else {
@@ -134,6 +139,7 @@ pub async fn relay_logic_calls(
             );
         }
     }
+    metrics_relayer_pending(BridgeObject::LogicCall, pending);
     if oldest_signed_call.is_none() {
         trace!("Could not find Call with signatures! exiting");
         return;
@@ -174,13 +180,17 @@ pub async fn relay_logic_calls(
         }
         let cost = cost.unwrap();
         info!(
-                "We have detected latest LogicCall {} but latest on Ethereum is {} This LogicCall is estimated to cost {} Gas @ {} Gwei / {:.4} ETH to submit",
-                latest_cosmos_call_nonce,
-                latest_ethereum_call,
-                cost.gas.clone(),
-                print_gwei(cost.gas_price),
-                print_eth(cost.get_total())
-            );
+            subsystem = "relayer",
+            nonce = oldest_signed_call.invalidation_nonce,
+            gas = cost.gas.to_string(),
+            cost_wei = cost.get_total().to_string();
+            "We have detected latest LogicCall {} but latest on Ethereum is {} This LogicCall is estimated to cost {} Gas @ {} Gwei / {:.4} ETH to submit",
+            latest_cosmos_call_nonce,
+            latest_ethereum_call,
+            cost.gas.clone(),
+            print_gwei(cost.gas_price),
+            print_eth(cost.get_total())
+        );
 
         let should_relay = if config.logic_call_market_enabled {
             should_relay_logic_call(
@@ -193,17 +203,26 @@ pub async fn relay_logic_calls(
         } else {
             true
         };
+        record_decision(BridgeObject::LogicCall, None, should_relay);
 
         if should_relay {
-            let res = send_eth_logic_call(
-                current_valset,
-                oldest_signed_call,
-                &oldest_signatures,
+            let res = relay_with_metrics(
+                BridgeObject::LogicCall,
+                oldest_signed_call.invalidation_nonce,
+                None,
+                cost,
+                our_ethereum_address,
                 web3,
-                timeout,
-                gravity_contract_address,
-                gravity_id.clone(),
-                ethereum_key,
+                send_eth_logic_call(
+                    current_valset,
+                    oldest_signed_call,
+                    &oldest_signatures,
+                    web3,
+                    timeout,
+                    gravity_contract_address,
+                    gravity_id.clone(),
+                    ethereum_key,
+                ),
             )
             .await;
             if res.is_err() {
@@ -211,6 +230,9 @@ pub async fn relay_logic_calls(
             }
         } else {
             info!(
+                subsystem = "relayer",
+                nonce = oldest_signed_call.invalidation_nonce,
+                outcome = "skipped";
                 "Not relaying logic call because it is not profitable to do so: {:?}",
                 oldest_signed_call
             );
@@ -3,12 +3,16 @@ use std::time::Duration;
 use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
 use gravity_utils::{
     clarity::{address::Address as EthAddress, PrivateKey as EthPrivateKey},
-    deep_space::{Coin, Contact, PrivateKey as CosmosPrivateKey},
+    deep_space::{client::ChainStatus, Coin, Contact, PrivateKey as CosmosPrivateKey},
     error::GravityError,
-    types::RelayerConfig,
+    types::GravityBridgeToolsConfig,
     web30::client::Web3,
 };
-use tokio::time::sleep;
+use metrics_exporter::{
+    health_loop_completed, health_loop_started, health_node_status, metrics_balance, metrics_loop,
+    metrics_rpc, Chain, LoopKind, NodeStatus,
+};
+use tokio::{sync::watch, time::sleep};
 use tonic::transport::Channel;
 
 use crate::{
@@ -20,7 +24,9 @@ use crate::{
 pub const TIMEOUT: Duration = Duration::from_secs(10);
 
 /// This function contains the orchestrator primary loop, it is broken out of the main loop so that
-/// it can be called in the test runner for easier orchestration of multi-node tests
+/// it can be called in the test runner for easier orchestration of multi-node tests. The relayer
+/// section of `config` is read at the start of every loop iteration so that it may be replaced
+/// while running
 #[allow(clippy::too_many_arguments)]
 pub async fn relayer_main_loop(
     ethereum_key: EthPrivateKey,
@@ -31,13 +37,30 @@ pub async fn relayer_main_loop(
     grpc_client: GravityQueryClient<Channel>,
     gravity_contract_address: EthAddress,
     gravity_id: String,
-    relayer_config: &RelayerConfig,
+    config: watch::Receiver<GravityBridgeToolsConfig>,
 ) -> Result<(), GravityError> {
     let mut grpc_client = grpc_client;
-    let loop_speed = Duration::from_secs(relayer_config.relayer_loop_speed);
+    let mut config_receiver = config;
     loop {
+        if config_receiver.has_changed().unwrap_or(false) {
+            info!("Relayer config changed, applying it to this loop iteration");
+        }
+        let config = config_receiver.borrow_and_update().clone();
+        let relayer_config = &config.relayer;
+        let loop_speed = Duration::from_secs(relayer_config.relayer_loop_speed);
+        health_loop_started(LoopKind::Relayer, loop_speed);
         let (async_result, _) = tokio::join!(
-            async {
+            metrics_loop(LoopKind::Relayer, async {
+                match metrics_rpc("eth_blockNumber", Chain::Ethereum, web3.eth_block_number()).await
+                {
+                    Ok(_) => health_node_status(Chain::Ethereum, NodeStatus::Moving),
+                    Err(e) => {
+                        warn!("Could not contact Eth node ({:?}), trying again", e);
+                        health_node_status(Chain::Ethereum, NodeStatus::Unreachable);
+                        return Ok(());
+                    }
+                }
+
                 let current_valset =
                     find_latest_valset(&mut grpc_client, gravity_contract_address, &web3).await;
 
@@ -48,6 +71,28 @@ pub async fn relayer_main_loop(
 
                 let current_valset = current_valset.unwrap();
 
+                if let Ok(balance) = web3.eth_get_balance(ethereum_key.to_address()).await {
+                    metrics_balance("eth", balance, 18);
+                }
+                let cosmos_block_height = match contact.get_chain_status().await {
+                    Ok(ChainStatus::Moving { block_height }) => {
+                        health_node_status(Chain::Cosmos, NodeStatus::Moving);
+                        Some(block_height)
+                    }
+                    Ok(ChainStatus::Syncing) => {
+                        health_node_status(Chain::Cosmos, NodeStatus::Syncing);
+                        None
+                    }
+                    Ok(ChainStatus::WaitingToStart) => {
+                        health_node_status(Chain::Cosmos, NodeStatus::WaitingToStart);
+                        None
+                    }
+                    Err(_) => {
+                        health_node_status(Chain::Cosmos, NodeStatus::Unreachable);
+                        None
+                    }
+                };
+
                 relay_valsets(
                     &current_valset,
                     ethereum_key,
@@ -69,6 +114,7 @@ pub async fn relayer_main_loop(
                     gravity_id.clone(),
                     TIMEOUT,
                     relayer_config,
+                    cosmos_block_height,
                 )
                 .await;
 
@@ -97,8 +143,9 @@ pub async fn relayer_main_loop(
                     .await
                 }
 
+                health_loop_completed(LoopKind::Relayer);
                 Ok(())
-            },
+            }),
             // the sleep will be called in the parallel with the relay,
             // the "join!" will await for the longest operation time
             sleep(loop_speed),
@@ -0,0 +1,97 @@
+//! Records what the relayer sees and does with `metrics_exporter`
+
+use std::future::Future;
+
+use ethereum_gravity::utils::{GasCost, RelayResult};
+use gravity_utils::{clarity::Address as EthAddress, error::GravityError, web30::client::Web3};
+use metrics_exporter::{
+    metrics_balance, metrics_error, metrics_relay, metrics_relay_cost, metrics_relay_decision,
+    BridgeObject, CostKind, ErrorKind, RelayDecision, RelayOutcome, Subsystem,
+};
+
+/// Sends a relay and records and logs its outcome along with its estimated and realized cost.
+/// The realized cost is the change in our ETH balance, so it also includes any other
+/// transaction from our key that is included at the same time
+pub async fn relay_with_metrics(
+    kind: BridgeObject,
+    nonce: u64,
+    token_contract: Option<EthAddress>,
+    estimated: GasCost,
+    our_ethereum_address: EthAddress,
+    web3: &Web3,
+    relay: impl Future<Output = Result<RelayResult, GravityError>>,
+) -> Result<RelayResult, GravityError> {
+    let before = web3.eth_get_balance(our_ethereum_address).await;
+    let res = relay.await;
+    record_relay(kind, &res);
+    let mut realized = None;
+    // only relays that sent a transaction have a realized cost to compare against
+    if sent_transaction(&res) {
+        metrics_relay_cost(kind, CostKind::Estimated, estimated.get_total());
+        let after = web3.eth_get_balance(our_ethereum_address).await;
+        if let (Ok(before), Ok(after)) = (before, after) {
+            realized = before.checked_sub(after);
+            if let Some(cost) = realized {
+                metrics_relay_cost(kind, CostKind::Realized, cost);
+            }
+            metrics_balance("eth", after, 18);
+        }
+    }
+    info!(
+        subsystem = "relayer",
+        nonce = nonce,
+        token_contract = token_contract.map(|t| t.to_string()).unwrap_or_default(),
+        gas = estimated.gas.to_string(),
+        cost_wei = realized.unwrap_or_else(|| estimated.get_total()).to_string(),
+        outcome = outcome(&res);
+        "Finished relaying {} {}",
+        kind.as_str(),
+        nonce
+    );
+    res
+}
+
+/// Records the outcome of the profitability check, `token` is the ERC20 a batch pays its fees in
+pub fn record_decision(kind: BridgeObject, token: Option<EthAddress>, should_relay: bool) {
+    let decision = if should_relay {
+        RelayDecision::Relay
+    } else {
+        RelayDecision::Skip
+    };
+    metrics_relay_decision(kind, token, decision);
+}
+
+/// The `outcome` field of relay log records
+fn outcome(res: &Result<RelayResult, GravityError>) -> &'static str {
+    match res {
+        Ok(RelayResult::Relayed) => "relayed",
+        Ok(RelayResult::AlreadyRelayed) => "already_relayed",
+        Ok(RelayResult::NotUpdated) => "not_updated",
+        Ok(RelayResult::TimedOut) => "timed_out",
+        Err(_) => "failed",
+    }
+}
+
+/// True if the relay is known to have submitted a transaction. An error may have happened
+/// before or after sending, those are counted as failed instead
+fn sent_transaction(res: &Result<RelayResult, GravityError>) -> bool {
+    matches!(res, Ok(RelayResult::Relayed) | Ok(RelayResult::NotUpdated))
+}
+
+fn record_relay(kind: BridgeObject, res: &Result<RelayResult, GravityError>) {
+    if sent_transaction(res) {
+        metrics_relay(kind, RelayOutcome::Attempted);
+    }
+    match res {
+        Ok(RelayResult::Relayed) => metrics_relay(kind, RelayOutcome::Succeeded),
+        Ok(RelayResult::AlreadyRelayed) | Ok(RelayResult::NotUpdated) => {
+            metrics_relay(kind, RelayOutcome::LostRace)
+        }
+        // nothing was sent, the relayer will not pick it up again
+        Ok(RelayResult::TimedOut) => {}
+        Err(_) => {
+            metrics_relay(kind, RelayOutcome::Failed);
+            metrics_error(Subsystem::Ethereum, ErrorKind::Relay);
+        }
+    }
+}
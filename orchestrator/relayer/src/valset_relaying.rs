@@ -17,9 +17,13 @@ use gravity_utils::{
     types::{RelayerConfig, Valset, ValsetConfirmResponse, ValsetRelayingMode},
     web30::client::Web3,
 };
+use metrics_exporter::{metrics_relayer_pending, BridgeObject};
 use tonic::transport::Channel;
 
-use crate::batch_relaying::get_cost_with_margin;
+use crate::{
+    batch_relaying::get_cost_with_margin,
+    metrics::{record_decision, relay_with_metrics},
+};
 
 #[allow(clippy::too_many_arguments)]
 /// High level entry point for valset relaying, this function starts by finding
@@ -62,7 +66,10 @@ pub async fn relay_valsets(
         Ok(v) => v,
         // If we get the RecoverableError we don't need to log it, since it is a kind of flag to indicate that it was expected.
         // And here we expect the ValsetUpToDate error which means that we don't need to update up to date valset.
-        Err(GravityError::RecoverableError(_)) => return,
+        Err(GravityError::RecoverableError(_)) => {
+            metrics_relayer_pending(BridgeObject::Valset, 0);
+            return;
+        }
         Err(e) => {
             error!(
                 "We were unable to find a valid validator set update to submit! {:?}",
@@ -72,6 +79,9 @@ pub async fn relay_valsets(
         }
     };
 
+    // only the latest submittable valset is relayed, the ones before it are skipped
+    metrics_relayer_pending(BridgeObject::Valset, 1);
+
     relay_valid_valset(
         latest_cosmos_valset_nonce,
         &latest_submittable_valset,
@@ -126,6 +136,10 @@ async fn relay_valid_valset(
     let cost = cost.unwrap();
 
     info!(
+        subsystem = "relayer",
+        nonce = valset_to_relay.nonce,
+        gas = cost.gas.to_string(),
+        cost_wei = cost.get_total().to_string();
        "We have detected that valset {} is valid to submit. Latest on Ethereum is {} This update is estimated to cost {} Gas @ {} Gwei/ {:.4} ETH to submit",
         valset_to_relay.nonce, current_valset.nonce,
         cost.gas.clone(),
@@ -142,21 +156,34 @@ async fn relay_valid_valset(
         &config.valset_relaying_mode,
     )
     .await;
+    record_decision(BridgeObject::Valset, None, should_relay);
 
     if should_relay {
-        let _res = send_eth_valset_update(
-            valset_to_relay,
-            current_valset,
-            &conformations,
+        let res = relay_with_metrics(
+            BridgeObject::Valset,
+            valset_to_relay.nonce,
+            None,
+            cost,
+            ethereum_key.to_address(),
             web3,
-            timeout,
-            gravity_contract_address,
-            gravity_id,
-            ethereum_key,
+            send_eth_valset_update(
+                valset_to_relay,
+                current_valset,
+                &conformations,
+                web3,
+                timeout,
+                gravity_contract_address,
+                gravity_id,
+                ethereum_key,
+            ),
         )
         .await;
+        if res.is_err() {
+            info!("Valset submission failed with {:?}", res);
+        }
     } else {
         info!(
+            subsystem = "relayer", nonce = valset_to_relay.nonce, outcome = "skipped";
             "The valset {} won't be relayed by this orchestrator, because relayer is disabled",
             valset_to_relay.nonce
         );
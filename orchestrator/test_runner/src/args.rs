@@ -0,0 +1,92 @@
+//! Command line arguments for the test runner. Every connection and key option can also be
+//! set with the environment variable the docker test scripts use or in a config file, flags
+//! take precedence over environment variables which take precedence over the config file
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+/// Runs the Gravity integration test scenarios against a running test environment.
+/// Without a subcommand the runner behaves as the docker scripts expect, running the
+/// scenario in TEST_TYPE or deploying contracts when DEPLOY_CONTRACTS is set
+#[derive(Parser, Debug)]
+#[command(name = "test-runner", version = env!("CARGO_PKG_VERSION"))]
+pub struct Opts {
+    #[command(flatten)]
+    pub config: ConfigOpts,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// List the registered scenarios
+    List,
+    /// Run one or more scenarios in order
+    Run(RunOpts),
+    /// Deploy the Gravity and test ERC20 contracts, then exit
+    DeployContracts,
+    /// Print the address of the distribution module with the configured address prefix
+    TestAddress,
+}
+
+#[derive(Args, Debug, Default, Clone)]
+pub struct RunOpts {
+    /// Scenario names, by default the happy path is run
+    #[arg(env = "TEST_TYPE", value_delimiter = ',')]
+    pub scenarios: Vec<String>,
+    /// Timeout in seconds for each scenario, overriding the scenario default
+    #[arg(long)]
+    pub timeout: Option<u64>,
+    /// Write a report of the results to this file
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+    /// The format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Junit)]
+    pub report_format: ReportFormat,
+    /// Run the remaining scenarios after one fails
+    #[arg(long)]
+    pub keep_going: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    Junit,
+    Json,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct ConfigOpts {
+    /// A TOML file with any of the options below
+    #[arg(long, env = "TEST_RUNNER_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+    /// The address prefix of the Cosmos chain
+    #[arg(long, env = "ADDRESS_PREFIX", global = true)]
+    pub address_prefix: Option<String>,
+    /// The denom validators stake
+    #[arg(long, env = "STAKING_TOKEN", global = true)]
+    pub staking_token: Option<String>,
+    /// The Cosmos gRPC endpoint
+    #[arg(long, env = "COSMOS_NODE_GRPC", global = true)]
+    pub cosmos_grpc: Option<String>,
+    /// The Cosmos tendermint RPC endpoint
+    #[arg(long, env = "COSMOS_NODE_ABCI", global = true)]
+    pub cosmos_abci: Option<String>,
+    /// The Ethereum JSON-RPC endpoint
+    #[arg(long, env = "ETH_NODE", global = true)]
+    pub eth_node: Option<String>,
+    /// The key of the Ethereum account funding the tests and deploying contracts
+    #[arg(long, env = "MINER_PRIVATE_KEY", hide_env_values = true, global = true)]
+    pub miner_private_key: Option<String>,
+    /// Use an already deployed Gravity contract instead of deploying one
+    #[arg(long, env = "GRAVITY_ADDRESS", global = true)]
+    pub gravity_address: Option<String>,
+    /// Use already deployed test ERC20 contracts, comma separated
+    #[arg(long, env = "ERC20_ADDRESSES", value_delimiter = ',', global = true)]
+    pub erc20_addresses: Option<Vec<String>>,
+    /// The directory with the validator-eth-keys, validator-phrases and orchestrator-phrases
+    /// files written by the test chain setup scripts
+    #[arg(long, env = "KEYS_DIR", global = true)]
+    pub keys_dir: Option<PathBuf>,
+}
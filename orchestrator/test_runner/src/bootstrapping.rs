@@ -1,5 +1,4 @@
 use std::{
-    env,
     fs::File,
     io::{BufRead, BufReader, Read, Write},
     path::Path,
@@ -11,15 +10,17 @@ use gravity_utils::{
     deep_space::{private_key::PrivateKey as CosmosPrivateKey, Contact},
 };
 
-use crate::{utils::ValidatorKeys, COSMOS_NODE_ABCI, ETH_NODE, MINER_PRIVATE_KEY, TOTAL_TIMEOUT};
+use crate::{
+    utils::ValidatorKeys, CONFIG, COSMOS_NODE_ABCI, ETH_NODE, MINER_PRIVATE_KEY, TOTAL_TIMEOUT,
+};
 
 /// Ethereum private keys for the validators are generated using the gravity eth_keys add command
-/// and dumped into a file /validator-eth-keys in the container, from there they are then used by
-/// the orchestrator on startup
+/// and dumped into a file validator-eth-keys in the keys directory (/ in the container), from
+/// there they are then used by the orchestrator on startup
 pub fn parse_ethereum_keys() -> Vec<EthPrivateKey> {
-    let filename = "/validator-eth-keys";
-    if !Path::new(filename).is_file() {
-        info!("Key file {}, not found", filename);
+    let filename = CONFIG.keys_dir.join("validator-eth-keys");
+    if !filename.is_file() {
+        info!("Key file {}, not found", filename.display());
         return Vec::new();
     };
     let file = File::open(filename).expect("Failed to find eth keys");
@@ -38,9 +39,9 @@ pub fn parse_ethereum_keys() -> Vec<EthPrivateKey> {
 }
 
 /// Parses the output of the cosmoscli keys add command to import the private key
-fn parse_phrases(filename: &str) -> Vec<CosmosPrivateKey> {
-    if !Path::new(filename).is_file() {
-        info!("Key file {}, not found", filename);
+fn parse_phrases(filename: &Path) -> Vec<CosmosPrivateKey> {
+    if !filename.is_file() {
+        info!("Key file {}, not found", filename.display());
         return Vec::new();
     };
 
@@ -65,21 +66,19 @@ fn parse_phrases(filename: &str) -> Vec<CosmosPrivateKey> {
 /// Validator private keys are generated via the gravity key add
 /// command, from there they are used to create gentx's and start the
 /// chain, these keys change every time the container is restarted.
-/// The mnemonic phrases are dumped into a text file /validator-phrases
+/// The mnemonic phrases are dumped into a text file validator-phrases
 /// the phrases are in increasing order, so validator 1 is the first key
 /// and so on. While validators may later fail to start it is guaranteed
 /// that we have one key for each validator in this file.
 pub fn parse_validator_keys() -> Vec<CosmosPrivateKey> {
-    let filename = "/validator-phrases";
-    parse_phrases(filename)
+    parse_phrases(&CONFIG.keys_dir.join("validator-phrases"))
 }
 
 /// Orchestrator private keys are generated via the gravity key add
 /// command just like the validator keys themselves and stored in a
-/// similar file /orchestrator-phrases
+/// similar file orchestrator-phrases
 pub fn parse_orchestrator_keys() -> Vec<CosmosPrivateKey> {
-    let filename = "/orchestrator-phrases";
-    parse_phrases(filename)
+    parse_phrases(&CONFIG.keys_dir.join("orchestrator-phrases"))
 }
 
 pub fn get_keys() -> Vec<ValidatorKeys> {
@@ -106,8 +105,11 @@ pub async fn deploy_contracts(contact: &Contact) {
     // yet produced the next block after submitting each eth address
     contact.wait_for_next_block(TOTAL_TIMEOUT).await.unwrap();
 
-    let remote_mode = if let Ok(s) = env::var("GRAVITY_ADDRESS") {
-        info!("GRAVITY_ADDRESS set, using gravity contract address {}", s);
+    let remote_mode = if let Some(address) = CONFIG.gravity_address {
+        info!(
+            "Gravity address set, using gravity contract address {}",
+            address
+        );
         // leads to the gravity contract not being deployed
         "true"
     } else {
@@ -202,14 +204,13 @@ pub fn parse_contract_addresses() -> BootstrapContractAddresses {
         }
     }
 
-    let gravity_address: EthAddress = env::var("GRAVITY_ADDRESS")
-        .map(|s| s.trim().parse().unwrap())
-        .unwrap_or_else(|_| maybe_gravity_address.unwrap());
+    let gravity_address: EthAddress = CONFIG
+        .gravity_address
+        .unwrap_or_else(|| maybe_gravity_address.unwrap());
 
-    // if ERC20_ADDRESSES convert to EthAddress and update the variable
-    if let Ok(s) = env::var("ERC20_ADDRESSES") {
-        // replace the var from the env is present
-        erc20_addresses = s.split(',').map(|s| s.trim().parse().unwrap()).collect();
+    // configured ERC20 addresses replace the deployed ones
+    if let Some(addresses) = &CONFIG.erc20_addresses {
+        erc20_addresses = addresses.clone();
     }
 
     BootstrapContractAddresses {
@@ -0,0 +1,161 @@
+//! Chaos tests, the orchestrators run behind fault injecting proxies for their Ethereum and
+//! Cosmos endpoints while valset updates and deposits go through the bridge. The test itself
+//! talks to the nodes directly. Each deposit must be credited exactly once and once the faults
+//! clear every oracle must catch up with the event nonce of the Gravity contract.
+//!
+//! Ethereum reorgs are not covered. The test chain runs with a block delay of zero, so any
+//! reorg replaces blocks the oracle may already have scanned, and the oracle does not recover
+//! from reorgs deeper than the block delay
+use std::time::Duration;
+
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{
+    clarity::{u256, Address as EthAddress},
+    deep_space::Contact,
+    u64_array_bigints,
+    web30::client::Web3,
+    GRAVITY_DENOM_PREFIX,
+};
+use tokio::time::sleep;
+use tonic::transport::Channel;
+
+use crate::{
+    fault_proxy::{FaultProxy, Faults, Protocol},
+    happy_path::{test_erc20_deposit_panic, test_valset_update},
+    unhalt_bridge::get_nonces,
+    utils::{
+        create_default_test_config, get_event_nonce_safe, get_user_key,
+        start_orchestrators_with_endpoints, ValidatorKeys,
+    },
+    ADDRESS_PREFIX, COSMOS_NODE_GRPC, ETH_NODE, MINER_ADDRESS, TOTAL_TIMEOUT,
+};
+
+/// Cosmos blocks to wait for a duplicate deposit to show up after the bridge settles
+const SETTLE_BLOCKS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chaos {
+    /// latency, dropped requests, rate limits and stale block numbers from Ethereum
+    EthereumRpc,
+    /// latency, dropped requests and rate limits from Cosmos gRPC
+    CosmosGrpc,
+}
+
+fn flaky() -> Faults {
+    Faults {
+        latency: Duration::from_millis(500),
+        drop_rate: 0.1,
+        rate_limit_rate: 0.1,
+        ..Default::default()
+    }
+}
+
+pub async fn chaos_test(
+    web30: &Web3,
+    grpc_client: GravityQueryClient<Channel>,
+    contact: &Contact,
+    keys: Vec<ValidatorKeys>,
+    gravity_address: EthAddress,
+    erc20_address: EthAddress,
+    chaos: Chaos,
+) {
+    let mut grpc_client = grpc_client;
+    let eth_proxy = FaultProxy::start(&ETH_NODE, Protocol::EthereumRpc);
+    let grpc_proxy = FaultProxy::start(&COSMOS_NODE_GRPC, Protocol::Grpc);
+    start_orchestrators_with_endpoints(
+        keys.clone(),
+        gravity_address,
+        false,
+        create_default_test_config(),
+        &eth_proxy.url(),
+        &grpc_proxy.url(),
+    )
+    .await;
+
+    match chaos {
+        Chaos::EthereumRpc => eth_proxy.set_faults(Faults {
+            stale_blocks: 2,
+            ..flaky()
+        }),
+        Chaos::CosmosGrpc => grpc_proxy.set_faults(flaky()),
+    }
+
+    let user_keys = get_user_key();
+    test_valset_update(web30, contact, &mut grpc_client, &keys, gravity_address).await;
+    for _ in 0u32..3 {
+        test_erc20_deposit_panic(
+            web30,
+            contact,
+            &mut grpc_client,
+            user_keys.cosmos_address,
+            gravity_address,
+            erc20_address,
+            u256!(100),
+            None,
+            None,
+        )
+        .await;
+    }
+
+    let injected = eth_proxy.injected_faults() + grpc_proxy.injected_faults();
+    eth_proxy.clear_faults();
+    grpc_proxy.clear_faults();
+    info!("Bridge operated with {} injected faults", injected);
+    assert!(injected > 0, "No faults were injected");
+
+    wait_for_oracles(web30, &mut grpc_client, &keys, gravity_address).await;
+
+    // a deposit submitted twice would be credited after the balance check above passed
+    let denom = format!("{GRAVITY_DENOM_PREFIX}{erc20_address}");
+    let balance = contact
+        .get_balance(user_keys.cosmos_address, denom.clone())
+        .await
+        .unwrap()
+        .map(|c| c.amount);
+    for _ in 0..SETTLE_BLOCKS {
+        contact.wait_for_next_block(TOTAL_TIMEOUT).await.unwrap();
+    }
+    let settled = contact
+        .get_balance(user_keys.cosmos_address, denom)
+        .await
+        .unwrap()
+        .map(|c| c.amount);
+    assert_eq!(balance, settled, "A deposit was credited twice");
+    info!("Chaos test {:?} passed", chaos);
+}
+
+/// Waits for every orchestrator to attest to the latest event of the Gravity contract, an
+/// oracle that is stuck or claims events that do not exist fails the test
+async fn wait_for_oracles(
+    web30: &Web3,
+    grpc_client: &mut GravityQueryClient<Channel>,
+    keys: &[ValidatorKeys],
+    gravity_address: EthAddress,
+) {
+    let event_nonce = get_event_nonce_safe(gravity_address, web30, *MINER_ADDRESS)
+        .await
+        .unwrap();
+    let result = tokio::time::timeout(TOTAL_TIMEOUT, async {
+        loop {
+            let nonces = get_nonces(grpc_client, keys, &ADDRESS_PREFIX).await;
+            assert!(
+                nonces.iter().all(|n| *n <= event_nonce),
+                "Oracle claimed event nonces {:?} past the contract nonce {}",
+                nonces,
+                event_nonce
+            );
+            if nonces.iter().all(|n| *n == event_nonce) {
+                return;
+            }
+            info!(
+                "Waiting for oracle nonces {:?} to reach {}",
+                nonces, event_nonce
+            );
+            sleep(Duration::from_secs(5)).await;
+        }
+    })
+    .await;
+    if result.is_err() {
+        panic!("Oracles did not catch up with event nonce {}", event_nonce);
+    }
+}
@@ -0,0 +1,145 @@
+//! Resolves the test runner configuration from flags, environment variables, the optional
+//! config file and the defaults of the docker test environment, in that order
+
+use std::{fs, path::PathBuf};
+
+use gravity_utils::{
+    clarity::{Address as EthAddress, PrivateKey as EthPrivateKey},
+    DEFAULT_ADDRESS_PREFIX, TEST_DEFAULT_ETH_NODE_ENDPOINT, TEST_DEFAULT_MINER_KEY,
+};
+use serde::Deserialize;
+
+use crate::args::ConfigOpts;
+
+/// The config file accepts the same options as the flags, with underscores
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    address_prefix: Option<String>,
+    staking_token: Option<String>,
+    cosmos_grpc: Option<String>,
+    cosmos_abci: Option<String>,
+    eth_node: Option<String>,
+    miner_private_key: Option<String>,
+    gravity_address: Option<String>,
+    erc20_addresses: Option<Vec<String>>,
+    keys_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    pub address_prefix: String,
+    pub staking_token: String,
+    pub cosmos_grpc: String,
+    pub cosmos_abci: String,
+    pub eth_node: String,
+    pub miner_private_key: EthPrivateKey,
+    /// when set the Gravity contract is not deployed and this one is used instead
+    pub gravity_address: Option<EthAddress>,
+    /// when set these replace the test ERC20s found in the deployer output
+    pub erc20_addresses: Option<Vec<EthAddress>>,
+    pub keys_dir: PathBuf,
+}
+
+impl RunnerConfig {
+    pub fn load(opts: &ConfigOpts) -> Result<RunnerConfig, String> {
+        let file = match &opts.config {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read config file {path:?} {e:?}"))?;
+                toml::from_str(&contents)
+                    .map_err(|e| format!("Invalid config file {path:?} {e}"))?
+            }
+            None => ConfigFile::default(),
+        };
+        let pick = |flag: &Option<String>, file: Option<String>, default: &str| {
+            flag.clone().or(file).unwrap_or_else(|| default.to_string())
+        };
+
+        let miner_private_key = pick(
+            &opts.miner_private_key,
+            file.miner_private_key,
+            TEST_DEFAULT_MINER_KEY,
+        );
+        let miner_private_key = miner_private_key
+            .parse()
+            .map_err(|e| format!("Invalid miner private key {e:?}"))?;
+        let gravity_address = opts
+            .gravity_address
+            .clone()
+            .or(file.gravity_address)
+            .map(|a| parse_address(&a))
+            .transpose()?;
+        let erc20_addresses = opts
+            .erc20_addresses
+            .clone()
+            .or(file.erc20_addresses)
+            .map(|a| a.iter().map(|a| parse_address(a)).collect())
+            .transpose()?;
+
+        Ok(RunnerConfig {
+            address_prefix: pick(
+                &opts.address_prefix,
+                file.address_prefix,
+                DEFAULT_ADDRESS_PREFIX,
+            ),
+            staking_token: pick(&opts.staking_token, file.staking_token, "stake"),
+            cosmos_grpc: pick(&opts.cosmos_grpc, file.cosmos_grpc, "http://localhost:9090"),
+            cosmos_abci: pick(
+                &opts.cosmos_abci,
+                file.cosmos_abci,
+                "http://localhost:26657",
+            ),
+            eth_node: pick(
+                &opts.eth_node,
+                file.eth_node,
+                TEST_DEFAULT_ETH_NODE_ENDPOINT,
+            ),
+            miner_private_key,
+            gravity_address,
+            erc20_addresses,
+            keys_dir: opts
+                .keys_dir
+                .clone()
+                .or(file.keys_dir)
+                .unwrap_or_else(|| PathBuf::from("/")),
+        })
+    }
+}
+
+fn parse_address(address: &str) -> Result<EthAddress, String> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid Ethereum address {address} {e:?}"))
+}
+
+#[test]
+fn test_config_precedence() {
+    let path = std::env::temp_dir().join("test-runner-config.toml");
+    fs::write(
+        &path,
+        "eth_node = \"http://eth:8545\"\ncosmos_grpc = \"http://cosmos:9090\"\n\
+         erc20_addresses = [\"0xD7600ae27C99988A6CD360234062b540F88ECA43\"]\n",
+    )
+    .unwrap();
+    let opts = ConfigOpts {
+        config: Some(path.clone()),
+        cosmos_grpc: Some("http://flag:9090".to_string()),
+        ..Default::default()
+    };
+    let config = RunnerConfig::load(&opts).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(config.cosmos_grpc, "http://flag:9090");
+    assert_eq!(config.eth_node, "http://eth:8545");
+    assert_eq!(config.address_prefix, DEFAULT_ADDRESS_PREFIX);
+    assert_eq!(config.erc20_addresses.unwrap().len(), 1);
+    assert!(config.gravity_address.is_none());
+
+    let opts = ConfigOpts {
+        gravity_address: Some("not an address".to_string()),
+        ..Default::default()
+    };
+    assert!(RunnerConfig::load(&opts).is_err());
+}
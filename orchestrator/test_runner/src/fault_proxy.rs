@@ -0,0 +1,357 @@
+//! A fault injecting proxy for the Ethereum JSON-RPC and Cosmos gRPC endpoints. The chaos
+//! scenarios start orchestrators against a pair of these proxies and change the faults while
+//! the bridge is running to check that the oracle and signer recover from flaky infrastructure
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use hyper::{
+    body::to_bytes,
+    client::HttpConnector,
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Client, Request, Response, Server, StatusCode, Uri,
+};
+use rand::Rng;
+use serde_json::{json, Value};
+use tokio::{task::JoinHandle, time::sleep};
+
+/// The faults applied to proxied requests, they can be changed while the proxy runs
+#[derive(Debug, Clone, Default)]
+pub struct Faults {
+    /// delay added to every request before it is forwarded
+    pub latency: Duration,
+    /// the chance in [0, 1] that a request is dropped without an answer
+    pub drop_rate: f64,
+    /// the chance in [0, 1] that a request is answered with a rate limit error
+    pub rate_limit_rate: f64,
+    /// Ethereum only, `eth_blockNumber` answers this many blocks behind the node
+    pub stale_blocks: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// HTTP/1 JSON-RPC, faults are applied per call and block numbers can be rewritten
+    EthereumRpc,
+    /// HTTP/2 gRPC, rate limits are answered with `RESOURCE_EXHAUSTED`
+    Grpc,
+}
+
+#[derive(Debug, Default)]
+struct ProxyState {
+    faults: Faults,
+    /// requests seen by JSON-RPC method or gRPC path
+    requests: HashMap<String, u64>,
+    injected: u64,
+}
+
+#[derive(Clone)]
+struct Handler {
+    upstream: Uri,
+    protocol: Protocol,
+    client: Client<HttpConnector, Body>,
+    state: Arc<Mutex<ProxyState>>,
+}
+
+/// A running proxy, it is stopped when dropped
+pub struct FaultProxy {
+    addr: SocketAddr,
+    state: Arc<Mutex<ProxyState>>,
+    server: JoinHandle<()>,
+}
+
+impl FaultProxy {
+    /// Starts a proxy for `upstream` on a free local port, without any faults
+    pub fn start(upstream: &str, protocol: Protocol) -> FaultProxy {
+        let upstream: Uri = upstream.parse().expect("Invalid upstream url");
+        let state = Arc::new(Mutex::new(ProxyState::default()));
+        let client = match protocol {
+            Protocol::EthereumRpc => Client::new(),
+            Protocol::Grpc => Client::builder().http2_only(true).build_http(),
+        };
+        let handler = Handler {
+            upstream,
+            protocol,
+            client,
+            state: state.clone(),
+        };
+
+        let make_service = make_service_fn(move |_| {
+            let handler = handler.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| handler.clone().handle(request)))
+            }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .expect("Failed to bind the fault proxy")
+            .http2_only(protocol == Protocol::Grpc)
+            .serve(make_service);
+        let addr = server.local_addr();
+        let server = tokio::spawn(async move {
+            if let Err(e) = server.await {
+                error!("Fault proxy failed {:?}", e);
+            }
+        });
+        FaultProxy {
+            addr,
+            state,
+            server,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn set_faults(&self, faults: Faults) {
+        info!("Fault proxy {} now injecting {:?}", self.addr, faults);
+        self.state.lock().unwrap().faults = faults;
+    }
+
+    pub fn clear_faults(&self) {
+        self.set_faults(Faults::default());
+    }
+
+    /// The number of requests seen for a JSON-RPC method or gRPC path, faulted or not
+    pub fn request_count(&self, name: &str) -> u64 {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .get(name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The number of requests that had a fault other than latency applied
+    pub fn injected_faults(&self) -> u64 {
+        self.state.lock().unwrap().injected
+    }
+}
+
+impl Drop for FaultProxy {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+impl Handler {
+    async fn handle(self, request: Request<Body>) -> Result<Response<Body>, String> {
+        let faults = self.state.lock().unwrap().faults.clone();
+        if !faults.latency.is_zero() {
+            sleep(faults.latency).await;
+        }
+        match self.protocol {
+            Protocol::EthereumRpc => self.handle_rpc(request, faults).await,
+            Protocol::Grpc => self.handle_grpc(request, faults).await,
+        }
+    }
+
+    /// Counts the request and rolls for the drop and rate limit faults
+    fn roll(&self, name: &str, faults: &Faults) -> Option<Fault> {
+        let mut state = self.state.lock().unwrap();
+        *state.requests.entry(name.to_string()).or_default() += 1;
+        let mut rng = rand::thread_rng();
+        let fault = if rng.gen_bool(faults.drop_rate.clamp(0.0, 1.0)) {
+            Fault::Drop
+        } else if rng.gen_bool(faults.rate_limit_rate.clamp(0.0, 1.0)) {
+            Fault::RateLimit
+        } else {
+            return None;
+        };
+        state.injected += 1;
+        Some(fault)
+    }
+
+    async fn handle_rpc(
+        self,
+        request: Request<Body>,
+        faults: Faults,
+    ) -> Result<Response<Body>, String> {
+        let (mut parts, body) = request.into_parts();
+        let body = to_bytes(body).await.map_err(|e| format!("{e:?}"))?;
+        let call: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        let method = call["method"].as_str().unwrap_or("unknown").to_string();
+        match self.roll(&method, &faults) {
+            Some(Fault::Drop) => return Err(format!("Dropped {method}")),
+            Some(Fault::RateLimit) => {
+                let error = json!({
+                    "jsonrpc": "2.0",
+                    "id": call["id"],
+                    "error": {"code": -32005, "message": "rate limit exceeded"},
+                });
+                return Ok(Response::builder()
+                    .status(StatusCode::TOO_MANY_REQUESTS)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(error.to_string()))
+                    .unwrap());
+            }
+            None => {}
+        }
+
+        parts.uri = self.upstream.clone();
+        parts.headers.remove(header::HOST);
+        let response = self
+            .client
+            .request(Request::from_parts(parts, Body::from(body)))
+            .await
+            .map_err(|e| format!("Upstream failed {e:?}"))?;
+        if method != "eth_blockNumber" || faults.stale_blocks == 0 {
+            return Ok(response);
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let body = to_bytes(body).await.map_err(|e| format!("{e:?}"))?;
+        match self.stale_block_number(&body, &faults) {
+            Some(rewritten) => {
+                parts.headers.remove(header::CONTENT_LENGTH);
+                Ok(Response::from_parts(parts, Body::from(rewritten)))
+            }
+            None => Ok(Response::from_parts(parts, Body::from(body))),
+        }
+    }
+
+    /// Moves the block number in an `eth_blockNumber` answer back by the stale blocks
+    fn stale_block_number(&self, body: &[u8], faults: &Faults) -> Option<Vec<u8>> {
+        let mut response: Value = serde_json::from_slice(body).ok()?;
+        let head = parse_quantity(response["result"].as_str()?)?;
+        response["result"] = json!(format!("{:#x}", head.saturating_sub(faults.stale_blocks)));
+        self.state.lock().unwrap().injected += 1;
+        serde_json::to_vec(&response).ok()
+    }
+
+    async fn handle_grpc(
+        self,
+        request: Request<Body>,
+        faults: Faults,
+    ) -> Result<Response<Body>, String> {
+        let path = request.uri().path().to_string();
+        match self.roll(&path, &faults) {
+            Some(Fault::Drop) => return Err(format!("Dropped {path}")),
+            // a trailers only response, which is how servers answer with an error status
+            Some(Fault::RateLimit) => {
+                return Ok(Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "application/grpc")
+                    .header(
+                        "grpc-status",
+                        (tonic::Code::ResourceExhausted as i32).to_string(),
+                    )
+                    .header("grpc-message", "rate limit exceeded")
+                    .body(Body::empty())
+                    .unwrap());
+            }
+            None => {}
+        }
+
+        let (mut parts, body) = request.into_parts();
+        let path_and_query = parts
+            .uri
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/");
+        parts.uri = format!(
+            "{}://{}{}",
+            self.upstream.scheme_str().unwrap_or("http"),
+            self.upstream
+                .authority()
+                .map(|a| a.as_str())
+                .unwrap_or_default(),
+            path_and_query
+        )
+        .parse()
+        .map_err(|e| format!("{e:?}"))?;
+        parts.headers.remove(header::HOST);
+        self.client
+            .request(Request::from_parts(parts, body))
+            .await
+            .map_err(|e| format!("Upstream failed {e:?}"))
+    }
+}
+
+enum Fault {
+    Drop,
+    RateLimit,
+}
+
+fn parse_quantity(quantity: &str) -> Option<u64> {
+    u64::from_str_radix(quantity.trim_start_matches("0x"), 16).ok()
+}
+
+#[tokio::test]
+async fn test_rpc_faults() {
+    // an Ethereum node at block 0x64
+    let node =
+        Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|request: Request<Body>| async {
+                let call: Value =
+                    serde_json::from_slice(&to_bytes(request.into_body()).await?).unwrap();
+                let result = match call["method"].as_str().unwrap() {
+                    "eth_blockNumber" => json!("0x64"),
+                    "eth_getLogs" => json!([]),
+                    _ => json!(null),
+                };
+                let body = json!({"jsonrpc": "2.0", "id": call["id"], "result": result});
+                Ok::<_, hyper::Error>(Response::new(Body::from(body.to_string())))
+            }))
+        }));
+    let node_url = format!("http://{}", node.local_addr());
+    tokio::spawn(node);
+
+    let proxy = FaultProxy::start(&node_url, Protocol::EthereumRpc);
+    let call = |method: &str| {
+        let request = Request::post(proxy.url())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": []}).to_string(),
+            ))
+            .unwrap();
+        async move {
+            let response = Client::new().request(request).await?;
+            let status = response.status();
+            let body: Value =
+                serde_json::from_slice(&to_bytes(response.into_body()).await?).unwrap();
+            Ok::<_, hyper::Error>((status, body))
+        }
+    };
+
+    let (_, body) = call("eth_blockNumber").await.unwrap();
+    assert_eq!(body["result"], "0x64");
+    assert_eq!(proxy.injected_faults(), 0);
+
+    proxy.set_faults(Faults {
+        stale_blocks: 4,
+        ..Default::default()
+    });
+    let (_, body) = call("eth_blockNumber").await.unwrap();
+    assert_eq!(body["result"], "0x60");
+    let (_, body) = call("eth_getLogs").await.unwrap();
+    assert_eq!(body["result"], json!([]));
+    assert_eq!(proxy.injected_faults(), 1);
+
+    proxy.set_faults(Faults {
+        rate_limit_rate: 1.0,
+        ..Default::default()
+    });
+    let (status, body) = call("eth_blockNumber").await.unwrap();
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(body["error"]["code"], -32005);
+    assert_eq!(body["id"], 1);
+
+    proxy.set_faults(Faults {
+        drop_rate: 1.0,
+        ..Default::default()
+    });
+    assert!(call("eth_blockNumber").await.is_err());
+    assert_eq!(proxy.request_count("eth_blockNumber"), 4);
+    assert_eq!(proxy.request_count("eth_getLogs"), 1);
+
+    proxy.clear_faults();
+    let (_, body) = call("eth_blockNumber").await.unwrap();
+    assert_eq!(body["result"], "0x64");
+}
@@ -6,43 +6,37 @@
 #[macro_use]
 extern crate log;
 
-use std::{env, time::Duration};
+use std::{env, process, time::Duration};
 
-use evidence_based_slashing::evidence_based_slashing;
+use clap::Parser;
 use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
 use gravity_utils::{
     clarity::{u256, Address as EthAddress, PrivateKey as EthPrivateKey, Uint256},
     deep_space::{coin::Coin, Contact},
     get_block_delay, get_expected_block_delay,
     get_with_retry::get_net_version_with_retry,
-    u64_array_bigints, DEFAULT_ADDRESS_PREFIX, GRAVITY_DENOM_PREFIX,
-    TEST_DEFAULT_ETH_NODE_ENDPOINT, TEST_DEFAULT_MINER_KEY, TEST_ETH_CHAIN_ID, TEST_GAS_LIMIT,
+    u64_array_bigints, GRAVITY_DENOM_PREFIX, TEST_ETH_CHAIN_ID, TEST_GAS_LIMIT,
     TEST_RUN_BLOCK_STIMULATOR, USE_FINALIZATION,
 };
-use happy_path::happy_path_test;
-use happy_path_v2::happy_path_test_v2;
 use lazy_static::lazy_static;
-use orch_keys::orch_keys;
-use relay_market::relay_market_test;
-use remote_stress_test::remote_stress_test;
-use transaction_stress_test::transaction_stress_test;
-use unhalt_bridge::unhalt_bridge_test;
-use valset_stress::validator_set_stress_test;
 
 use crate::{
-    airdrop_proposal::airdrop_proposal_test, bootstrapping::*,
-    deposit_overflow::deposit_overflow_test, ethereum_blacklist_test::ethereum_blacklist_test,
-    ibc_metadata::ibc_metadata_proposal_test, invalid_events::invalid_events,
-    pause_bridge::pause_bridge_test, signature_slashing::signature_slashing_test,
-    slashing_delegation::slashing_delegation_test, tx_cancel::send_to_eth_and_cancel, utils::*,
-    valset_rewards::valset_rewards_test,
+    args::{Command, Opts, RunOpts},
+    bootstrapping::*,
+    config::RunnerConfig,
+    scenarios::{find_scenario, run_scenario, write_report, Outcome, Scenario, TestEnv, SCENARIOS},
+    utils::*,
 };
 
 mod airdrop_proposal;
+mod args;
 mod bootstrapping;
+mod chaos;
+mod config;
 mod deposit_overflow;
 mod ethereum_blacklist_test;
 mod evidence_based_slashing;
+mod fault_proxy;
 mod happy_path;
 mod happy_path_v2;
 mod ibc_metadata;
@@ -51,6 +45,7 @@ mod orch_keys;
 mod pause_bridge;
 mod relay_market;
 mod remote_stress_test;
+mod scenarios;
 mod signature_slashing;
 mod slashing_delegation;
 mod transaction_stress_test;
@@ -65,18 +60,18 @@ const OPERATION_TIMEOUT: Duration = Duration::from_secs(120);
 /// the timeout for the total system
 const TOTAL_TIMEOUT: Duration = Duration::from_secs(3600);
 
-// Retrieve values from runtime ENV vars
+// Retrieve values from flags, runtime ENV vars or the config file
 lazy_static! {
-    static ref ADDRESS_PREFIX: String =
-        env::var("ADDRESS_PREFIX").unwrap_or_else(|_| DEFAULT_ADDRESS_PREFIX.to_owned());
-    static ref STAKING_TOKEN: String =
-        env::var("STAKING_TOKEN").unwrap_or_else(|_| "stake".to_owned());
-    static ref COSMOS_NODE_GRPC: String =
-        env::var("COSMOS_NODE_GRPC").unwrap_or_else(|_| "http://localhost:9090".to_owned());
-    static ref COSMOS_NODE_ABCI: String =
-        env::var("COSMOS_NODE_ABCI").unwrap_or_else(|_| "http://localhost:26657".to_owned());
-    static ref ETH_NODE: String =
-        env::var("ETH_NODE").unwrap_or_else(|_| TEST_DEFAULT_ETH_NODE_ENDPOINT.to_owned());
+    static ref OPTS: Opts = Opts::parse();
+    static ref CONFIG: RunnerConfig = RunnerConfig::load(&OPTS.config).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1)
+    });
+    static ref ADDRESS_PREFIX: String = CONFIG.address_prefix.clone();
+    static ref STAKING_TOKEN: String = CONFIG.staking_token.clone();
+    static ref COSMOS_NODE_GRPC: String = CONFIG.cosmos_grpc.clone();
+    static ref COSMOS_NODE_ABCI: String = CONFIG.cosmos_abci.clone();
+    static ref ETH_NODE: String = CONFIG.eth_node.clone();
 }
 
 /// this value reflects the contents of /tests/container-scripts/setup-validator.sh
@@ -90,10 +85,7 @@ lazy_static! {
     // this key is the private key for the public key defined in tests/assets/ETHGenesis.json
     // where the full node / miner sends its rewards. Therefore it's always going
     // to have a lot of ETH to pay for things like contract deployments
-    static ref MINER_PRIVATE_KEY: EthPrivateKey = env::var("MINER_PRIVATE_KEY").unwrap_or_else(|_|
-        TEST_DEFAULT_MINER_KEY.to_owned()
-            ).parse()
-            .unwrap();
+    static ref MINER_PRIVATE_KEY: EthPrivateKey = CONFIG.miner_private_key;
     static ref MINER_ADDRESS: EthAddress = MINER_PRIVATE_KEY.to_address();
 }
 
@@ -132,20 +124,95 @@ pub fn should_deploy_contracts() -> bool {
     }
 }
 
-#[tokio::main]
-pub async fn main() {
+/// Without a subcommand the docker test scripts select what to do with environment variables
+fn legacy_command() -> Command {
     if matches!(env::var("GET_TEST_ADDRESS").as_deref(), Ok("1")) {
-        // A way to get the correct Bech32 to the scripts. Derived from the
-        // sha256 hash of 'distribution' to create the address of the module
-        let data = bech32::decode("gravity1jv65s3grqf6v6jl3dp4t6c9t9rk99cd8r0kyvh")
-            .unwrap()
-            .1;
-        println!(
-            "{}",
-            bech32::encode(&ADDRESS_PREFIX, data, bech32::Variant::Bech32).unwrap()
-        );
-        return;
+        Command::TestAddress
+    } else if should_deploy_contracts() {
+        Command::DeployContracts
+    } else {
+        let scenarios = env::var("TEST_TYPE")
+            .map(|t| t.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+        Command::Run(RunOpts {
+            scenarios,
+            ..Default::default()
+        })
+    }
+}
+
+/// Looks up the scenarios to run, an empty list runs the happy path
+fn resolve_scenarios(names: &[String]) -> Result<Vec<&'static Scenario>, String> {
+    let names: Vec<&str> = names
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Ok(vec![&SCENARIOS[0]]);
     }
+    names
+        .into_iter()
+        .map(|n| {
+            find_scenario(n).ok_or_else(|| {
+                format!("Unknown scenario {n}, run `test-runner list` for the registered scenarios")
+            })
+        })
+        .collect()
+}
+
+async fn test_env(gravity_address: EthAddress, erc20_addresses: &[EthAddress]) -> TestEnv {
+    TestEnv {
+        web30: gravity_utils::web30::client::Web3::new(ETH_NODE.as_str(), OPERATION_TIMEOUT),
+        contact: Contact::new(
+            COSMOS_NODE_GRPC.as_str(),
+            OPERATION_TIMEOUT,
+            ADDRESS_PREFIX.as_str(),
+        )
+        .unwrap(),
+        grpc_client: GravityQueryClient::connect(COSMOS_NODE_GRPC.as_str())
+            .await
+            .unwrap(),
+        keys: get_keys(),
+        gravity_address,
+        erc20_addresses: erc20_addresses.to_vec(),
+    }
+}
+
+#[tokio::main]
+pub async fn main() {
+    let command = OPTS.command.clone().unwrap_or_else(legacy_command);
+    let run_opts = match command {
+        Command::TestAddress => {
+            // A way to get the correct Bech32 to the scripts. Derived from the
+            // sha256 hash of 'distribution' to create the address of the module
+            let data = bech32::decode("gravity1jv65s3grqf6v6jl3dp4t6c9t9rk99cd8r0kyvh")
+                .unwrap()
+                .1;
+            println!(
+                "{}",
+                bech32::encode(&ADDRESS_PREFIX, data, bech32::Variant::Bech32).unwrap()
+            );
+            return;
+        }
+        Command::List => {
+            for s in SCENARIOS {
+                println!("{:<20} {}", s.name, s.description);
+            }
+            return;
+        }
+        Command::DeployContracts => None,
+        Command::Run(opts) => Some(opts),
+    };
+    // fail on a typo before waiting for the chains
+    let scenarios = match run_opts.as_ref().map(|o| resolve_scenarios(&o.scenarios)) {
+        Some(Ok(s)) => s,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => Vec::new(),
+    };
 
     env_logger::init();
 
@@ -160,9 +227,6 @@ pub async fn main() {
     info!("Waiting for Cosmos chain to come online");
     wait_for_cosmos_online(&contact, TOTAL_TIMEOUT).await;
 
-    let grpc_client = GravityQueryClient::connect(COSMOS_NODE_GRPC.as_str())
-        .await
-        .unwrap();
     let web30 = gravity_utils::web30::client::Web3::new(ETH_NODE.as_str(), OPERATION_TIMEOUT);
     let keys = get_keys();
 
@@ -266,12 +330,15 @@ pub async fn main() {
         });
     }
 
-    // if we detect this env var we are only deploying contracts, do that then exit.
-    if should_deploy_contracts() {
-        info!("test-runner in contract deploying mode, deploying contracts, then exiting");
-        deploy_contracts(&contact).await;
-        return;
-    }
+    // in contract deploying mode we only deploy contracts, do that then exit.
+    let run_opts = match run_opts {
+        Some(opts) => opts,
+        None => {
+            info!("test-runner in contract deploying mode, deploying contracts, then exiting");
+            deploy_contracts(&contact).await;
+            return;
+        }
+    };
 
     let contracts = parse_contract_addresses();
     // the address of the deployed Gravity contract
@@ -297,165 +364,27 @@ pub async fn main() {
             .is_some());
     }
 
-    // This segment contains optional tests, by default we run a happy path test
-    // this tests all major functionality of Gravity once or twice.
-    // VALSET_STRESS sends in 1k valsets to sign and update
-    // BATCH_STRESS fills several batches and executes an out of order batch
-    // VALIDATOR_OUT simulates a validator not participating in the happy path test
-    // V2_HAPPY_PATH runs the happy path tests but focusing on moving Cosmos assets to Ethereum
-    // ARBITRARY_LOGIC tests the arbitrary logic functionality, where an arbitrary contract call
-    //                 is created and deployed vai the bridge.
-    let test_type = env::var("TEST_TYPE");
-    info!("Starting tests with {:?}", test_type);
-    if let Ok(test_type) = test_type {
-        if test_type == "VALIDATOR_OUT" {
-            info!("Starting Validator out test");
-            happy_path_test(
-                &web30,
-                grpc_client,
-                &contact,
-                keys,
-                gravity_address,
-                erc20_addresses[0],
-                true,
-            )
-            .await;
-            return;
-        } else if test_type == "BATCH_STRESS" {
-            let contact = Contact::new(
-                COSMOS_NODE_GRPC.as_str(),
-                TOTAL_TIMEOUT,
-                ADDRESS_PREFIX.as_str(),
-            )
-            .unwrap();
-            transaction_stress_test(
-                &web30,
-                &contact,
-                grpc_client,
-                keys,
-                gravity_address,
-                erc20_addresses,
-            )
-            .await;
-            return;
-        } else if test_type == "REMOTE_STRESS" {
-            let contact = Contact::new(
-                COSMOS_NODE_GRPC.as_str(),
-                TOTAL_TIMEOUT,
-                ADDRESS_PREFIX.as_str(),
-            )
-            .unwrap();
-            remote_stress_test(&web30, &contact, keys, gravity_address, erc20_addresses).await;
-            return;
-        } else if test_type == "VALSET_STRESS" {
-            info!("Starting Valset update stress test");
-            validator_set_stress_test(&web30, grpc_client, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "VALSET_REWARDS" {
-            info!("Starting Valset rewards test");
-            valset_rewards_test(&web30, grpc_client, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "V2_HAPPY_PATH" || test_type == "HAPPY_PATH_V2" {
-            info!("Starting happy path for Gravity v2");
-            happy_path_test_v2(&web30, grpc_client, &contact, keys, gravity_address, false).await;
-            return;
-        } else if test_type == "RELAY_MARKET" {
-            info!("Starting relay market tests!");
-            relay_market_test(&web30, grpc_client, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "ORCHESTRATOR_KEYS" {
-            info!("Starting orchestrator key update tests!");
-            orch_keys(grpc_client, &contact, keys).await;
-            return;
-        } else if test_type == "EVIDENCE" {
-            info!("Starting evidence based slashing tests!");
-            evidence_based_slashing(&web30, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "TXCANCEL" {
-            info!("Starting SendToEth cancellation test!");
-            send_to_eth_and_cancel(
-                &contact,
-                grpc_client,
-                &web30,
-                keys,
-                gravity_address,
-                erc20_addresses[0],
-            )
-            .await;
-            return;
-        } else if test_type == "INVALID_EVENTS" {
-            info!("Starting invalid events test!");
-            invalid_events(
-                &web30,
-                &contact,
-                keys,
-                gravity_address,
-                erc20_addresses[0],
-                grpc_client,
-            )
-            .await;
-            return;
-        } else if test_type == "UNHALT_BRIDGE" {
-            info!("Starting unhalt bridge tests");
-            unhalt_bridge_test(
-                &web30,
-                grpc_client,
-                &contact,
-                keys,
-                gravity_address,
-                erc20_addresses[0],
-            )
-            .await;
-            return;
-        } else if test_type == "PAUSE_BRIDGE" {
-            info!("Starting pause bridge tests");
-            pause_bridge_test(
-                &web30,
-                grpc_client,
-                &contact,
-                keys,
-                gravity_address,
-                erc20_addresses[0],
-            )
-            .await;
-            return;
-        } else if test_type == "DEPOSIT_OVERFLOW" {
-            info!("Starting deposit overflow test!");
-            deposit_overflow_test(&web30, &contact, keys, erc20_addresses, grpc_client).await;
-            return;
-        } else if test_type == "ETHEREUM_BLACKLIST" {
-            info!("Starting ethereum blacklist test");
-            ethereum_blacklist_test(grpc_client, &contact, keys).await;
-            return;
-        } else if test_type == "AIRDROP_PROPOSAL" {
-            info!("Starting airdrop governance proposal test");
-            airdrop_proposal_test(&contact, keys).await;
-            return;
-        } else if test_type == "SIGNATURE_SLASHING" {
-            info!("Starting Signature Slashing test");
-            signature_slashing_test(&web30, grpc_client, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "SLASHING_DELEGATION" {
-            info!("Starting Slashing Delegation test");
-            slashing_delegation_test(&web30, grpc_client, &contact, keys, gravity_address).await;
-            return;
-        } else if test_type == "IBC_METADATA" {
-            info!("Starting IBC metadata proposal test");
-            ibc_metadata_proposal_test(gravity_address, keys, grpc_client, &contact, &web30).await;
-            return;
-        } else if !test_type.is_empty() {
-            panic!("Err Unknown test type")
+    let mut results = Vec::new();
+    for scenario in scenarios {
+        let env = test_env(gravity_address, &erc20_addresses).await;
+        let timeout = run_opts
+            .timeout
+            .map(Duration::from_secs)
+            .unwrap_or(scenario.timeout);
+        let result = run_scenario(scenario, env, timeout).await;
+        let passed = result.outcome == Outcome::Passed;
+        results.push(result);
+        if !passed && !run_opts.keep_going {
+            break;
         }
     }
-    info!("Starting Happy path test");
-    happy_path_test(
-        &web30,
-        grpc_client,
-        &contact,
-        keys,
-        gravity_address,
-        erc20_addresses[0],
-        false,
-    )
-    .await;
+
+    if let Some(path) = &run_opts.report {
+        if let Err(e) = write_report(path, run_opts.report_format, &results) {
+            error!("Failed to write the report to {:?} {:?}", path, e);
+        }
+    }
+    if results.iter().any(|r| r.outcome != Outcome::Passed) {
+        process::exit(1);
+    }
 }
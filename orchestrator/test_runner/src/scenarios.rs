@@ -0,0 +1,591 @@
+//! The registry of integration test scenarios and the runner that executes them with a
+//! timeout and reports the results. To add a scenario write an `async fn` taking a `TestEnv`
+//! and add it to `SCENARIOS`, scenarios signal failure by panicking
+
+use std::{
+    any::Any,
+    fmt::Write as _,
+    fs,
+    panic::AssertUnwindSafe,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use futures::{future::LocalBoxFuture, FutureExt};
+use gravity_proto::gravity::query_client::QueryClient as GravityQueryClient;
+use gravity_utils::{clarity::Address as EthAddress, deep_space::Contact, web30::client::Web3};
+use serde::Serialize;
+use tonic::transport::Channel;
+
+use crate::{
+    airdrop_proposal::airdrop_proposal_test,
+    args::ReportFormat,
+    chaos::{chaos_test, Chaos},
+    deposit_overflow::deposit_overflow_test,
+    ethereum_blacklist_test::ethereum_blacklist_test,
+    evidence_based_slashing::evidence_based_slashing,
+    happy_path::happy_path_test,
+    happy_path_v2::happy_path_test_v2,
+    ibc_metadata::ibc_metadata_proposal_test,
+    invalid_events::invalid_events,
+    orch_keys::orch_keys,
+    pause_bridge::pause_bridge_test,
+    relay_market::relay_market_test,
+    remote_stress_test::remote_stress_test,
+    signature_slashing::signature_slashing_test,
+    slashing_delegation::slashing_delegation_test,
+    transaction_stress_test::transaction_stress_test,
+    tx_cancel::send_to_eth_and_cancel,
+    unhalt_bridge::unhalt_bridge_test,
+    utils::{stop_orchestrators, ValidatorKeys},
+    valset_rewards::valset_rewards_test,
+    valset_stress::validator_set_stress_test,
+    ADDRESS_PREFIX, COSMOS_NODE_GRPC, TOTAL_TIMEOUT,
+};
+
+/// The connections, keys and contracts a scenario runs against, built fresh for every
+/// scenario since most of them take ownership of the gRPC client and keys
+pub struct TestEnv {
+    pub web30: Web3,
+    pub contact: Contact,
+    pub grpc_client: GravityQueryClient<Channel>,
+    pub keys: Vec<ValidatorKeys>,
+    pub gravity_address: EthAddress,
+    pub erc20_addresses: Vec<EthAddress>,
+}
+
+pub struct Scenario {
+    /// the name used on the command line and in TEST_TYPE
+    pub name: &'static str,
+    /// older names that still select this scenario
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    pub timeout: Duration,
+    pub run: fn(TestEnv) -> LocalBoxFuture<'static, ()>,
+}
+
+/// Stress tests move enough transactions that they get twice the usual time
+const STRESS_TIMEOUT: Duration = Duration::from_secs(2 * 3600);
+
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "HAPPY_PATH",
+        aliases: &[],
+        description: "Tests all major functionality of Gravity once or twice",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| happy_path(env, false).boxed_local(),
+    },
+    Scenario {
+        name: "VALIDATOR_OUT",
+        aliases: &[],
+        description: "The happy path with a validator not participating",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| happy_path(env, true).boxed_local(),
+    },
+    Scenario {
+        name: "BATCH_STRESS",
+        aliases: &[],
+        description: "Fills several batches and executes an out of order batch",
+        timeout: STRESS_TIMEOUT,
+        run: |env| batch_stress(env).boxed_local(),
+    },
+    Scenario {
+        name: "REMOTE_STRESS",
+        aliases: &[],
+        description: "Batch stress against a remote chain, sized by NUM_USERS",
+        timeout: STRESS_TIMEOUT,
+        run: |env| remote_stress(env).boxed_local(),
+    },
+    Scenario {
+        name: "VALSET_STRESS",
+        aliases: &[],
+        description: "Sends in many validator sets to sign and update",
+        timeout: STRESS_TIMEOUT,
+        run: |env| valset_stress(env).boxed_local(),
+    },
+    Scenario {
+        name: "VALSET_REWARDS",
+        aliases: &[],
+        description: "Relayers are paid the valset reward",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| valset_rewards(env).boxed_local(),
+    },
+    Scenario {
+        name: "V2_HAPPY_PATH",
+        aliases: &["HAPPY_PATH_V2"],
+        description: "The happy path for moving Cosmos assets to Ethereum",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| happy_path_v2(env).boxed_local(),
+    },
+    Scenario {
+        name: "RELAY_MARKET",
+        aliases: &[],
+        description: "Batches are relayed only when profitable",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| relay_market(env).boxed_local(),
+    },
+    Scenario {
+        name: "ORCHESTRATOR_KEYS",
+        aliases: &[],
+        description: "Orchestrator delegate keys can be updated",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| orchestrator_keys(env).boxed_local(),
+    },
+    Scenario {
+        name: "EVIDENCE",
+        aliases: &[],
+        description: "Evidence based slashing of bad signatures",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| evidence(env).boxed_local(),
+    },
+    Scenario {
+        name: "TXCANCEL",
+        aliases: &[],
+        description: "A SendToEth can be cancelled before it is batched",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| tx_cancel(env).boxed_local(),
+    },
+    Scenario {
+        name: "INVALID_EVENTS",
+        aliases: &[],
+        description: "Malformed Ethereum events do not halt the bridge",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| invalid_events_scenario(env).boxed_local(),
+    },
+    Scenario {
+        name: "UNHALT_BRIDGE",
+        aliases: &[],
+        description: "A halted bridge is restarted by governance",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| unhalt_bridge(env).boxed_local(),
+    },
+    Scenario {
+        name: "PAUSE_BRIDGE",
+        aliases: &[],
+        description: "The bridge can be paused and resumed by governance",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| pause_bridge(env).boxed_local(),
+    },
+    Scenario {
+        name: "DEPOSIT_OVERFLOW",
+        aliases: &[],
+        description: "Deposits that would overflow the supply are rejected",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| deposit_overflow(env).boxed_local(),
+    },
+    Scenario {
+        name: "ETHEREUM_BLACKLIST",
+        aliases: &[],
+        description: "Blacklisted Ethereum addresses can not receive funds",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| ethereum_blacklist(env).boxed_local(),
+    },
+    Scenario {
+        name: "AIRDROP_PROPOSAL",
+        aliases: &[],
+        description: "The airdrop governance proposal",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| airdrop_proposal(env).boxed_local(),
+    },
+    Scenario {
+        name: "SIGNATURE_SLASHING",
+        aliases: &[],
+        description: "Validators that do not sign are slashed",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| signature_slashing(env).boxed_local(),
+    },
+    Scenario {
+        name: "SLASHING_DELEGATION",
+        aliases: &[],
+        description: "Delegations to a slashed validator",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| slashing_delegation(env).boxed_local(),
+    },
+    Scenario {
+        name: "IBC_METADATA",
+        aliases: &[],
+        description: "The IBC metadata governance proposal",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| ibc_metadata(env).boxed_local(),
+    },
+    Scenario {
+        name: "CHAOS_ETH_RPC",
+        aliases: &[],
+        description: "The bridge works through a slow, lossy and rate limited Ethereum node",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| chaos(env, Chaos::EthereumRpc).boxed_local(),
+    },
+    Scenario {
+        name: "CHAOS_COSMOS_GRPC",
+        aliases: &[],
+        description: "The bridge works through a slow, lossy and rate limited Cosmos node",
+        timeout: TOTAL_TIMEOUT,
+        run: |env| chaos(env, Chaos::CosmosGrpc).boxed_local(),
+    },
+];
+
+async fn happy_path(env: TestEnv, validator_out: bool) {
+    happy_path_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+        validator_out,
+    )
+    .await
+}
+
+/// the stress tests wait on large transactions and need a longer request timeout
+fn long_timeout_contact() -> Contact {
+    Contact::new(
+        COSMOS_NODE_GRPC.as_str(),
+        TOTAL_TIMEOUT,
+        ADDRESS_PREFIX.as_str(),
+    )
+    .unwrap()
+}
+
+async fn batch_stress(env: TestEnv) {
+    transaction_stress_test(
+        &env.web30,
+        &long_timeout_contact(),
+        env.grpc_client,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses,
+    )
+    .await
+}
+
+async fn remote_stress(env: TestEnv) {
+    remote_stress_test(
+        &env.web30,
+        &long_timeout_contact(),
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses,
+    )
+    .await
+}
+
+async fn valset_stress(env: TestEnv) {
+    validator_set_stress_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+    )
+    .await
+}
+
+async fn valset_rewards(env: TestEnv) {
+    valset_rewards_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+    )
+    .await
+}
+
+async fn happy_path_v2(env: TestEnv) {
+    happy_path_test_v2(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        false,
+    )
+    .await
+}
+
+async fn relay_market(env: TestEnv) {
+    relay_market_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+    )
+    .await
+}
+
+async fn orchestrator_keys(env: TestEnv) {
+    orch_keys(env.grpc_client, &env.contact, env.keys).await
+}
+
+async fn evidence(env: TestEnv) {
+    evidence_based_slashing(&env.web30, &env.contact, env.keys, env.gravity_address).await
+}
+
+async fn tx_cancel(env: TestEnv) {
+    send_to_eth_and_cancel(
+        &env.contact,
+        env.grpc_client,
+        &env.web30,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+    )
+    .await
+}
+
+async fn invalid_events_scenario(env: TestEnv) {
+    invalid_events(
+        &env.web30,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+        env.grpc_client,
+    )
+    .await
+}
+
+async fn unhalt_bridge(env: TestEnv) {
+    unhalt_bridge_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+    )
+    .await
+}
+
+async fn pause_bridge(env: TestEnv) {
+    pause_bridge_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+    )
+    .await
+}
+
+async fn deposit_overflow(env: TestEnv) {
+    deposit_overflow_test(
+        &env.web30,
+        &env.contact,
+        env.keys,
+        env.erc20_addresses,
+        env.grpc_client,
+    )
+    .await
+}
+
+async fn ethereum_blacklist(env: TestEnv) {
+    ethereum_blacklist_test(env.grpc_client, &env.contact, env.keys).await
+}
+
+async fn airdrop_proposal(env: TestEnv) {
+    airdrop_proposal_test(&env.contact, env.keys).await
+}
+
+async fn signature_slashing(env: TestEnv) {
+    signature_slashing_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+    )
+    .await
+}
+
+async fn slashing_delegation(env: TestEnv) {
+    slashing_delegation_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+    )
+    .await
+}
+
+async fn ibc_metadata(env: TestEnv) {
+    ibc_metadata_proposal_test(
+        env.gravity_address,
+        env.keys,
+        env.grpc_client,
+        &env.contact,
+        &env.web30,
+    )
+    .await
+}
+
+async fn chaos(env: TestEnv, chaos: Chaos) {
+    chaos_test(
+        &env.web30,
+        env.grpc_client,
+        &env.contact,
+        env.keys,
+        env.gravity_address,
+        env.erc20_addresses[0],
+        chaos,
+    )
+    .await
+}
+
+/// Finds a scenario by name or alias, ignoring case
+pub fn find_scenario(name: &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|s| {
+        s.name.eq_ignore_ascii_case(name) || s.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "message")]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioResult {
+    pub name: &'static str,
+    pub outcome: Outcome,
+    pub seconds: f64,
+}
+
+/// Runs a scenario to completion, a panic fails the scenario instead of the runner. The
+/// orchestrators the scenario started are stopped however it ends
+pub async fn run_scenario(scenario: &Scenario, env: TestEnv, timeout: Duration) -> ScenarioResult {
+    info!("Starting scenario {}", scenario.name);
+    let start = Instant::now();
+    let run = AssertUnwindSafe((scenario.run)(env)).catch_unwind();
+    let outcome = match tokio::time::timeout(timeout, run).await {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(panic)) => Outcome::Failed(panic_message(panic)),
+        Err(_) => Outcome::TimedOut,
+    };
+    stop_orchestrators().await;
+    let result = ScenarioResult {
+        name: scenario.name,
+        outcome,
+        seconds: start.elapsed().as_secs_f64(),
+    };
+    match &result.outcome {
+        Outcome::Passed => info!("Scenario {} passed in {:.0}s", result.name, result.seconds),
+        Outcome::Failed(e) => error!("Scenario {} failed with {}", result.name, e),
+        Outcome::TimedOut => error!("Scenario {} timed out after {:?}", result.name, timeout),
+    }
+    result
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+pub fn write_report(
+    path: &Path,
+    format: ReportFormat,
+    results: &[ScenarioResult],
+) -> Result<(), std::io::Error> {
+    let report = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(results).unwrap(),
+        ReportFormat::Junit => junit_report(results),
+    };
+    fs::write(path, report)
+}
+
+fn junit_report(results: &[ScenarioResult]) -> String {
+    let failures = results
+        .iter()
+        .filter(|r| matches!(r.outcome, Outcome::Failed(_)))
+        .count();
+    let errors = results
+        .iter()
+        .filter(|r| r.outcome == Outcome::TimedOut)
+        .count();
+    let total: f64 = results.iter().map(|r| r.seconds).sum();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuite name=\"gravity-integration\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{total:.3}\">",
+        results.len()
+    );
+    for r in results {
+        let _ = write!(
+            xml,
+            "  <testcase classname=\"test_runner\" name=\"{}\" time=\"{:.3}\"",
+            r.name, r.seconds
+        );
+        match &r.outcome {
+            Outcome::Passed => xml.push_str("/>\n"),
+            Outcome::Failed(e) => {
+                let _ = writeln!(
+                    xml,
+                    ">\n    <failure message=\"{}\"/>\n  </testcase>",
+                    xml_escape(e)
+                );
+            }
+            Outcome::TimedOut => {
+                xml.push_str(">\n    <error message=\"timed out\"/>\n  </testcase>\n");
+            }
+        }
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+#[test]
+fn test_registry_and_report() {
+    let mut names: Vec<&str> = SCENARIOS.iter().map(|s| s.name).collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), SCENARIOS.len());
+    assert_eq!(
+        find_scenario("happy_path_v2").unwrap().name,
+        "V2_HAPPY_PATH"
+    );
+    assert!(find_scenario("NOT_A_TEST").is_none());
+
+    let results = [
+        ScenarioResult {
+            name: "HAPPY_PATH",
+            outcome: Outcome::Passed,
+            seconds: 1.0,
+        },
+        ScenarioResult {
+            name: "EVIDENCE",
+            outcome: Outcome::Failed("assertion `a < b` failed".to_string()),
+            seconds: 2.0,
+        },
+        ScenarioResult {
+            name: "BATCH_STRESS",
+            outcome: Outcome::TimedOut,
+            seconds: 3.0,
+        },
+    ];
+    let xml = junit_report(&results);
+    assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"1\""));
+    assert!(xml.contains("<failure message=\"assertion `a &lt; b` failed\"/>"));
+    let json = serde_json::to_value(&results).unwrap();
+    assert_eq!(json[1]["outcome"]["status"], "failed");
+    assert_eq!(json[2]["outcome"]["status"], "timed_out");
+}
@@ -1,4 +1,4 @@
-use std::{panic, time::Duration};
+use std::{panic, sync::Mutex, time::Duration};
 
 use cosmos_gravity::{proposals::submit_parameter_change_proposal, query::get_gravity_params};
 use ethereum_gravity::utils::get_event_nonce;
@@ -23,9 +23,10 @@ use gravity_utils::{
     web30::{client::Web3, jsonrpc::error::Web3Error, types::SendTxOption},
     TEST_GAS_LIMIT,
 };
+use lazy_static::lazy_static;
 use orchestrator::main_loop::orchestrator_main_loop;
 use rand::Rng;
-use tokio::time::sleep;
+use tokio::{sync::watch, task::JoinHandle, time::sleep};
 
 use crate::{
     get_deposit, get_fee, ADDRESS_PREFIX, COSMOS_NODE_GRPC, ETH_NODE, MINER_ADDRESS,
@@ -276,6 +277,12 @@ pub struct ValidatorKeys {
     pub validator_key: CosmosPrivateKey,
 }
 
+lazy_static! {
+    /// The orchestrators spawned by the running scenario, aborted by `stop_orchestrators` so
+    /// they don't keep signing and relaying into the next scenario run by the same process
+    static ref ORCHESTRATORS: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+}
+
 /// This function pays the piper for the strange concurrency model that we use for the tests
 /// we spwan a thread, create a tokio executor and then start the orchestrator within that scope
 pub async fn start_orchestrators(
@@ -283,6 +290,27 @@ pub async fn start_orchestrators(
     gravity_address: EthAddress,
     validator_out: bool,
     orchestrator_config: GravityBridgeToolsConfig,
+) {
+    start_orchestrators_with_endpoints(
+        keys,
+        gravity_address,
+        validator_out,
+        orchestrator_config,
+        &ETH_NODE,
+        &COSMOS_NODE_GRPC,
+    )
+    .await
+}
+
+/// Starts the orchestrators against the given Ethereum and Cosmos gRPC endpoints instead of the
+/// configured ones, used to run them behind a fault injecting proxy
+pub async fn start_orchestrators_with_endpoints(
+    keys: Vec<ValidatorKeys>,
+    gravity_address: EthAddress,
+    validator_out: bool,
+    orchestrator_config: GravityBridgeToolsConfig,
+    eth_node: &str,
+    cosmos_grpc: &str,
 ) {
     // used to break out of the loop early to simulate one validator
     // not running an Orchestrator
@@ -298,25 +326,22 @@ pub async fn start_orchestrators(
             k.orch_key.to_address(&ADDRESS_PREFIX).unwrap(),
             get_operator_address(k.validator_key),
         );
-        let mut grpc_client = GravityQueryClient::connect(COSMOS_NODE_GRPC.as_str())
+        let mut grpc_client = GravityQueryClient::connect(cosmos_grpc.to_string())
             .await
             .unwrap();
         let params = get_gravity_params(&mut grpc_client)
             .await
             .expect("Failed to get Gravity Bridge module parameters!");
+        let eth_node = eth_node.to_string();
+        let cosmos_grpc = cosmos_grpc.to_string();
 
         // but that will execute all the orchestrators in our test in parallel
         // by spawning to tokio's future executor
-        drop(tokio::spawn(async move {
-            let web30 =
-                gravity_utils::web30::client::Web3::new(ETH_NODE.as_str(), OPERATION_TIMEOUT);
-
-            let contact = Contact::new(
-                COSMOS_NODE_GRPC.as_str(),
-                OPERATION_TIMEOUT,
-                ADDRESS_PREFIX.as_str(),
-            )
-            .unwrap();
+        let orchestrator = tokio::spawn(async move {
+            let web30 = gravity_utils::web30::client::Web3::new(&eth_node, OPERATION_TIMEOUT);
+
+            let contact =
+                Contact::new(&cosmos_grpc, OPERATION_TIMEOUT, ADDRESS_PREFIX.as_str()).unwrap();
 
             let _ = orchestrator_main_loop(
                 k.orch_key,
@@ -327,10 +352,11 @@ pub async fn start_orchestrators(
                 gravity_address,
                 params.gravity_id,
                 get_fee(),
-                config,
+                watch::channel(config).1,
             )
             .await;
-        }));
+        });
+        ORCHESTRATORS.lock().unwrap().push(orchestrator);
         // used to break out of the loop early to simulate one validator
         // not running an orchestrator
         count += 1;
@@ -340,6 +366,21 @@ pub async fn start_orchestrators(
     }
 }
 
+/// Aborts every orchestrator started with `start_orchestrators`, returning once they have
+/// stopped
+pub async fn stop_orchestrators() {
+    let orchestrators: Vec<_> = ORCHESTRATORS.lock().unwrap().drain(..).collect();
+    if orchestrators.is_empty() {
+        return;
+    }
+    info!("Stopping {} orchestrators", orchestrators.len());
+    for orchestrator in orchestrators.iter() {
+        orchestrator.abort();
+    }
+    // an aborted task only stops at its next await point
+    let _ = join_all(orchestrators).await;
+}
+
 // Submits a false send to cosmos for every orchestrator key in keys, sending amount of erc20_address
 // tokens to cosmos_receiver, claiming to come from ethereum_sender for the given fee.
 // If a timeout is supplied, contact.send_message() will block waiting for the tx to appear